@@ -0,0 +1,111 @@
+//! Collects the `register_day!` calls of `src/aocYYYY/dayN.rs` into the `YEARS` registry, so a
+//! new day only has to be declared in its year's `mod.rs`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const MACRO: &str = "register_day!(";
+
+/// Reads `(year, day)` from the `register_day!(Solution, 2024, 16, "Title")` call in the source.
+/// The call starts a line, mentions of it in comments or further along a line don't count.
+fn registration(path: &Path, source: &str) -> Option<(u16, u8)> {
+    let mut offset = 0;
+    let start = source.split_inclusive('\n').find_map(|line| {
+        let line_start = offset;
+        offset += line.len();
+        let code = line.trim_start();
+        let call = code.strip_prefix("crate::").unwrap_or(code);
+        call.starts_with(MACRO)
+            .then(|| line_start + (line.len() - call.len()) + MACRO.len())
+    })?;
+    let args = source[start..]
+        .splitn(4, ',')
+        .map(str::trim)
+        .collect::<Vec<_>>();
+    let parse_arg = |i: usize| {
+        args.get(i)
+            .and_then(|arg| arg.parse::<u32>().ok())
+            .unwrap_or_else(|| panic!("{}: register_day! needs a year and a day", path.display()))
+    };
+    Some((parse_arg(1) as u16, parse_arg(2) as u8))
+}
+
+fn declared_modules(mod_rs: &Path) -> Vec<String> {
+    fs::read_to_string(mod_rs)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("pub mod "))
+        .map(|m| m.trim_end_matches(';').to_string())
+        .collect()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let mut years: BTreeMap<u16, BTreeMap<u8, String>> = BTreeMap::new();
+    let mut year_dirs = fs::read_dir("src")
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_dir())
+        .collect::<Vec<_>>();
+    year_dirs.sort();
+    for dir in year_dirs {
+        let dir_name = dir.file_name().unwrap().to_string_lossy().to_string();
+        let year: u16 = match dir_name.strip_prefix("aoc").and_then(|y| y.parse().ok()) {
+            Some(year) => year,
+            None => continue,
+        };
+        let modules = declared_modules(&dir.join("mod.rs"));
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let module = path.file_stem().unwrap().to_string_lossy().to_string();
+            let day: u8 = match module.strip_prefix("day").and_then(|d| d.parse().ok()) {
+                Some(day) if path.extension().is_some_and(|e| e == "rs") => day,
+                _ => continue,
+            };
+            let source = fs::read_to_string(&path).unwrap();
+            let (reg_year, reg_day) = match registration(&path, &source) {
+                Some(registered) => registered,
+                None => continue,
+            };
+            if (reg_year, reg_day) != (year, day) {
+                panic!(
+                    "{} registers {} day {}, expected {} day {}",
+                    path.display(),
+                    reg_year,
+                    reg_day,
+                    year,
+                    day
+                );
+            }
+            if !modules.contains(&module) {
+                panic!(
+                    "{} registers a day, but it's not declared in {}",
+                    path.display(),
+                    dir.join("mod.rs").display()
+                );
+            }
+            years.entry(year).or_default().insert(
+                day,
+                format!("crate::{}::{}::REGISTRATION", dir_name, module),
+            );
+        }
+    }
+
+    let mut out = String::new();
+    for (year, days) in years.iter() {
+        out += &format!("const DAYS_{}: DayMap = phf::phf_map! {{\n", year);
+        for (day, registration) in days.iter() {
+            out += &format!("    {}u8 => &{},\n", day, registration);
+        }
+        out += "};\n\n";
+    }
+    out += "pub const YEARS: phf::Map<u16, DayMap> = phf::phf_map! {\n";
+    for year in years.keys() {
+        out += &format!("    {}u16 => DAYS_{},\n", year, year);
+    }
+    out += "};\n";
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(dest, out).unwrap();
+}
@@ -1,18 +1,28 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 
 pub struct Day1;
 
+crate::register_day!(Day1, 2022, 1, "Calorie Counting", tags: &["parsing"]);
+
 impl Day for Day1 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{}", result);
+    type Input = Vec<i32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(get_elves(input).collect())
+    }
+
+    fn part1(&self, elves: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(elves).into())
     }
 
-    fn test_data(&self) -> String {
-        return "1000
+    fn part2(&self, elves: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(elves).into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "1000
 2000
 3000
 
@@ -25,25 +35,26 @@ impl Day for Day1 {
 8000
 9000
 
-10000"
-            .to_string();
+10000",
+        )
+        .part1(24000)
+        .part2(45000)]
     }
 }
 
-fn q1(input: &str) -> i32 {
-    let elf_calories = get_elves(input);
-    let max_elf: Option<i32> = elf_calories.max();
+fn q1(elves: &[i32]) -> i32 {
+    let max_elf: Option<i32> = elves.iter().copied().max();
     max_elf.unwrap_or(-1)
 }
 
-fn q2(input: &str) -> i32 {
-    let mut elf_calories = get_elves(input).collect::<Vec<_>>();
+fn q2(elves: &[i32]) -> i32 {
+    let mut elf_calories = elves.to_vec();
     elf_calories.sort_unstable();
     elf_calories.into_iter().rev().take(3).sum()
 }
 
-fn q2_alt(input: &str) -> i32 {
-    let elf_calories = get_elves(input);
+fn q2_alt(elves: &[i32]) -> i32 {
+    let elf_calories = elves.iter().copied();
     struct Top3 {
         fst: i32,
         sec: i32,
@@ -91,7 +102,7 @@ fn get_elves<'a>(input: &'a str) -> impl Iterator<Item = i32> + 'a {
             .map(|line| {
                 line.parse::<i32>()
                     .map_err(|e| {
-                        println!("Yo what {} {}", line, e);
+                        crate::trace!("Yo what {} {}", line, e);
                     })
                     .unwrap()
             })
@@ -1,17 +1,31 @@
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 
 pub struct Day10;
 
+crate::register_day!(Day10, 2022, 10, "Cathode-Ray Tube", tags: &["interpreter", "simulation"]);
+
 impl Day for Day10 {
-    fn question(&self, input: &str, question: Question) {
-        crate::aoc2022::day10::question(input);
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, instructions: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(instructions, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        return "addx 15
+    fn part2(&self, instructions: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(instructions, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "addx 15
 addx -11
 addx 6
 addx -3
@@ -156,12 +170,21 @@ addx -6
 addx -11
 noop
 noop
-noop"
-            .to_string();
+noop",
+        )
+        .part1(13140)
+        .part2(
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....",
+        )]
     }
 }
 
-enum Instruction {
+pub enum Instruction {
     Noop,
     Addx(i32),
 }
@@ -211,14 +234,15 @@ impl Instruction {
     }
 }
 
-pub fn question(input: &str) {
-    let instructions = input.lines().map(|l| l.parse::<Instruction>().unwrap());
-    let states = instructions.fold(vec![CycleState { reg_x: 1 }], |acc, i| {
-        let new_states = i.run(&acc[acc.len() - 1]);
-        vec![acc, new_states].concat()
-    });
+fn question(instructions: &[Instruction], question: Question) -> Answer {
+    let states = instructions
+        .iter()
+        .fold(vec![CycleState { reg_x: 1 }], |acc, i| {
+            let new_states = i.run(&acc[acc.len() - 1]);
+            vec![acc, new_states].concat()
+        });
     for (i, s) in states.iter().enumerate() {
-        println!(
+        crate::trace!(
             "{}: x={} signal_strength={}",
             i + 1,
             s.reg_x,
@@ -236,14 +260,19 @@ pub fn question(input: &str) {
         + signal_strengths[139]
         + signal_strengths[179]
         + signal_strengths[219];
-    println!("{}", answer);
+    if question == Question::First {
+        return answer.into();
+    }
 
-    for (i, state) in states.iter().enumerate() {
+    let mut crt = String::new();
+    // the last state is after the last cycle, there's no pixel left to draw then
+    for (i, state) in states.iter().take(240).enumerate() {
         let pixel = state.draw_pixel((i + 1) as i32);
-        let to_draw = if pixel { "#" } else { "." };
-        print!("{}", to_draw);
+        let to_draw = if pixel { '#' } else { '.' };
+        crt.push(to_draw);
         if i % 40 == 39 {
-            println!();
+            crt.push('\n');
         }
     }
+    Answer::grid(crt)
 }
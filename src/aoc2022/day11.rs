@@ -3,17 +3,31 @@ use std::num::ParseIntError;
 use std::ops::{Div, Range};
 use std::str::FromStr;
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 
 pub struct Day11;
 
+crate::register_day!(Day11, 2022, 11, "Monkey in the Middle", tags: &["simulation", "math"]);
+
 impl Day for Day11 {
-    fn question(&self, input: &str, question: Question) {
-        crate::aoc2022::day11::question(input, question);
+    type Input = Vec<Monkey>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_monkeys(input)?)
+    }
+
+    fn part1(&self, monkeys: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(monkeys, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        return "Monkey 0:
+    fn part2(&self, monkeys: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(monkeys, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
   Test: divisible by 23
@@ -39,8 +53,10 @@ Monkey 3:
   Operation: new = old + 3
   Test: divisible by 17
     If true: throw to monkey 0
-    If false: throw to monkey 1"
-            .to_string();
+    If false: throw to monkey 1",
+        )
+        .part1(10605)
+        .part2(2713310158u64)]
     }
 }
 
@@ -105,7 +121,7 @@ impl MonkeyTest {
 }
 
 #[derive(Clone, Debug)]
-struct Monkey {
+pub struct Monkey {
     n: usize,
     items: Vec<i128>,
     operation: MonkeyOperation,
@@ -241,14 +257,11 @@ impl FromStr for Monkey {
     }
 }
 
-fn read_monkeys(input: &str) -> Vec<Monkey> {
-    input
-        .split("\n\n")
-        .map(|l| l.parse().unwrap())
-        .collect_vec()
+fn read_monkeys(input: &str) -> Result<Vec<Monkey>, String> {
+    input.split("\n\n").map(|l| l.parse()).collect()
 }
 
-pub fn question(input: &str, which_question: Question) {
+fn question(monkeys: &[Monkey], which_question: Question) -> Answer {
     // let mut monkey = read_monkeys(input);
     //
     // for i in 0..20 {
@@ -262,9 +275,8 @@ pub fn question(input: &str, which_question: Question) {
     //         monkey[m].items = vec![]
     //     }
     // }
-    let monkeys = read_monkeys(input);
     let alldiv = monkeys.iter().fold(1, |acc, m| acc * m.test.divisible);
-    println!("Kozos: {}", alldiv);
+    crate::debug!("Kozos: {}", alldiv);
     let range_end = if which_question == Question::First {
         20
     } else {
@@ -292,9 +304,9 @@ pub fn question(input: &str, which_question: Question) {
             newstate[m.n].inspected += n_items;
             newstate
         });
-        println!("ROUND {}", i);
+        crate::trace!("ROUND {}", i);
         for monkey in newmonkeys.iter() {
-            println!(
+            crate::trace!(
                 "Monkey {}: {} -- insp {}",
                 monkey.n,
                 monkey.items.iter().map(ToString::to_string).join(", "),
@@ -305,15 +317,12 @@ pub fn question(input: &str, which_question: Question) {
     });
     endmonkeys.sort_by(|m, m2| m2.inspected.cmp(&m.inspected));
     for monkey in endmonkeys.iter() {
-        println!(
+        crate::trace!(
             "Monkey {}: {} -- insp {}",
             monkey.n,
             monkey.items.iter().map(ToString::to_string).join(", "),
             monkey.inspected
         )
     }
-    println!(
-        "FINAL SCORE OF MONKEY BUSINESS: {}",
-        endmonkeys[0].inspected * endmonkeys[1].inspected
-    )
+    (endmonkeys[0].inspected * endmonkeys[1].inspected).into()
 }
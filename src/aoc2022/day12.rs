@@ -1,22 +1,38 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::convert::From;
 use std::slice::Iter;
 use std::str::FromStr;
 
 pub struct Day12;
+
+crate::register_day!(Day12, 2022, 12, "Hill Climbing Algorithm", parts: 0, tags: &["grid", "search"]);
 impl Day for Day12 {
-    fn question(&self, input: &str, question: Question) {
-        todo!("Implemented in go so far")
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, _map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
+    }
+
+    fn part2(&self, _map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
     }
 
-    fn test_data(&self) -> String {
-        "Sabqponm
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Sabqponm
 abcryxxl
 accszExk
 acctuvwj
-abdefghi"
-            .to_string()
+abdefghi",
+        )
+        .part1(31)
+        .part2(29)]
     }
 }
 
@@ -26,7 +42,7 @@ struct Height {
     end: bool,
 }
 
-struct Map(Vec<Vec<Height>>);
+pub struct Map(Vec<Vec<Height>>);
 struct DistanceMap(Vec<Vec<Option<u32>>>);
 
 impl From<char> for Height {
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use serde::Deserialize;
 use serde_json::Value;
@@ -7,13 +8,26 @@ use std::convert::TryFrom;
 use std::str::FromStr;
 
 pub struct Day13;
+
+crate::register_day!(Day13, 2022, 13, "Distress Signal", tags: &["parsing"]);
 impl Day for Day13 {
-    fn question(&self, input: &str, question: Question) {
-        crate::aoc2022::day13::question(input)
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_pairs(input)?)
+    }
+
+    fn part1(&self, pairs: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(pairs, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        "[1,1,3,1,1]
+    fn part2(&self, pairs: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(pairs, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "[1,1,3,1,1]
 [1,1,5,1,1]
 
 [[1],[2,3,4]]
@@ -35,13 +49,15 @@ impl Day for Day13 {
 [[]]
 
 [1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]"
-            .to_string()
+[1,[2,[3,[4,[5,6,0]]]],8,9]",
+        )
+        .part1(13)
+        .part2(140)]
     }
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Clone)]
-enum Packet {
+pub enum Packet {
     Num(i64),
     Array(Vec<Packet>),
 }
@@ -93,35 +109,39 @@ fn well_ordered(left: &Packet, right: &Packet) -> Ordering {
     }
 }
 
-pub fn question(input: &str) {
-    let pairs: Vec<(Packet, Packet)> = input
+fn read_pairs(input: &str) -> Result<Vec<(Packet, Packet)>, String> {
+    input
         .split("\n\n")
         .map(|pair| {
-            let tuple = pair
+            let packets = pair
                 .lines()
                 .filter(|line| line.len() > 0)
                 .map(|line| {
-                    println!("{}", line);
-                    let v: Value = serde_json::from_str(line).unwrap();
-                    Packet::try_from(v).unwrap()
+                    crate::trace!("{}", line);
+                    let v: Value = serde_json::from_str(line).map_err(|e| e.to_string())?;
+                    Packet::try_from(v)
                 })
+                .collect::<Result<Vec<_>, String>>()?;
+            packets
+                .into_iter()
                 .collect_tuple()
-                .unwrap();
-            tuple
+                .ok_or(format!("{} is not a pair of packets", pair))
         })
-        .collect_vec();
+        .collect()
+}
 
+fn question(pairs: &[(Packet, Packet)], question: Question) -> Answer {
     let results = pairs.iter().map(|p| well_ordered(&p.0, &p.1)).collect_vec();
-    println!("{:?}", results);
-    println!(
-        "{}",
-        results
+    crate::debug!("{:?}", results);
+    if question == Question::First {
+        return results
             .iter()
             .enumerate()
             .filter(|(i, o)| **o == Ordering::Less)
             .map(|(n, _)| n + 1)
             .sum::<usize>()
-    );
+            .into();
+    }
 
     let mut all_packets = pairs.iter().fold(Vec::new(), |mut acc, (p1, p2)| {
         acc.push(p1.clone());
@@ -141,5 +161,5 @@ pub fn question(input: &str) {
         .iter()
         .find_position(|&p| p == &divider_2)
         .unwrap();
-    println!("Q2: {}", (idx_1 + 1) * (idx_2 + 1));
+    ((idx_1 + 1) * (idx_2 + 1)).into()
 }
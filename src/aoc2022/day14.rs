@@ -1,5 +1,7 @@
-use crate::common::day::{Day, Question};
-use crate::common::map::{Bottom, Map};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
+use crate::common::map::Grid;
+use crate::common::step;
 use itertools::Itertools;
 use serde_json::ser::CharEscape::LineFeed;
 use std::borrow::Borrow;
@@ -7,37 +9,40 @@ use std::fmt::{Display, Formatter};
 use std::str::Split;
 
 pub struct Day14;
+
+crate::register_day!(Day14, 2022, 14, "Regolith Reservoir", tags: &["grid", "simulation"]);
 impl Day for Day14 {
-    fn question(&self, input: &str, question: Question) {
-        self::question(input, question);
+    type Input = Vec<Line>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, lines: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(lines, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        "498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9"
-            .to_string()
+    fn part2(&self, lines: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(lines, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9",
+        )
+        .part1(24)
+        .part2(93)]
     }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub(crate) enum Object {
+enum Object {
     Air,
     Rock,
     Sand,
 }
 
-impl Default for Object {
-    fn default() -> Self {
-        Object::Air
-    }
-}
-
-impl Bottom for Object {
-    fn bottom() -> Self {
-        Object::Rock
-    }
-}
-
 impl Display for Object {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let symbol = match self {
@@ -49,7 +54,7 @@ impl Display for Object {
     }
 }
 
-struct Line {
+pub struct Line {
     from: (usize, usize),
     to: (usize, usize),
 }
@@ -83,7 +88,7 @@ impl Line {
             self.from.1
         }
     }
-    fn put_on_map(&self, map: &mut Map<Object>) {
+    fn put_on_map(&self, map: &mut Cave) {
         for x in self.min_x()..(self.max_x() + 1) {
             for y in self.min_y()..(self.max_y() + 1) {
                 map.set(x, y, Object::Rock);
@@ -92,15 +97,76 @@ impl Line {
     }
 }
 
-fn parse_lines(input: &str) -> Vec<Line> {
+/// The slice of the cave from `min_x` on, deep enough for sand to fall past the lowest rock, or
+/// to rest on the floor two below it.
+struct Cave {
+    grid: Grid<Object>,
+    min_x: usize,
+}
+
+impl Cave {
+    fn new(min_x: usize, max_x: usize, max_y: usize, floor: bool) -> Self {
+        let mut grid = Grid::new(max_x - min_x + 1, max_y + 3, Object::Air);
+        if floor {
+            for x in 0..grid.width() {
+                grid[(x, max_y + 2)] = Object::Rock;
+            }
+        }
+        Cave { grid, min_x }
+    }
+    fn get(&self, x: usize, y: usize) -> Object {
+        self.grid[(x - self.min_x, y)]
+    }
+    fn set(&mut self, x: usize, y: usize, obj: Object) {
+        self.grid[(x - self.min_x, y)] = obj;
+    }
+    /// Lets a unit of sand fall from `(drop_x, drop_y)`, true if it fell into the abyss.
+    fn drop(&mut self, drop_x: usize, drop_y: usize) -> bool {
+        let mut x = drop_x;
+        let mut y = drop_y;
+        loop {
+            if y + 1 >= self.grid.height() {
+                return true;
+            }
+            if self.get(x, y + 1) == Object::Air {
+                y += 1;
+            } else if self.get(x - 1, y + 1) == Object::Air {
+                x -= 1;
+                y += 1;
+            } else if self.get(x + 1, y + 1) == Object::Air {
+                x += 1;
+                y += 1;
+            } else {
+                self.set(x, y, Object::Sand);
+                break;
+            }
+        }
+        false
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+fn parse_vertex(s: &str) -> Result<(usize, usize), Error> {
+    let (a, b) = s
+        .split_once(",")
+        .ok_or_else(|| Error::new(format!("expected x,y, got \"{}\"", s)))?;
+    Ok((a.parse()?, b.parse()?))
+}
+
+fn parse_lines(input: &str) -> Result<Vec<Line>, Error> {
     let mut lines = Vec::new();
-    let input_rows = input.split("\n");
-    for row in input_rows {
+    let input_rows = input.lines();
+    for (y, row) in input_rows.enumerate() {
         let vertices: Vec<(usize, usize)> = row
             .split(" -> ")
-            .map(|s| s.split_once(",").unwrap())
-            .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-            .collect_vec();
+            .map(parse_vertex)
+            .collect::<Result<_, _>>()
+            .map_err(|e: Error| e.on_line(y))?;
         let mut last_to: Option<(usize, usize)> = None;
         for vertex in vertices {
             if let Some(l) = last_to {
@@ -112,17 +178,16 @@ fn parse_lines(input: &str) -> Vec<Line> {
             last_to = Some(vertex);
         }
     }
-    lines
+    Ok(lines)
 }
 
-pub fn question(input: &str, which_question: Question) {
-    let lines = parse_lines(input);
-    let min_x = lines.iter().min_by_key(|l| l.min_x()).unwrap().min_x() - 150;
-    let max_x = lines.iter().max_by_key(|l| l.max_x()).unwrap().max_x() + 150;
-    let max_y = lines.iter().max_by_key(|l| l.max_y()).unwrap().max_y() + 1;
-    let mut map = Map::new(min_x, max_x, max_y, which_question == Question::Second);
+fn question(lines: &[Line], which_question: Question) -> Answer {
+    let min_x = lines.iter().min_by_key(|l| l.min_x()).unwrap().min_x() - 155;
+    let max_x = lines.iter().max_by_key(|l| l.max_x()).unwrap().max_x() + 155;
+    let max_y = lines.iter().max_by_key(|l| l.max_y()).unwrap().max_y();
+    let mut map = Cave::new(min_x, max_x, max_y, which_question == Question::Second);
     lines.iter().for_each(|l| l.put_on_map(&mut map));
-    println!("{}", map);
+    crate::trace!("{}", map);
     let mut cnt: usize = 0;
 
     while !map.drop(500, 0) {
@@ -131,9 +196,12 @@ pub fn question(input: &str, which_question: Question) {
             break;
         }
         if cnt % 500 == 0 {
-            print!(".");
+            crate::debug!("{} units of sand", cnt);
         }
+        step::tick(
+            || format!("{} units of sand at rest", cnt),
+            || map.to_string(),
+        );
     }
-    println!("{}", map);
-    println!("\n{}", cnt)
+    Answer::from(cnt).with_diagnostic(map.to_string())
 }
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashSet;
@@ -8,16 +9,29 @@ use std::str::FromStr;
 
 pub struct Day15;
 
+crate::register_day!(Day15, 2022, 15, "Beacon Exclusion Zone", tags: &["geometry", "ranges"]);
+
 impl Day for Day15 {
-    fn question(&self, input: &str, question: Question) {
-        match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
+    type Input = Vec<Sensor>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, sensors: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        Ok(q1(sensors, params.get("row")?).into())
+    }
+
+    fn part2(&self, sensors: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        match q2(sensors, params.get("max")?) {
+            Some(frequency) => Ok(frequency.into()),
+            None => Err(Error::new("no distress beacon found")),
         }
     }
 
-    fn test_data(&self) -> String {
-        "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
 Sensor at x=12, y=14: closest beacon is at x=10, y=16
@@ -30,8 +44,16 @@ Sensor at x=20, y=14: closest beacon is at x=25, y=17
 Sensor at x=17, y=20: closest beacon is at x=21, y=22
 Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
-Sensor at x=20, y=1: closest beacon is at x=15, y=3"
-            .to_string()
+Sensor at x=20, y=1: closest beacon is at x=15, y=3",
+        )
+        .param("row", 10)
+        .param("max", 20)
+        .part1(26)
+        .part2(56000011)]
+    }
+
+    fn params(&self, _question: Question) -> Params {
+        Params::new().with("row", 2000000).with("max", 4000000)
     }
 }
 
@@ -68,7 +90,7 @@ fn manhattan(x1: i64, y1: i64, x2: i64, y2: i64) -> i64 {
     (x1 - x2).abs() + (y1 - y2).abs()
 }
 
-struct Sensor {
+pub struct Sensor {
     x: i64,
     y: i64,
     bx: i64,
@@ -137,13 +159,9 @@ impl Sensor {
     }
 }
 
-pub fn q2(input: &str) {
-    let sensors: Result<Vec<Sensor>, _> = input.split("\n").map(|l| l.parse()).collect();
-    let sensors = sensors.unwrap();
-
+fn q2(sensors: &[Sensor], search_space: i64) -> Option<i64> {
     let beacons: HashSet<(i64, i64)> = sensors.iter().map(|s| (s.bx, s.by)).collect();
 
-    const search_space: i64 = 4000000;
     for y in 0..search_space {
         let mut spans = sensors
             .iter()
@@ -159,9 +177,9 @@ pub fn q2(input: &str) {
             } else {
                 let ce = curr_end.unwrap();
                 if ce + 1 < sp.0 {
-                    println!("{}: {}-{}", y, ce + 1, sp.0 - 1);
+                    crate::trace!("{}: {}-{}", y, ce + 1, sp.0 - 1);
                     if !beacons.contains(&(sp.0 - 1, y)) {
-                        println!("^^ FOUND: {}", (sp.0 - 1) * 4000000 + y);
+                        return Some((sp.0 - 1) * 4000000 + y);
                     }
                     curr_end = Some(sp.1);
                 } else if ce < sp.1 {
@@ -170,13 +188,10 @@ pub fn q2(input: &str) {
             }
         }
     }
+    None
 }
 
-pub fn q1(input: &str) {
-    let sensors: Result<Vec<Sensor>, _> = input.split("\n").map(|l| l.parse()).collect();
-    let sensors = sensors.unwrap();
-    let target_y = 2000000;
-    // let target_y = 10;
+fn q1(sensors: &[Sensor], target_y: i64) -> i64 {
     let mut spans = sensors
         .iter()
         .filter_map(|s| s.line_coverage(target_y))
@@ -197,9 +212,13 @@ pub fn q1(input: &str) {
             current_span_ends: None,
         },
         |acc, span| {
-            println!(
+            crate::trace!(
                 "{}-{} current state: {:?}-{:?} {},",
-                span.0, span.1, acc.current_span_begins, acc.current_span_ends, acc.sum
+                span.0,
+                span.1,
+                acc.current_span_begins,
+                acc.current_span_ends,
+                acc.sum
             );
             if acc.current_span_begins == None {
                 let a = Accum {
@@ -207,7 +226,7 @@ pub fn q1(input: &str) {
                     current_span_ends: Some(span.1),
                     sum: acc.sum,
                 };
-                println!("{:?}", a);
+                crate::trace!("{:?}", a);
                 a
             } else if span.0 > acc.current_span_ends.unwrap() {
                 Accum {
@@ -229,5 +248,5 @@ pub fn q1(input: &str) {
     );
     let sum =
         result.sum + (result.current_span_ends.unwrap() - result.current_span_begins.unwrap() + 1);
-    println!("{}", sum);
+    sum
 }
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashMap;
@@ -7,13 +8,26 @@ use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day16;
+
+crate::register_day!(Day16, 2022, 16, "Proboscidea Volcanium", parts: 0, tags: &["graph", "search", "dp"]);
 impl Day for Day16 {
-    fn question(&self, input: &str, question: Question) {
-        question_fail1(input);
+    type Input = ValveMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        read_valve_map(input)
+    }
+
+    fn part1(&self, valve_map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question_fail1(valve_map))
     }
 
-    fn test_data(&self) -> String {
-        "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+    fn part2(&self, valve_map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question_fail1(valve_map))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
 Valve CC has flow rate=2; tunnels lead to valves DD, BB
 Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
@@ -22,15 +36,17 @@ Valve FF has flow rate=0; tunnels lead to valves EE, GG
 Valve GG has flow rate=0; tunnels lead to valves FF, HH
 Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II"
-            .to_string()
+Valve JJ has flow rate=21; tunnel leads to valve II",
+        )
+        .part1(1651)
+        .part2(1707)]
     }
 }
 
 type ValveId = String;
 type ValveMap = HashMap<ValveId, Valve>;
 
-struct Valve {
+pub struct Valve {
     id: ValveId,
     tunnels: Vec<ValveId>,
     flow: u32,
@@ -40,7 +56,7 @@ impl FromStr for Valve {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        println!("{}", s);
+        crate::trace!("{}", s);
         let re =
             Regex::new(r"Valve ([A-Z]*) has flow rate=([0-9]*); tunnels? leads? to valves? (.*)")
                 .unwrap();
@@ -83,18 +99,16 @@ impl Valve {
     }
 }
 
-fn read_valve_map(input: &str) -> ValveMap {
-    input
-        .split("\n")
-        .map(|l| l.parse::<Valve>().unwrap())
+fn read_valve_map(input: &str) -> Result<ValveMap, Error> {
+    Ok(parse_each_line::<Valve>(input)?
+        .into_iter()
         .map(|v| (v.get_id(), v))
-        .collect()
+        .collect())
 }
 
 pub fn question(input: &str) {}
 
-pub fn question_fail1(input: &str) {
-    let valve_map = read_valve_map(input);
+fn question_fail1(valve_map: &ValveMap) -> Answer {
     let mut state = HashMap::<&ValveId, ValveState>::new();
     for vid in valve_map.keys() {
         state.insert(
@@ -166,7 +180,7 @@ pub fn question_fail1(input: &str) {
                 );
             }
             if vid == "AA" {
-                println!(
+                crate::trace!(
                     "{:?} {:?}",
                     new_state.get(vid).unwrap().releases,
                     new_state.get(vid).unwrap().sum()
@@ -174,14 +188,14 @@ pub fn question_fail1(input: &str) {
                 let ns = new_state.get(vid).unwrap();
                 let opt =
                     ns.sum() + ((30 - ns.releases.len()) as u32) * ns.releases.last().unwrap_or(&0);
-                println!("OPT: {}", opt);
+                crate::trace!("OPT: {}", opt);
                 // println!("  {:?} {:?}", current_place.releases, current_place.releases.iter().map(|r| r+my_valve.flow).collect_vec())
             }
         }
         state = new_state;
     }
     let aa_state = state.get(&"AA".to_string()).unwrap();
-    println!(
+    crate::debug!(
         "{}",
         aa_state
             .debug
@@ -191,9 +205,8 @@ pub fn question_fail1(input: &str) {
             .join("\n\n")
     );
 
-    println!(
-        "For AA the state is open={}; sum={}",
-        aa_state.open.join(","),
-        aa_state.sum()
-    );
+    Answer::from(aa_state.sum()).with_diagnostic(format!(
+        "For AA the state is open={}",
+        aa_state.open.join(",")
+    ))
 }
@@ -1,7 +1,9 @@
-use crate::common::day::{Day, Question};
+use crate::common::cycle;
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
+use crate::common::geom::{Direction, Point2};
+use crate::common::step;
 use std::fmt::{Display, Formatter};
-use std::io::{stdin, Read};
-use std::ops::Add;
 
 const WIDTH: usize = 7;
 const ROW_MEMORY: usize = 13;
@@ -9,47 +11,49 @@ const ROW_MEMORY: usize = 13;
 type Bitmap = u128;
 
 pub struct Day17;
+
+crate::register_day!(Day17, 2022, 17, "Pyroclastic Flow", tags: &["simulation", "cycle"]);
 impl Day for Day17 {
-    fn question(&self, input: &str, question: Question) {
-        self::question(
-            input,
-            match question {
-                Question::First => 2022,
-                Question::Second => 1000000000000,
-            },
-        );
+    type Input = Vec<Direction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input
+            .trim()
+            .chars()
+            .enumerate()
+            .map(|(x, ch)| match ch {
+                '<' => Ok(Direction::Left),
+                '>' => Ok(Direction::Right),
+                _ => Err(Error::at(0, x, format!("'{}' is not a jet", ch))),
+            })
+            .collect()
     }
 
-    fn test_data(&self) -> String {
-        ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>".to_string()
+    fn part1(&self, jets: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        Ok(question(jets, params.get("rounds")?))
     }
-}
 
-#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug)]
-struct Coordinate {
-    x: i8,
-    y: i128,
-}
+    fn part2(&self, jets: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        Ok(question(jets, params.get("rounds")?))
+    }
 
-impl Add for Coordinate {
-    type Output = Coordinate;
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>")
+            .part1(3068)
+            .part2(1514285714288u64)]
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Coordinate {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    fn params(&self, question: Question) -> Params {
+        match question {
+            Question::First => Params::new().with("rounds", 2022),
+            Question::Second => Params::new().with("rounds", 1000000000000u64),
         }
     }
 }
 
-impl Coordinate {
-    fn index(&self) -> usize {
-        ((self.y - 1) as usize) * WIDTH + ((self.x - 1) as usize)
-    }
-    fn bitmap(&self, bottom_y: i128) -> Bitmap {
-        // println!("y={} botty={}", self.y, bottom_y);
-        (1 << (WIDTH as i8 - self.x)) << ((self.y - bottom_y) * WIDTH as i128)
-    }
+/// The bit of the pixel at `c` in a [`Field`] whose lowest row is `bottom_y`.
+fn pixel_bitmap(c: Point2<i128>, bottom_y: i128) -> Bitmap {
+    (1 << (WIDTH as i128 - c.x)) << ((c.y - bottom_y) * WIDTH as i128)
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -64,56 +68,56 @@ enum TetrisShape {
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 struct Tetris {
     shape: TetrisShape,
-    bottom_left: Coordinate,
+    bottom_left: Point2<i128>,
 }
 
 impl Tetris {
-    fn pixels(&self) -> Vec<Coordinate> {
+    fn pixels(&self) -> Vec<Point2<i128>> {
         match self.shape {
             TetrisShape::HLine => vec![
                 self.bottom_left,
-                self.bottom_left + Coordinate { x: 1, y: 0 },
-                self.bottom_left + Coordinate { x: 2, y: 0 },
-                self.bottom_left + Coordinate { x: 3, y: 0 },
+                self.bottom_left + Point2::new(1, 0),
+                self.bottom_left + Point2::new(2, 0),
+                self.bottom_left + Point2::new(3, 0),
             ],
             TetrisShape::Plus => vec![
-                self.bottom_left + Coordinate { x: 0, y: 1 },
-                self.bottom_left + Coordinate { x: 1, y: 1 },
-                self.bottom_left + Coordinate { x: 2, y: 1 },
-                self.bottom_left + Coordinate { x: 1, y: 0 },
-                self.bottom_left + Coordinate { x: 1, y: 2 },
+                self.bottom_left + Point2::new(0, 1),
+                self.bottom_left + Point2::new(1, 1),
+                self.bottom_left + Point2::new(2, 1),
+                self.bottom_left + Point2::new(1, 0),
+                self.bottom_left + Point2::new(1, 2),
             ],
             TetrisShape::El => vec![
                 self.bottom_left,
-                self.bottom_left + Coordinate { x: 1, y: 0 },
-                self.bottom_left + Coordinate { x: 2, y: 0 },
-                self.bottom_left + Coordinate { x: 2, y: 1 },
-                self.bottom_left + Coordinate { x: 2, y: 2 },
+                self.bottom_left + Point2::new(1, 0),
+                self.bottom_left + Point2::new(2, 0),
+                self.bottom_left + Point2::new(2, 1),
+                self.bottom_left + Point2::new(2, 2),
             ],
             TetrisShape::VLine => vec![
                 self.bottom_left,
-                self.bottom_left + Coordinate { x: 0, y: 1 },
-                self.bottom_left + Coordinate { x: 0, y: 2 },
-                self.bottom_left + Coordinate { x: 0, y: 3 },
+                self.bottom_left + Point2::new(0, 1),
+                self.bottom_left + Point2::new(0, 2),
+                self.bottom_left + Point2::new(0, 3),
             ],
             TetrisShape::Box => vec![
                 self.bottom_left,
-                self.bottom_left + Coordinate { x: 1, y: 0 },
-                self.bottom_left + Coordinate { x: 0, y: 1 },
-                self.bottom_left + Coordinate { x: 1, y: 1 },
+                self.bottom_left + Point2::new(1, 0),
+                self.bottom_left + Point2::new(0, 1),
+                self.bottom_left + Point2::new(1, 1),
             ],
         }
     }
-    fn top(&self) -> Coordinate {
+    fn top(&self) -> Point2<i128> {
         match self.shape {
-            TetrisShape::HLine => self.bottom_left + Coordinate { x: 3, y: 0 },
-            TetrisShape::Plus => self.bottom_left + Coordinate { x: 1, y: 2 },
-            TetrisShape::El => self.bottom_left + Coordinate { x: 2, y: 2 },
-            TetrisShape::VLine => self.bottom_left + Coordinate { x: 0, y: 3 },
-            TetrisShape::Box => self.bottom_left + Coordinate { x: 1, y: 1 },
+            TetrisShape::HLine => self.bottom_left + Point2::new(3, 0),
+            TetrisShape::Plus => self.bottom_left + Point2::new(1, 2),
+            TetrisShape::El => self.bottom_left + Point2::new(2, 2),
+            TetrisShape::VLine => self.bottom_left + Point2::new(0, 3),
+            TetrisShape::Box => self.bottom_left + Point2::new(1, 1),
         }
     }
-    fn shift(&self, by: Coordinate, field: &Field) -> Option<Self> {
+    fn shift(&self, by: Point2<i128>, field: &Field) -> Option<Self> {
         let new_tetris = Tetris {
             bottom_left: self.bottom_left + by,
             shape: self.shape,
@@ -139,7 +143,7 @@ impl Tetris {
         };
         bm
     }
-    fn width(&self) -> i8 {
+    fn width(&self) -> i128 {
         match self.shape {
             TetrisShape::HLine => 4,
             TetrisShape::Plus => 3,
@@ -152,6 +156,7 @@ impl Tetris {
 
 // x: 012345678 -- 0 and 8 are walls
 // y: grows upwards
+#[derive(Copy, Clone)]
 struct Field {
     field: Bitmap,
     bottom_y: i128,
@@ -164,15 +169,15 @@ impl Field {
             bottom_y: 1,
         }
     }
-    fn get(&self, c: &Coordinate) -> bool {
+    fn get(&self, c: &Point2<i128>) -> bool {
         if c.x == 0 || c.x as usize == WIDTH + 1 || c.y == 0 {
             return true;
         }
-        c.bitmap(self.bottom_y) & self.field != 0
+        pixel_bitmap(*c, self.bottom_y) & self.field != 0
     }
     fn collides(&self, t: &Tetris) -> bool {
         if t.bottom_left.x == 0
-            || t.bottom_left.x + t.width() > (WIDTH + 1) as i8
+            || t.bottom_left.x + t.width() > (WIDTH + 1) as i128
             || t.bottom_left.y == 0
         {
             return true;
@@ -186,9 +191,11 @@ impl Field {
             let (extrarows, _) = self.field.overflowing_shr((WIDTH * ROW_MEMORY) as u32);
             let fl: f32 = extrarows as f32;
             let extrarows = fl.log(WIDTH as f32).floor() as u32;
-            println!(
+            crate::trace!(
                 "Shifting: by={} er={} filed={:#10b}",
-                self.bottom_y, extrarows, self.field
+                self.bottom_y,
+                extrarows,
+                self.field
             );
             self.field >>= extrarows * (WIDTH as u32);
             self.bottom_y += extrarows as i128;
@@ -205,108 +212,129 @@ impl Field {
     }
 }
 
-fn print_state(field: &Field, tetris: Option<Tetris>) {
+fn draw_state(field: &Field, tetris: Option<Tetris>) -> String {
+    let mut drawing = String::new();
     let top_draw = tetris.map(|t| t.top().y + 1).unwrap_or(field.top_y() + 1);
     for y in (field.bottom_y..(top_draw + 1)).rev() {
         for x in 0..9 {
             if tetris
-                .map(|t| t.pixels().iter().any(|px| px == &Coordinate { x, y }))
+                .map(|t| t.pixels().iter().any(|px| px == &Point2::new(x, y)))
                 .unwrap_or(false)
             {
-                print!("@");
+                drawing.push('@');
             } else if x == 0 {
-                print!("|");
+                drawing.push('|');
             } else if x == 8 {
                 if y % 5 == 0 {
-                    println!("| {}", y)
+                    drawing += &format!("| {}\n", y);
                 } else {
-                    println!("|");
+                    drawing += "|\n";
                 }
             } else if y == 0 {
-                print!("-");
-            } else if field.get(&Coordinate { x, y }) {
-                print!("#");
+                drawing.push('-');
+            } else if field.get(&Point2::new(x, y)) {
+                drawing.push('#');
             } else {
-                print!(".");
+                drawing.push('.');
             }
         }
     }
+    drawing
 }
 
-pub fn question(input: &str, rounds: i128) {
-    let mut field = Field::new();
-    let mut active_tetris = None;
-    let mut shape_rotation: i128 = 0;
-    const DEBUG_LEN: i128 = 5;
-    for ch in input.chars().cycle() {
-        if active_tetris.is_none() {
-            active_tetris = Some(Tetris {
-                shape: match shape_rotation % 5 {
-                    0 => TetrisShape::HLine,
-                    1 => TetrisShape::Plus,
-                    2 => TetrisShape::El,
-                    3 => TetrisShape::VLine,
-                    4 => TetrisShape::Box,
-                    _ => panic!("Mathematical hilarity"), // ??
-                },
-                bottom_left: Coordinate {
-                    x: 3,
-                    y: field.top_y() + 4,
-                },
-            });
-            if shape_rotation < DEBUG_LEN {
-                println!("\nRock {} begins falling:", shape_rotation);
-                print_state(&field, active_tetris);
-            }
-        }
-        let h_move = match ch {
-            '<' => Coordinate { x: -1, y: 0 },
-            '>' => Coordinate { x: 1, y: 0 },
-            _ => panic!("Non-<> char: {}", ch),
-        };
-        if let Some(after_h_move) = active_tetris.unwrap().shift(h_move, &field) {
-            active_tetris = Some(after_h_move);
-            if shape_rotation < DEBUG_LEN {
-                println!("\nJet of gas pushes rock to side:");
-                print_state(&field, active_tetris);
-                pause()
-            }
-        } else if shape_rotation < DEBUG_LEN {
-            println!("\nJet of gas pushes rock to side, but nothing happens");
-            print_state(&field, active_tetris);
-            pause()
+/// The chamber after some rocks came to rest, and where the jets are at.
+#[derive(Copy, Clone)]
+struct Chamber {
+    field: Field,
+    rocks: i128,
+    jet: usize,
+}
+
+impl Chamber {
+    /// What repeats: the next rock, the next jet and the shape of the top rows. The height keeps
+    /// growing.
+    fn key(&self) -> (i128, usize, Bitmap, i128) {
+        (
+            self.rocks % 5,
+            self.jet,
+            self.field.field,
+            self.field.top_y() - self.field.bottom_y,
+        )
+    }
+}
+
+fn drop_rock(jets: &[Direction], chamber: &Chamber) -> Chamber {
+    let mut field = chamber.field;
+    let mut jet = chamber.jet;
+    let mut active_tetris = Tetris {
+        shape: match chamber.rocks % 5 {
+            0 => TetrisShape::HLine,
+            1 => TetrisShape::Plus,
+            2 => TetrisShape::El,
+            3 => TetrisShape::VLine,
+            4 => TetrisShape::Box,
+            _ => panic!("Mathematical hilarity"), // ??
+        },
+        bottom_left: Point2::new(3, field.top_y() + 4),
+    };
+    step::tick(
+        || format!("Rock {} begins falling", chamber.rocks),
+        || draw_state(&field, Some(active_tetris)),
+    );
+    loop {
+        let h_move = Point2::new(jets[jet].delta().0 as i128, 0);
+        jet = (jet + 1) % jets.len();
+        if let Some(after_h_move) = active_tetris.shift(h_move, &field) {
+            active_tetris = after_h_move;
+            step::tick(
+                || "Jet of gas pushes rock to side".to_string(),
+                || draw_state(&field, Some(active_tetris)),
+            );
+        } else {
+            step::tick(
+                || "Jet of gas pushes rock to side, but nothing happens".to_string(),
+                || draw_state(&field, Some(active_tetris)),
+            );
         }
-        let drop = Coordinate { x: 0, y: -1 };
-        let after_drop = active_tetris.unwrap().shift(drop, &field);
-        if after_drop.is_some() {
+        let drop = Point2::new(0, -1);
+        if let Some(after_drop) = active_tetris.shift(drop, &field) {
             active_tetris = after_drop;
-            if shape_rotation < DEBUG_LEN {
-                println!("\nRock falls:");
-                print_state(&field, active_tetris);
-                pause()
-            }
+            step::tick(
+                || "Rock falls".to_string(),
+                || draw_state(&field, Some(active_tetris)),
+            );
         } else {
-            field.set_in_stone(active_tetris.unwrap());
-            active_tetris = None;
-            shape_rotation += 1;
-            if shape_rotation == rounds {
-                break;
-            }
-            println!("Top now at {}: {}", shape_rotation, field.top_y());
-            if shape_rotation <= DEBUG_LEN {
-                println!("\n***TOP NOW AT {}", field.top_y());
-                println!("Rock falls 1 unit, causing it to come to rest:");
-                print_state(&field, active_tetris);
-                pause()
-            }
+            field.set_in_stone(active_tetris);
+            crate::debug!("Top now at {}: {}", chamber.rocks + 1, field.top_y());
+            step::tick(
+                || format!("Rock comes to rest, top now at {}", field.top_y()),
+                || draw_state(&field, None),
+            );
+            return Chamber {
+                field,
+                rocks: chamber.rocks + 1,
+                jet,
+            };
         }
     }
-    // println!("\n\n\nEND =================================================");
-    // print_state(&field, None);
-    println!("Top at end: {}", field.top_y());
 }
-//3097 too low
-fn pause() {
-    // stdin().read(&mut [0]).unwrap();
-    // print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+
+/// The rocks fall in a loop sooner or later, the height after `rounds` of them is extrapolated
+/// from one round of it.
+pub fn question(jets: &[Direction], rounds: i128) -> Answer {
+    let start = Chamber {
+        field: Field::new(),
+        rocks: 0,
+        jet: 0,
+    };
+    let history = cycle::find_by_key(start, |chamber| drop_rock(jets, chamber), Chamber::key);
+    crate::debug!(
+        "Rocks fall in a loop of {} after {}",
+        history.cycle.length,
+        history.cycle.prefix
+    );
+    history
+        .extrapolate(rounds as usize, |chamber| chamber.field.top_y())
+        .into()
 }
+//3097 too low
@@ -1,17 +1,27 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
 
 pub struct Day18;
+
+crate::register_day!(Day18, 2022, 18, "Boiling Boulders", parts: 1, tags: &["grid", "search"]);
 impl Day for Day18 {
-    fn question(&self, input: &str, question: Question) {
-        if question == Question::First {
-            bruteforce::q1(input)
-        } else {
-            todo!("Not solved yet")
-        }
+    type Input = Vec<bruteforce::Cube>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, cubes: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(bruteforce::q1(cubes).into())
+    }
+
+    fn part2(&self, _cubes: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
     }
 
-    fn test_data(&self) -> String {
-        "2,2,2
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "2,2,2
 1,2,2
 3,2,2
 2,1,2
@@ -23,8 +33,10 @@ impl Day for Day18 {
 1,2,5
 3,2,5
 2,1,5
-2,3,5"
-            .to_string()
+2,3,5",
+        )
+        .part1(64)
+        .part2(58)]
     }
 }
 
@@ -33,7 +45,7 @@ mod bruteforce {
     use std::str::FromStr;
 
     #[derive(Copy, Clone)]
-    struct Cube {
+    pub struct Cube {
         x: u32,
         y: u32,
         z: u32,
@@ -100,16 +112,14 @@ mod bruteforce {
         }
     }
 
-    pub fn q1(input: &str) {
-        let cubes: Result<Vec<Cube>, _> = input.split("\n").map(|l| l.parse()).collect();
-        let mut cubes = cubes.unwrap();
+    pub fn q1(cubes: &[Cube]) -> u32 {
+        let mut cubes = cubes.to_vec();
         for i in 0..cubes.len() {
             for j in 0..cubes.len() {
                 let c = cubes[j];
                 cubes[i].process_neighbor(&c);
             }
         }
-        let sum: u32 = cubes.iter().map(|c| c.sides()).sum();
-        println!("{}", sum);
+        cubes.iter().map(|c| c.sides()).sum()
     }
 }
@@ -1,7 +1,8 @@
 use std::num::ParseIntError;
 use std::str::{FromStr, Split};
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashSet;
@@ -11,20 +12,32 @@ pub const DEMO_INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay
 Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
 pub struct Day19;
 
+crate::register_day!(Day19, 2022, 19, "Not Enough Minerals", tags: &["search", "dp"]);
+
 impl Day for Day19 {
-    fn question(&self, input: &str, question: Question) {
-        self::question(input, question);
+    type Input = Vec<Blueprint>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, blueprints: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(blueprints, Question::First))
+    }
+
+    fn part2(&self, blueprints: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(blueprints, Question::Second))
     }
 
-    fn test_data(&self) -> String {
-        DEMO_INPUT.to_string()
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(DEMO_INPUT).part1(33).part2(3472)]
     }
 }
 
 type Cost = u32;
 type Quantity = u32;
 
-struct Blueprint {
+pub struct Blueprint {
     id: usize,
     ore_ore: Cost,
     clay_ore: Cost,
@@ -202,7 +215,7 @@ impl Blueprint {
                 geode: 0,
             }],
             |acc, i| {
-                println!("Bp {}: {}: {:?} states", self.id, i, acc.len());
+                crate::trace!("Bp {}: {}: {:?} states", self.id, i, acc.len());
                 let options = acc.iter().flat_map(|s| {
                     let no_build = s.step();
                     let new_ore = if s.ore >= self.ore_ore {
@@ -264,11 +277,10 @@ impl Blueprint {
     }
 }
 
-pub fn question(input: &str, question: Question) {
-    let blueprints: Result<Vec<Blueprint>, _> = input.lines().map(|l| l.parse()).collect();
+fn question(blueprints: &[Blueprint], question: Question) -> Answer {
     let blueprints = match question {
-        Question::First => blueprints.unwrap(),
-        Question::Second => blueprints.unwrap().into_iter().take(3).collect_vec(),
+        Question::First => blueprints,
+        Question::Second => &blueprints[..blueprints.len().min(3)],
     };
     let result: Quantity = match question {
         Question::First => blueprints
@@ -277,5 +289,5 @@ pub fn question(input: &str, question: Question) {
             .sum(),
         Question::Second => blueprints.iter().map(|b| b.run_blueprint(32)).product(),
     };
-    println!("{}", result);
+    result.into()
 }
@@ -1,22 +1,34 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
 use std::str::FromStr;
 
 pub struct Day2;
 
+crate::register_day!(Day2, 2022, 2, "Rock Paper Scissors", tags: &["parsing"]);
+
 impl Day for Day2 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{}", result);
+    type Input = Vec<Round>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, rounds: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(rounds).into())
+    }
+
+    fn part2(&self, rounds: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(rounds).into())
     }
 
-    fn test_data(&self) -> String {
-        return "A Y
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "A Y
 B X
-C Z"
-        .to_string();
+C Z",
+        )
+        .part1(15)
+        .part2(12)]
     }
 }
 
@@ -93,26 +105,42 @@ impl FromStr for RPSResult {
     }
 }
 
-pub fn q1(input: &str) -> i32 {
-    let plays = input.lines().map(|l| {
-        let pair: Vec<RPS> = l.split(" ").map(|s| s.parse::<RPS>().unwrap()).collect();
-        let battle_score = pair[1].play(&pair[0]).score();
-        let own_score = pair[1].score();
-        battle_score + own_score
-    });
-    plays.sum()
+/// The second column is a play in question 1 and a result in question 2, so we keep both.
+pub struct Round {
+    enemy: RPS,
+    mine: RPS,
+    goal: RPSResult,
 }
 
-pub fn q2(input: &str) -> i32 {
-    let plays = input.lines().map(|l| {
+impl FromStr for Round {
+    type Err = String;
+
+    fn from_str(l: &str) -> Result<Self, Self::Err> {
         let str_pair: Vec<&str> = l.split(" ").collect();
         if str_pair.len() != 2 {
-            panic!("weird input: {}", l)
+            return Err(format!("weird input: {}", l));
         }
-        let enemy: RPS = str_pair[0].parse().unwrap();
-        let my_goal: RPSResult = str_pair[1].parse().unwrap();
-        let my_play = my_goal.select_play(&enemy);
-        my_goal.score() + my_play.score()
+        Ok(Round {
+            enemy: str_pair[0].parse()?,
+            mine: str_pair[1].parse()?,
+            goal: str_pair[1].parse()?,
+        })
+    }
+}
+
+pub fn q1(rounds: &[Round]) -> i32 {
+    let plays = rounds.iter().map(|r| {
+        let battle_score = r.mine.play(&r.enemy).score();
+        let own_score = r.mine.score();
+        battle_score + own_score
+    });
+    plays.sum()
+}
+
+pub fn q2(rounds: &[Round]) -> i32 {
+    let plays = rounds.iter().map(|r| {
+        let my_play = r.goal.select_play(&r.enemy);
+        r.goal.score() + my_play.score()
     });
     plays.sum()
 }
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 
 const DECRYPTION_CONSTANT: i128 = 811589153;
@@ -11,13 +12,25 @@ pub const DEMO_INPUT: &str = "1
 4";
 
 pub struct Day20;
+
+crate::register_day!(Day20, 2022, 20, "Grove Positioning System", tags: &["simulation"]);
 impl Day for Day20 {
-    fn question(&self, input: &str, question: Question) {
-        self::question(input, question);
+    type Input = Vec<i128>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, numbers: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(numbers, Question::First))
+    }
+
+    fn part2(&self, numbers: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(numbers, Question::Second))
     }
 
-    fn test_data(&self) -> String {
-        DEMO_INPUT.to_string()
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(DEMO_INPUT).part1(3).part2(1623178306u64)]
     }
 }
 
@@ -52,7 +65,7 @@ impl Vecnum for Vec<Num> {
     fn print_state(&self) {
         let mut nums_to_sort = self.iter().collect_vec();
         nums_to_sort.sort_by_key(|n| n.pos());
-        println!(
+        crate::trace!(
             "{}",
             nums_to_sort
                 .iter()
@@ -61,7 +74,7 @@ impl Vecnum for Vec<Num> {
         );
     }
     fn get_nth(&self, n: usize) -> i128 {
-        let null_pos = self.iter().find(|n| n.value == 0).unwrap();
+        let null_pos = self.iter().find(|n| n.orig_value == 0).unwrap();
         let pos = null_pos.pos() + (n as i32);
         let pos = pos % (self.len() as i32);
         self.iter()
@@ -71,12 +84,11 @@ impl Vecnum for Vec<Num> {
     }
 }
 
-pub fn question(input: &str, question: Question) {
-    let mut nums: Vec<Num> = input
-        .lines()
-        .map(|l| l.parse().unwrap())
+fn question(numbers: &[i128], question: Question) -> Answer {
+    let mut nums: Vec<Num> = numbers
+        .iter()
         .enumerate()
-        .map(|(i, v)| Num::new(i, v))
+        .map(|(i, &v)| Num::new(i, v))
         .collect_vec();
     if question == Question::Second {
         for i in 0..nums.len() {
@@ -93,7 +105,7 @@ pub fn question(input: &str, question: Question) {
             let old_pos = nums[i].pos() as i128;
             let mut new_pos = (old_pos as i128) + nums[i].value;
             // println!("A{}", new_pos);
-            while new_pos > nums.len() as i128 {
+            while new_pos >= nums.len() as i128 {
                 new_pos -= (nums.len() - 1) as i128;
             }
             while new_pos <= 0 {
@@ -117,15 +129,15 @@ pub fn question(input: &str, question: Question) {
             }
             // nums.print_state();
         }
-        println!("Done with round {}", round)
+        crate::trace!("Done with round {}", round)
     }
     let thou = nums.get_nth(1000);
-    println!("1000: {}", thou);
     let twothou = nums.get_nth(2000);
-    println!("2000: {}", twothou);
     let threethou = nums.get_nth(3000);
-    println!("3000: {}", threethou);
-    println!("RESULT: {}", thou + twothou + threethou);
+    Answer::from(thou + twothou + threethou)
+        .with_diagnostic(format!("1000: {}", thou))
+        .with_diagnostic(format!("2000: {}", twothou))
+        .with_diagnostic(format!("3000: {}", threethou))
     // 5743 too lo
     // 11387407405743 too hi
     // 5100837826605 ain ri
@@ -1,5 +1,5 @@
-use crate::common::day::{Day, Question};
-use itertools::Itertools;
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
@@ -11,35 +11,45 @@ type Value = i128;
 type MonkeyMap = HashMap<String, Monkey>;
 pub struct Solution;
 
+crate::register_day!(Solution, 2022, 21, "Monkey Math", tags: &["tree", "math"]);
+
 impl Day for Solution {
-    fn question(&self, input: &str, question: Question) {
-        let mut monkeys: MonkeyMap = input
-            .lines()
-            .map(|l| l.parse().unwrap())
-            .map(|m: Monkey| (m.name.clone(), m))
-            .collect();
-        match question {
-            Question::First => {
-                let root = monkeys.get("root").unwrap();
-                let res = root.evaluate(&monkeys, Question::First);
-                println!("{}", res)
-            }
-            Question::Second => {
-                let root = monkeys.get("root").unwrap();
-                let root_left = monkeys.get(&root.operation.as_ref().unwrap().left).unwrap();
-                let root_right = monkeys
-                    .get(&root.operation.as_ref().unwrap().right)
-                    .unwrap();
-                let left_res = root_left.evaluate(&monkeys, Question::Second);
-                let right_res = root_right.evaluate(&monkeys, Question::Second);
-                println!("{}", left_res);
-                println!("{}", right_res);
-            }
-        }
+    type Input = MonkeyMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_each_line::<Monkey>(input)?
+            .into_iter()
+            .map(|m| (m.name.clone(), m))
+            .collect())
+    }
+
+    fn part1(&self, monkeys: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        let root = monkeys.get("root").unwrap();
+        let res = root.evaluate(monkeys, Question::First);
+        Ok(Answer::number(res.constant as i128))
+    }
+
+    fn part2(&self, monkeys: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        let root = monkeys.get("root").unwrap();
+        let root_left = monkeys.get(&root.operation.as_ref().unwrap().left).unwrap();
+        let root_right = monkeys
+            .get(&root.operation.as_ref().unwrap().right)
+            .unwrap();
+        let left_res = root_left.evaluate(monkeys, Question::Second);
+        let right_res = root_right.evaluate(monkeys, Question::Second);
+        let equation = format!("{} = {}", left_res, right_res);
+        let (with_humn, other) = if left_res.x != 0.0 {
+            (left_res, right_res)
+        } else {
+            (right_res, left_res)
+        };
+        let humn = (other.constant - with_humn.constant) / with_humn.x;
+        Ok(Answer::number(humn.round() as i128).with_diagnostic(equation))
     }
 
-    fn test_data(&self) -> String {
-        "root: pppw + sjmn
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "root: pppw + sjmn
 dbpl: 5
 cczh: sllz + lgvd
 zczc: 2
@@ -53,8 +63,10 @@ sllz: 4
 pppw: cczh / lfqf
 lgvd: ljgn * ptdq
 drzm: hmdt - zczc
-hmdt: 32"
-            .to_string()
+hmdt: 32",
+        )
+        .part1(152)
+        .part2(301)]
     }
 }
 
@@ -115,7 +127,7 @@ impl Mul for ResultValue {
             x: self.x * rhs.constant + self.constant * rhs.x,
         };
         if self.x > 0.0 || rhs.x > 0.0 {
-            println!("*** {}*{} = {}", self, rhs, res);
+            crate::trace!("*** {}*{} = {}", self, rhs, res);
         }
         if self.x > 0.0 && rhs.x > 0.0 {
             panic!("x^2 lost: {}*{} became {}", self, rhs, res);
@@ -141,7 +153,7 @@ impl Div for ResultValue {
             _ => panic!("doesn't occur thankfully"),
         };
         if self.x > 0.0 || rhs.x > 0.0 {
-            println!("/// {} DIV {}  = {}", self, rhs, res);
+            crate::trace!("/// {} DIV {}  = {}", self, rhs, res);
         }
         res
     }
@@ -159,7 +171,7 @@ struct MonkeyOperation {
     op: MonkeyOperationOperator,
     right: String,
 }
-struct Monkey {
+pub struct Monkey {
     name: String,
     value: Option<Value>,
     operation: Option<MonkeyOperation>,
@@ -211,7 +223,7 @@ impl MonkeyOperation {
 impl Monkey {
     fn evaluate(&self, monkeys: &MonkeyMap, question: Question) -> ResultValue {
         if question == Question::Second && self.name == "humn" {
-            println!("gotteem");
+            crate::trace!("gotteem");
             return ResultValue {
                 constant: 0.0,
                 x: 1.0,
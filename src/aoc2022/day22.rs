@@ -1,15 +1,31 @@
-use crate::common::day::{Day, Question};
-use itertools::Itertools;
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_grid, Error};
+use crate::common::geom::Direction;
+use std::convert::TryFrom;
+use std::str::FromStr;
 
 pub struct Solution;
 
+crate::register_day!(Solution, 2022, 22, "Monkey Map", parts: 1, tags: &["grid", "simulation"]);
+
 impl Day for Solution {
-    fn question(&self, input: &str, question: Question) {
-        todo!()
+    type Input = Notes;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part1(&self, notes: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(password(notes)?.into())
+    }
+
+    fn part2(&self, _notes: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
     }
 
-    fn test_data(&self) -> String {
-        "        ...#
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "        ...#
         .#..
         #...
         ....
@@ -22,8 +38,9 @@ impl Day for Solution {
         .#......
         ......#.
 
-10R5L5R10L4R5L5"
-            .to_string()
+10R5L5R10L4R5L5",
+        )
+        .part1(6032)]
     }
 }
 
@@ -34,28 +51,22 @@ enum Pixel {
     Nothing,
 }
 
-#[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq)]
-enum Facing {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq)]
 struct Location {
-    facing: Facing,
+    facing: Direction,
     x: usize,
     y: usize,
 }
 
-impl From<char> for Pixel {
-    fn from(ch: char) -> Self {
+impl TryFrom<char> for Pixel {
+    type Error = String;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            ' ' => Pixel::Nothing,
-            '#' => Pixel::Wall,
-            '.' => Pixel::Open,
-            _ => panic!("Nonexistent map tile {}", ch),
+            ' ' => Ok(Pixel::Nothing),
+            '#' => Ok(Pixel::Wall),
+            '.' => Ok(Pixel::Open),
+            _ => Err(format!("'{}' is not a map tile", ch)),
         }
     }
 }
@@ -64,72 +75,130 @@ struct Map {
     map: Vec<Vec<Pixel>>,
 }
 
-impl Map {
-    fn new(s: &str) -> Self {
-        let ls = s.lines().collect::<Vec<_>>();
-        let map = ls
-            .iter()
-            .map(|l| l.chars().map(|ch| ch.into()).collect_vec())
-            .collect_vec();
-        Self { map }
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Move {
+    Forward(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+/// The map and the path to follow on it.
+pub struct Notes {
+    map: Map,
+    path: Vec<Move>,
+}
+
+impl FromStr for Notes {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (map, path) = s
+            .split_once("\n\n")
+            .ok_or("no empty line between the map and the path")?;
+        let path_line = map.lines().count() + 1;
+        let mut moves = Vec::new();
+        let mut steps = None;
+        for (x, ch) in path.trim_end().chars().enumerate() {
+            let turn = match ch {
+                'L' => Move::TurnLeft,
+                'R' => Move::TurnRight,
+                _ => {
+                    let digit = ch.to_digit(10).ok_or_else(|| {
+                        Error::at(path_line, x, format!("'{}' is not a move", ch))
+                    })?;
+                    steps = Some(steps.unwrap_or(0) * 10 + digit as usize);
+                    continue;
+                }
+            };
+            moves.extend(steps.take().map(Move::Forward));
+            moves.push(turn);
+        }
+        moves.extend(steps.map(Move::Forward));
+        Ok(Notes {
+            map: map.parse()?,
+            path: moves,
+        })
     }
-    fn first_open_column(&self) -> usize {
-        self.map[0]
-            .iter()
-            .enumerate()
-            .find(|(i, p)| **p == Pixel::Open)
-            .map(|(i, _)| i)
-            .unwrap()
+}
+
+impl FromStr for Map {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Map {
+            map: parse_grid(s)?,
+        })
     }
-    fn max_y(&self) -> usize {
-        self.map.len() - 1
+}
+
+impl Map {
+    /// What's at `(x, y)`, nothing off the board. The rows are as long as their last tile.
+    fn get(&self, x: isize, y: isize) -> Pixel {
+        if x < 0 || y < 0 {
+            return Pixel::Nothing;
+        }
+        self.map
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or(Pixel::Nothing)
     }
-    fn max_x(&self) -> usize {
-        self.map[0].len() - 1
+    /// The leftmost open tile of the top row, facing right.
+    fn start(&self) -> Result<Location, Error> {
+        let x = self
+            .map
+            .first()
+            .and_then(|row| row.iter().position(|&p| p == Pixel::Open))
+            .ok_or("no open tile on the top row")?;
+        Ok(Location {
+            facing: Direction::Right,
+            x,
+            y: 0,
+        })
     }
+    /// One tile ahead, off the edge of the board it's the tile on the far side of it. Staying put
+    /// when there's a wall in the way.
     fn move_one(&self, loc: Location) -> Location {
-        let mut new_xy = (loc.x, loc.y);
-        loop {
-            new_xy = match loc.facing {
-                Facing::Up => {
-                    if loc.y == 0 {
-                        (loc.x, self.max_y())
-                    } else {
-                        (loc.x, loc.y - 1)
-                    }
-                }
-                Facing::Down => {
-                    if loc.y == self.max_y() {
-                        (loc.x, 0)
-                    } else {
-                        (loc.x, loc.y + 1)
-                    }
-                }
-                Facing::Left => {
-                    if loc.x == 0 {
-                        (self.max_x(), loc.y)
-                    } else {
-                        (loc.x - 1, loc.y)
-                    }
-                }
-                Facing::Right => {
-                    if loc.x == self.max_x() {
-                        (0, loc.y)
-                    } else {
-                        (loc.x + 1, loc.y)
-                    }
-                }
-            };
-            if self.map[new_xy.0][new_xy.1] == Pixel::Wall {
-                return loc;
+        let (dx, dy) = loc.facing.delta();
+        let (mut x, mut y) = (loc.x as isize + dx, loc.y as isize + dy);
+        if self.get(x, y) == Pixel::Nothing {
+            x = loc.x as isize;
+            y = loc.y as isize;
+            while self.get(x - dx, y - dy) != Pixel::Nothing {
+                x -= dx;
+                y -= dy;
             }
-            if self.map[new_xy.0][new_xy.1] == Pixel::Open {
-                return Location {
-                    facing: loc.facing,
-                    x: new_xy.0,
-                    y: new_xy.1,
-                };
+        }
+        match self.get(x, y) {
+            Pixel::Wall => loc,
+            _ => Location {
+                facing: loc.facing,
+                x: x as usize,
+                y: y as usize,
+            },
+        }
+    }
+}
+
+/// Where the path ends, as `1000 * row + 4 * column + facing` counting from 1.
+fn password(notes: &Notes) -> Result<usize, Error> {
+    let mut loc = notes.map.start()?;
+    for &step in notes.path.iter() {
+        match step {
+            Move::Forward(n) => {
+                for _ in 0..n {
+                    loc = notes.map.move_one(loc);
+                }
             }
+            Move::TurnLeft => loc.facing = loc.facing.turn_left(),
+            Move::TurnRight => loc.facing = loc.facing.turn_right(),
         }
     }
+    let facing = match loc.facing {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
+    };
+    Ok(1000 * (loc.y + 1) + 4 * (loc.x + 1) + facing)
 }
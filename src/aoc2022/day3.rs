@@ -1,26 +1,38 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::{Chunk, Itertools};
 use std::collections::HashMap;
 
 pub struct Day3;
 
+crate::register_day!(Day3, 2022, 3, "Rucksack Reorganization", tags: &["parsing"]);
+
 impl Day for Day3 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{}", result);
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn part1(&self, rucksacks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(rucksacks).into())
+    }
+
+    fn part2(&self, rucksacks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(rucksacks).into())
     }
 
-    fn test_data(&self) -> String {
-        return "vJrwpWtwJgWrhcsFMMfFFhFp
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw"
-            .to_string();
+CrZsJsPPZsGzwwsLwLmpwMDw",
+        )
+        .part1(157)
+        .part2(70)]
     }
 }
 
@@ -47,9 +59,9 @@ fn char_value(c: char) -> u8 {
     }
 }
 
-pub fn q1(input: &str) -> i32 {
-    let rucksack_letters = input
-        .lines()
+pub fn q1(rucksacks: &[String]) -> i32 {
+    let rucksack_letters = rucksacks
+        .iter()
         .map(|l| {
             let compartments = l.split_at(l.len() / 2);
             let (lm1, lm2) = (lettermap(compartments.0), lettermap(compartments.1));
@@ -74,26 +86,26 @@ pub fn q1(input: &str) -> i32 {
         .sum()
 }
 
-pub fn q2(input: &str) -> i32 {
-    let zeros = input
-        .lines()
+pub fn q2(rucksacks: &[String]) -> i32 {
+    let zeros = rucksacks
+        .iter()
         .enumerate()
         .filter(|(n, _)| n % 3 == 0)
         .map(|(_, x)| x);
-    let ones = input
-        .lines()
+    let ones = rucksacks
+        .iter()
         .enumerate()
         .filter(|(n, _)| n % 3 == 1)
         .map(|(_, x)| x);
-    let twos = input
-        .lines()
+    let twos = rucksacks
+        .iter()
         .enumerate()
         .filter(|(n, _)| n % 3 == 2)
         .map(|(_, x)| x);
     let groups = zeros.zip(ones).zip(twos).map(|((x, y), z)| [x, y, z]);
     groups
         .map(|gr| {
-            let lettermaps = gr.iter().map(|s| lettermap(s.clone()));
+            let lettermaps = gr.iter().map(|s| lettermap(s));
             let slm = sumlettermap(lettermaps);
             slm
         })
@@ -101,9 +113,9 @@ pub fn q2(input: &str) -> i32 {
         .map(|c| char_value(c) as i32)
         .sum()
 }
-pub fn q2_itertools(input: &str) -> i32 {
-    input
-        .lines()
+pub fn q2_itertools(rucksacks: &[String]) -> i32 {
+    rucksacks
+        .iter()
         .chunks(3)
         .into_iter()
         .map(|chunk: itertools::Chunk<_>| sumlettermap(chunk.map(|s| lettermap(s))))
@@ -1,29 +1,40 @@
 use std::borrow::Borrow;
-use std::error::Error;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
 
 pub struct Day4;
 
+crate::register_day!(Day4, 2022, 4, "Camp Cleanup", tags: &["ranges"]);
+
 impl Day for Day4 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{}", result);
+    type Input = Vec<ElfPair>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, elfpairs: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(elfpairs).into())
+    }
+
+    fn part2(&self, elfpairs: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(elfpairs).into())
     }
 
-    fn test_data(&self) -> String {
-        return "2-4,6-8
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "2-4,6-8
 2-3,4-5
 5-7,7-9
 2-8,3-7
 6-6,4-6
-2-6,4-8"
-            .to_string();
+2-6,4-8",
+        )
+        .part1(2)
+        .part2(4)]
     }
 }
 
@@ -51,7 +62,7 @@ impl FromStr for Elf {
     }
 }
 
-struct ElfPair(Elf, Elf);
+pub struct ElfPair(Elf, Elf);
 
 impl FromStr for ElfPair {
     type Err = String;
@@ -78,12 +89,10 @@ impl ElfPair {
     }
 }
 
-pub fn q1(input: &str) -> usize {
-    let elfpairs = input.lines().map(|s| s.parse::<ElfPair>().unwrap());
-    elfpairs.filter(|ep| ep.overlap()).count()
+fn q1(elfpairs: &[ElfPair]) -> usize {
+    elfpairs.iter().filter(|ep| ep.overlap()).count()
 }
 
-pub fn q2(input: &str) -> usize {
-    let elfpairs = input.lines().map(|s| s.parse::<ElfPair>().unwrap());
-    elfpairs.filter(|ep| ep.overlap_at_all()).count()
+fn q2(elfpairs: &[ElfPair]) -> usize {
+    elfpairs.iter().filter(|ep| ep.overlap_at_all()).count()
 }
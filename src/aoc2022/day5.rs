@@ -1,18 +1,31 @@
 use itertools::Itertools;
 use std::str::FromStr;
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 
 pub struct Day5;
 
+crate::register_day!(Day5, 2022, 5, "Supply Stacks", tags: &["parsing", "simulation"]);
+
 impl Day for Day5 {
-    fn question(&self, input: &str, question: Question) {
-        let result = run_question(input, question);
-        println!("{}", result);
+    type Input = Cargo;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        read_cargo(input)
+    }
+
+    fn part1(&self, cargo: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(run_question(cargo, Question::First).into())
     }
 
-    fn test_data(&self) -> String {
-        return "    [D]
+    fn part2(&self, cargo: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(run_question(cargo, Question::Second).into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "    [D]
 [N] [C]
 [Z] [M] [P]
  1   2   3
@@ -20,8 +33,10 @@ impl Day for Day5 {
 move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
-move 1 from 1 to 2"
-            .to_string();
+move 1 from 1 to 2",
+        )
+        .part1("CMZ")
+        .part2("MCD")]
     }
 }
 
@@ -40,10 +55,11 @@ fn lines_to_stacks(lines: Vec<Vec<Option<char>>>) -> Vec<Vec<char>> {
     lls
 }
 
-fn parse_cratelines(lines: Vec<&str>) -> Vec<Vec<Option<char>>> {
+fn parse_cratelines(lines: Vec<&str>) -> Result<Vec<Vec<Option<char>>>, Error> {
     lines
         .into_iter()
-        .map(|line| {
+        .enumerate()
+        .map(|(y, line)| {
             let mut vec = Vec::new();
             for (i, ch) in line.chars().enumerate() {
                 // goodbye functional :'(
@@ -51,11 +67,11 @@ fn parse_cratelines(lines: Vec<&str>) -> Vec<Vec<Option<char>>> {
                     vec.push(match ch {
                         x if x.is_uppercase() => Some(x),
                         ' ' => None,
-                        _ => panic!("What {}", ch),
+                        _ => return Err(Error::at(y, i, format!("'{}' isn't a crate", ch))),
                     });
                 }
             }
-            vec
+            Ok(vec)
         })
         .collect()
 }
@@ -68,39 +84,65 @@ struct Move {
 }
 
 impl FromStr for Move {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut words = s.split(" ");
-        words.next();
-        let how_many = words.next().unwrap().parse().unwrap();
-        words.next();
-        let from = words.next().unwrap().parse().unwrap();
-        words.next();
-        let to = words.next().unwrap().parse().unwrap();
-        Ok(Move { how_many, from, to })
+        match s.split(' ').collect_vec()[..] {
+            ["move", how_many, "from", from, "to", to] => Ok(Move {
+                how_many: how_many.parse()?,
+                from: from.parse()?,
+                to: to.parse()?,
+            }),
+            _ => Err(Error::new(format!(
+                "expected \"move N from A to B\", got \"{}\"",
+                s
+            ))),
+        }
     }
 }
 
-pub fn run_question(input: &str, question: Question) -> String {
+pub struct Cargo {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+}
+
+fn read_cargo(input: &str) -> Result<Cargo, Error> {
     let mut lines_iter = input.lines();
     let cratelines: Vec<_> = lines_iter
         .take_while_ref(|l| l.trim().starts_with("["))
         .collect();
     let n_lines = cratelines.len();
-    println!("{:#?}", n_lines);
-    let parsed = parse_cratelines(cratelines);
-    let mut stacks = lines_to_stacks(parsed);
+    crate::debug!("{:#?}", n_lines);
+    let parsed = parse_cratelines(cratelines)?;
+    let stacks = lines_to_stacks(parsed);
     let indices = lines_iter.nth(0);
-    println!("{:#?}", indices);
+    crate::debug!("{:#?}", indices);
     lines_iter.next();
-    let moves: Vec<Move> = lines_iter.map(|s| s.parse().unwrap()).collect();
-    println!("{:#?}", moves);
+    let moves: Vec<Move> = lines_iter
+        .enumerate()
+        .map(|(i, s)| {
+            // the moves start after the stack numbers and a blank line
+            let line = n_lines + 2 + i;
+            let mv: Move = s.parse().map_err(|e: Error| e.on_line(line))?;
+            for stack in [mv.from, mv.to].iter() {
+                if *stack == 0 || *stack > stacks.len() {
+                    return Err(Error::new(format!("there's no stack {}", stack)).on_line(line));
+                }
+            }
+            Ok(mv)
+        })
+        .collect::<Result<_, Error>>()?;
+    crate::debug!("{:#?}", moves);
+    Ok(Cargo { stacks, moves })
+}
+
+fn run_question(cargo: &Cargo, question: Question) -> String {
+    let mut stacks = cargo.stacks.clone();
     let run_fn = match question {
         Question::First => run_move_q1,
         Question::Second => run_move_q2,
     };
-    for mv in moves {
+    for mv in cargo.moves.iter() {
         run_fn(&mut stacks, mv)
     }
     stacks
@@ -109,15 +151,15 @@ pub fn run_question(input: &str, question: Question) -> String {
         .join("")
 }
 
-fn run_move_q1(stacks: &mut Vec<Vec<char>>, mv: Move) {
+fn run_move_q1(stacks: &mut Vec<Vec<char>>, mv: &Move) {
     for i in 0..(mv.how_many) {
         let ch = stacks[mv.from - 1].pop().unwrap();
         stacks[mv.to - 1].push(ch);
-        println!(".")
+        crate::trace!(".")
     }
 }
 
-fn run_move_q2(stacks: &mut Vec<Vec<char>>, mv: Move) {
+fn run_move_q2(stacks: &mut Vec<Vec<char>>, mv: &Move) {
     // im too tired
     let mut to_push = Vec::new();
     for i in 0..(mv.how_many) {
@@ -1,26 +1,38 @@
 use std::collections::{HashMap, VecDeque};
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 
 pub struct Day6;
 
+crate::register_day!(Day6, 2022, 6, "Tuning Trouble", tags: &["strings"]);
+
 impl Day for Day6 {
-    fn question(&self, input: &str, question: Question) {
-        let msg_length = match question {
-            Question::First => 4usize,
-            Question::Second => 14usize,
-        };
-        let result = run_question(input, msg_length);
-        let result = match result {
-            None => "no message found".to_string(),
-            Some(r) => format!("{}", r),
-        };
-        println!("{}", result);
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        find_marker(input, 4)
     }
 
-    fn test_data(&self) -> String {
-        return "bvwbjplbgvbhsrlpgdmjqwftvncz".to_string();
+    fn part2(&self, input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        find_marker(input, 14)
     }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
+            .part1(5)
+            .part2(23)]
+    }
+}
+
+fn find_marker(input: &str, msg_length: usize) -> Result<Answer, Error> {
+    run_question(input, msg_length)
+        .map(Answer::from)
+        .ok_or_else(|| Error::new(format!("no {} different characters in a row", msg_length)))
 }
 
 fn run_question(input: &str, msg_length: usize) -> Option<usize> {
@@ -1,28 +1,53 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 
 pub struct Day7;
 
+crate::register_day!(Day7, 2022, 7, "No Space Left On Device", tags: &["tree", "parsing"]);
+
 impl Day for Day7 {
-    fn question(&self, input: &str, question: Question) {
-        run_question(input);
+    type Input = Directory;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        read_tree(input)
     }
 
-    fn test_data(&self) -> String {
-        return "- / (dir)
-  - a (dir)
-    - e (dir)
-      - i (file, size=584)
-    - f (file, size=29116)
-    - g (file, size=2557)
-    - h.lst (file, size=62596)
-  - b.txt (file, size=14848514)
-  - c.dat (file, size=8504156)
-  - d (dir)
-    - j (file, size=4060174)
-    - d.log (file, size=8033020)
-    - d.ext (file, size=5626152)
-    - k (file, size=7214296)"
-            .to_string();
+    fn part1(&self, root: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(run_question(root, Question::First))
+    }
+
+    fn part2(&self, root: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(run_question(root, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k",
+        )
+        .part1(95437)
+        .part2(24933642)]
     }
 }
 
@@ -43,7 +68,7 @@ struct File {
 }
 
 #[derive(Debug)]
-struct Directory {
+pub struct Directory {
     name: String,
     entries: Vec<Entry>,
     calculated_size: Option<u32>,
@@ -60,14 +85,15 @@ impl Directory {
     fn push(&mut self, e: Entry) {
         self.entries.push(e);
     }
-    fn push_to(&mut self, e: Entry, path: &str) {
+    /// Errors if a directory on the way to `path` was never listed.
+    fn push_to(&mut self, e: Entry, path: &str) -> Result<(), String> {
         if path == "/" || path == "" {
             self.push(e);
-            return;
+            return Ok(());
         }
         let sub_dir = path.split("/").skip(1).take(1).join("");
         let rest = format!("/{}", path.split("/").skip(2).join("/"));
-        println!("A {} {} {:?}", sub_dir, rest, e);
+        crate::trace!("A {} {} {:?}", sub_dir, rest, e);
         let d = self
             .entries
             .iter_mut()
@@ -80,9 +106,9 @@ impl Directory {
                 None
             })
             .find(Option::is_some)
-            .unwrap()
-            .unwrap();
-        d.push_to(e, &rest);
+            .flatten()
+            .ok_or_else(|| format!("no directory {} on the way to {}", sub_dir, path))?;
+        d.push_to(e, &rest)
     }
     fn calculate_size(&mut self) -> u32 {
         if let Some(n) = self.calculated_size {
@@ -96,7 +122,7 @@ impl Directory {
             }
         }
         self.calculated_size = Some(sum);
-        println!("{}", sum);
+        crate::trace!("{}", sum);
         sum
     }
     fn collect_filter(&self, pred: &impl Fn(u32) -> bool) -> Vec<u32> {
@@ -113,16 +139,16 @@ impl Directory {
     }
 }
 
-pub fn run_question(input: &str) {
+fn read_tree(input: &str) -> Result<Directory, Error> {
     let mut root = Directory::new("/");
     let mut curr_path = "/".to_string();
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         if line.starts_with("$ cd") {
             let newcwdname = line.strip_prefix("$ cd ").unwrap();
             if newcwdname == "/" {
                 curr_path = "/".to_string();
             } else if newcwdname == ".." {
-                println!("PRE CD.. {}", curr_path);
+                crate::trace!("PRE CD.. {}", curr_path);
                 let parts = curr_path.split("/").collect::<Vec<_>>();
                 curr_path = parts.iter().take(parts.len() - 1).join("/");
             } else {
@@ -132,7 +158,7 @@ pub fn run_question(input: &str) {
                     newcwdname
                 )
             }
-            println!("new path: {}", curr_path)
+            crate::trace!("new path: {}", curr_path)
         // } else {
         //     // let mut cwd_ref= cwd.entries.iter().find(|i| {
         //     //     match i {
@@ -157,24 +183,35 @@ pub fn run_question(input: &str) {
         } else if line.starts_with("dir") {
             let new_dir_name = line.strip_prefix("dir ").unwrap();
             let new_dir = Directory::new(new_dir_name);
-            root.push_to(EntryDirectory(new_dir), &curr_path);
+            root.push_to(EntryDirectory(new_dir), &curr_path)
+                .map_err(|e| Error::new(e).on_line(i))?;
         } else {
-            println!("{}", line);
-            let parts: Vec<_> = line.split(" ").collect();
-            let size = parts[0].parse().unwrap();
+            crate::trace!("{}", line);
+            let (size, name) = line.split_once(" ").ok_or_else(|| {
+                Error::new(format!("expected a size and a name, got \"{}\"", line)).on_line(i)
+            })?;
+            let size = size.parse().map_err(|e| Error::from(e).on_line(i))?;
             let f = File {
-                name: parts[1].to_string(),
+                name: name.to_string(),
                 size,
             };
-            root.push_to(EntryFile(f), &curr_path);
+            root.push_to(EntryFile(f), &curr_path)
+                .map_err(|e| Error::new(e).on_line(i))?;
         }
     }
     root.calculate_size();
+    Ok(root)
+}
+
+fn run_question(root: &Directory, question: Question) -> Answer {
     let sum = root.collect_filter(&|n| n <= 100000);
     let free_now = 70000000 - root.calculated_size.unwrap();
     let need = 30000000 - free_now;
     let best = root.collect_filter(&|n| n >= need);
     let best = best.into_iter().min();
-    println!("{}", sum.iter().sum::<u32>());
-    println!("{} {} {:?}", free_now, need, best);
+    match question {
+        Question::First => sum.iter().sum::<u32>().into(),
+        Question::Second => Answer::from(best.unwrap())
+            .with_diagnostic(format!("free: {} need: {}", free_now, need)),
+    }
 }
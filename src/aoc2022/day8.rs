@@ -1,31 +1,52 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 
 pub struct Day8;
 
+crate::register_day!(Day8, 2022, 8, "Treetop Tree House", tags: &["grid"]);
+
 impl Day for Day8 {
-    fn question(&self, input: &str, question: Question) {
-        crate::aoc2022::day8::question(input);
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(y, l)| {
+                l.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        c.to_digit(10)
+                            .map(|d| d as i32)
+                            .ok_or_else(|| Error::at(y, x, format!("{} is not a tree height", c)))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn part1(&self, tree_map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(tree_map, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        return "30373
+    fn part2(&self, tree_map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(tree_map, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "30373
 25512
 65332
 33549
-35390"
-            .to_string();
+35390",
+        )
+        .part1(21)
+        .part2(8)]
     }
 }
 
-pub fn question(input: &str) {
-    let tree_map = input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+pub fn question(tree_map: &Vec<Vec<i32>>, question: Question) -> Answer {
     let mut seen = tree_map
         .iter()
         .map(|row| row.iter().map(|_| false).collect::<Vec<_>>())
@@ -77,11 +98,13 @@ pub fn question(input: &str) {
             }
         }
     }
-    println!("{:?}", seen);
+    crate::debug!("{:?}", seen);
     let count = seen.iter().fold(0, |acc, row| {
         acc + row.iter().fold(0, |acc_r, seen| acc_r + *seen as i32)
     });
-    println!("{}", count);
+    if question == Question::First {
+        return count.into();
+    }
 
     //scenic :/
 
@@ -91,7 +114,7 @@ pub fn question(input: &str) {
         .map(|(i, row)| {
             row.iter()
                 .enumerate()
-                .map(|(j, _)| scenic_score(&tree_map, i, j))
+                .map(|(j, _)| scenic_score(tree_map, i, j))
                 .max()
                 .unwrap()
         })
@@ -104,7 +127,7 @@ pub fn question(input: &str) {
     //     }
     // }
 
-    println!("Scenic max: {}", scenic_max);
+    scenic_max.into()
 }
 
 fn scenic_score(tree_map: &Vec<Vec<i32>>, pivot_row: usize, pivot_col: usize) -> u32 {
@@ -121,30 +144,24 @@ fn scenic_score(tree_map: &Vec<Vec<i32>>, pivot_row: usize, pivot_col: usize) ->
             break;
         }
     }
-    println!("right: {}", score_part);
-    if score_part != 0 {
-        score *= score_part;
-        score_part = 0;
-    }
+    crate::trace!("right: {}", score_part);
+    score *= score_part;
+    score_part = 0;
     //left
-    println!("I am {} tall", tree_height);
-    if pivot_col != 0 {
-        for col in (0..pivot_col).rev() {
-            score_part += 1;
-            println!(
-                "Checking if {} is taller than me...",
-                tree_map[pivot_row][col]
-            );
-            if tree_map[pivot_row][col] >= tree_height {
-                break;
-            }
-        }
-        println!("left: {}", score_part);
-        if score_part != 0 {
-            score *= score_part;
-            score_part = 0;
+    crate::trace!("I am {} tall", tree_height);
+    for col in (0..pivot_col).rev() {
+        score_part += 1;
+        crate::trace!(
+            "Checking if {} is taller than me...",
+            tree_map[pivot_row][col]
+        );
+        if tree_map[pivot_row][col] >= tree_height {
+            break;
         }
     }
+    crate::trace!("left: {}", score_part);
+    score *= score_part;
+    score_part = 0;
     //down
     for row in (pivot_row + 1)..n_rows {
         score_part += 1;
@@ -152,29 +169,20 @@ fn scenic_score(tree_map: &Vec<Vec<i32>>, pivot_row: usize, pivot_col: usize) ->
             break;
         }
     }
-    println!("down: {}", score_part);
-
-    if score_part != 0 {
-        score *= score_part;
-        score_part = 0;
-    }
+    crate::trace!("down: {}", score_part);
+    score *= score_part;
+    score_part = 0;
     //up
-    if pivot_row != 0 {
-        for row in (0..pivot_row).rev() {
-            score_part += 1;
-            if tree_map[row][pivot_col] >= tree_height {
-                break;
-            }
-        }
-        println!("up: {}", score_part);
-
-        if score_part != 0 {
-            score *= score_part;
-            score_part = 0;
+    for row in (0..pivot_row).rev() {
+        score_part += 1;
+        if tree_map[row][pivot_col] >= tree_height {
+            break;
         }
     }
+    crate::trace!("up: {}", score_part);
+    score *= score_part;
 
-    println!("Score for {} {}: {}", pivot_row, pivot_col, score);
+    crate::trace!("Score for {} {}: {}", pivot_row, pivot_col, score);
 
     score
 }
@@ -3,25 +3,42 @@ use std::num::ParseIntError;
 use std::ops::{AddAssign, Sub};
 use std::str::FromStr;
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
+use crate::common::geom::Direction;
 
 pub struct Day9;
 
+crate::register_day!(Day9, 2022, 9, "Rope Bridge", tags: &["grid", "simulation"]);
+
 impl Day for Day9 {
-    fn question(&self, input: &str, question: Question) {
-        crate::aoc2022::day9::question(input);
+    type Input = Vec<InputMoves>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, moves: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(moves, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        return "R 4
+    fn part2(&self, moves: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(question(moves, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "R 4
 U 4
 L 3
 D 1
 R 4
 D 1
 L 5
-R 2"
-        .to_string();
+R 2",
+        )
+        .part1(13)
+        .part2(1)]
     }
 }
 
@@ -52,12 +69,9 @@ impl Position {
         Position { row: 0, col: 0 }
     }
     fn update(&mut self, d: Direction) {
-        match d {
-            Direction::Right => self.col += 1,
-            Direction::Left => self.col -= 1,
-            Direction::Up => self.row -= 1,
-            Direction::Down => self.row += 1,
-        }
+        let (dx, dy) = d.delta();
+        self.col += dx as i32;
+        self.row += dy as i32;
     }
 }
 
@@ -115,30 +129,18 @@ fn new_tail(head: &Position, tail: &Position) -> Position {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum Direction {
-    Right,
-    Left,
-    Up,
-    Down,
-}
-
-impl FromStr for Direction {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use crate::aoc2022::day9::Direction::*;
-        match s {
-            "R" => Ok(Right),
-            "L" => Ok(Left),
-            "U" => Ok(Up),
-            "D" => Ok(Down),
-            _ => Err(format!("{} is unknown direction", s)),
-        }
+fn parse_direction(s: &str) -> Result<Direction, String> {
+    match s {
+        "R" => Ok(Direction::Right),
+        "L" => Ok(Direction::Left),
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        _ => Err(format!("{} is unknown direction", s)),
     }
 }
 
-struct InputMoves {
+#[derive(Copy, Clone)]
+pub struct InputMoves {
     direction: Direction,
     amount: i32,
 }
@@ -149,13 +151,13 @@ impl FromStr for InputMoves {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts = s.split_whitespace().collect::<Vec<_>>();
         Ok(InputMoves {
-            direction: parts[0].parse()?,
+            direction: parse_direction(parts[0])?,
             amount: parts[1].parse().map_err(|e: ParseIntError| e.to_string())?,
         })
     }
 }
 
-struct InputMovesIterator {
+pub struct InputMovesIterator {
     moves: Box<InputMoves>,
     next_idx: i32,
 }
@@ -187,12 +189,15 @@ impl IntoIterator for InputMoves {
     }
 }
 
-pub fn question(input: &str) {
-    let moves = input.split("\n").map(|l| l.parse::<InputMoves>().unwrap());
-    const nodes_n: usize = 10;
-    let mut nodes = [Position::zero(); nodes_n];
+fn question(moves: &[InputMoves], question: Question) -> Answer {
+    const NODES_MAX: usize = 10;
+    let nodes_n = match question {
+        Question::First => 2,
+        Question::Second => NODES_MAX,
+    };
+    let mut nodes = [Position::zero(); NODES_MAX];
     let mut seen_map = HashSet::new();
-    for mvs in moves {
+    for mvs in moves.iter().copied() {
         for dir in mvs {
             nodes[0].update(dir);
             // println!("\nMOVE: {:?}", dir);
@@ -222,19 +227,21 @@ pub fn question(input: &str) {
     let max_row = seen_map.iter().max_by_key(|&&k| k.row).unwrap().row;
     let min_col = seen_map.iter().min_by_key(|&&k| k.col).unwrap().col;
     let max_col = seen_map.iter().max_by_key(|&&k| k.col).unwrap().col;
-    println!(
-        "Dimensions: row [{},{}] column [{},{}]",
-        min_row, max_row, min_col, max_col
-    );
+    let mut visited = String::new();
     for i in min_row..max_row + 1 {
         for j in min_col..max_col + 1 {
             if seen_map.contains(&Position { row: i, col: j }) {
-                print!("#");
+                visited.push('#');
             } else {
-                print!(".");
+                visited.push('.');
             }
         }
-        print!("\n");
+        visited.push('\n');
     }
-    println!("{}", seen_map.len());
+    Answer::from(seen_map.len())
+        .with_diagnostic(format!(
+            "Dimensions: row [{},{}] column [{},{}]",
+            min_row, max_row, min_col, max_col
+        ))
+        .with_diagnostic(visited)
 }
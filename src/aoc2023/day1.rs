@@ -1,27 +1,49 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::str::{Chars, MatchIndices};
 
 pub struct Day1;
 
+crate::register_day!(Day1, 2023, 1, "Trebuchet?!", tags: &["strings"]);
+
 impl Day for Day1 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{}", result);
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(input).into())
     }
 
-    fn test_data(&self) -> String {
-        return "two1nine
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::named(
+                "digits",
+                "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+            )
+            .part1(142),
+            Example::named(
+                "spelled",
+                "two1nine
 eightwothree
 abcone2threexyz
 xtwone3four
 4nineeightseven2
 zoneight234
-7pqrstsixteen"
-            .to_string();
+7pqrstsixteen",
+            )
+            .part2(281),
+        ]
     }
 }
 
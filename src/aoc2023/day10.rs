@@ -1,25 +1,39 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
+use crate::common::map::{self, Grid, Position};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
-use std::str::FromStr;
 
 pub struct Day10;
 
+crate::register_day!(Day10, 2023, 10, "Pipe Maze", tags: &["grid", "geometry"]);
+
 impl Day for Day10 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res)
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(map).map(|(steps, _)| steps)?.into())
+    }
+
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(map).map(|(_, inside)| inside)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "..F7.
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "..F7.
 .FJ|.
 SJ.L7
 |F--J
-LJ..."
-            .to_string()
+LJ...",
+        )
+        .part1(8)]
     }
 }
 
@@ -49,6 +63,12 @@ impl Coord {
     }
 }
 
+impl Position for Coord {
+    fn coord(self) -> Option<map::Coord> {
+        Some((self.x(), self.y()))
+    }
+}
+
 impl Display for Coord {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "x={} y={}", self.x(), self.y())
@@ -117,25 +137,7 @@ impl Tile {
     }
 }
 
-pub struct Map(Vec<Vec<Tile>>);
-
-impl FromStr for Map {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows: Result<Vec<Vec<Tile>>, String> = s
-            .lines()
-            .map(|line| line.chars().map(|ch| Tile::try_from(ch)).collect())
-            .collect();
-        rows.map(|v| Map(v))
-    }
-}
-
-impl From<Vec<Vec<Tile>>> for Map {
-    fn from(x: Vec<Vec<Tile>>) -> Self {
-        Map(x)
-    }
-}
+pub type Map = Grid<Tile>;
 
 #[derive(Eq, PartialEq)]
 enum WalkThruState {
@@ -147,76 +149,35 @@ enum WalkThruState {
     OutsideAfterF,
 }
 
-impl Map {
-    fn find_starter(&self) -> Coord {
-        let fnd = self
-            .0
-            .iter()
-            .enumerate()
-            .map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .find(|(j, col)| **col == Tile::Starter)
-                    .map(|(j, _)| (i, j))
-            })
-            .filter(|row| row.is_some())
-            .next();
-        let (x, y) = fnd.unwrap().unwrap();
-        Coord(x, y)
-    }
-    fn get(&self, coord: &Coord) -> Option<Tile> {
-        if coord.0 < 0 || coord.1 < 0 {
-            None
-        } else if coord.0 >= self.height() || coord.1 >= self.width() {
-            None
-        } else {
-            Some(self.0[coord.0][coord.1])
-        }
-    }
-    fn width(&self) -> usize {
-        self.0[0].len()
-    }
-    fn height(&self) -> usize {
-        self.0.len()
-    }
-
-    pub fn pipes(&self) -> usize {
-        self.0
-            .iter()
-            .map(|row| row.iter().filter(|tile| tile != &&Tile::Ground).count())
-            .sum::<usize>()
-    }
+pub fn pipes(map: &Map) -> usize {
+    map.cells()
+        .filter(|(_, tile)| **tile != Tile::Ground)
+        .count()
+}
 
-    pub fn draw_pipe_map(&self, pipe_map: &HashMap<Coord, bool>) {
-        for i in 0..self.height() {
-            for j in 0..self.width() {
+pub fn draw_pipe_map(map: &Map, pipe_map: &HashMap<Coord, bool>) {
+    for i in 0..map.height() {
+        let row: String = (0..map.width())
+            .map(|j| {
                 if pipe_map.contains_key(&Coord(i, j)) {
-                    print!("#");
+                    '#'
                 } else {
-                    print!(".");
+                    '.'
                 }
-            }
-            println!();
-        }
-    }
-}
-
-impl Display for Map {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.0.iter() {
-            for cell in row.iter() {
-                write!(f, "{}", cell);
-            }
-            writeln!(f);
-        }
-        Ok(())
+            })
+            .collect();
+        crate::trace!("{}", row);
     }
 }
 
 // done with functional on day 10?!
-fn q(input: &str, question: Question) -> Result<(usize, usize), String> {
-    let map: Map = input.parse()?;
-    let starter = map.find_starter();
+/// Both questions fall out of the same walk around the loop: the steps to the far end and the
+/// tiles inside.
+fn q(map: &Map) -> Result<(usize, usize), String> {
+    let (x, y) = map
+        .find(&Tile::Starter)
+        .ok_or("There's no starter on the map")?;
+    let starter = Coord(y, x);
     let neighbors = [
         if starter.1 == 0 {
             None
@@ -244,7 +205,7 @@ fn q(input: &str, question: Question) -> Result<(usize, usize), String> {
     let (rt1, rt2) = neighbors
         .into_iter()
         .filter(|neighbor| {
-            if let Some(tile) = map.get(neighbor) {
+            if let Some(tile) = map.get(**neighbor) {
                 let neighbors_nexts = tile.next_coords(neighbor);
                 if neighbors_nexts.is_err() {
                     false
@@ -267,10 +228,10 @@ fn q(input: &str, question: Question) -> Result<(usize, usize), String> {
     pipe_map.insert(rt2, true);
     let mut steps = 1;
     let steps = loop {
-        println!("We at {} {}", rt1, rt2);
+        crate::trace!("We at {} {}", rt1, rt2);
         steps += 1;
         let tile1 = map
-            .get(&rt1)
+            .get(rt1)
             .ok_or(format!("We ran out of bounds at {}", rt1))?;
         let nexts = tile1.next_coords(&rt1)?;
         let tmp = rt1;
@@ -285,7 +246,7 @@ fn q(input: &str, question: Question) -> Result<(usize, usize), String> {
         }
         pipe_map.insert(rt1, true);
         let tile2 = map
-            .get(&rt2)
+            .get(rt2)
             .ok_or(format!("We ran out of bounds at {}", rt2))?;
         let nexts = tile2.next_coords(&rt2)?;
         let tmp = rt2;
@@ -309,8 +270,9 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
     let mut inside = 0usize;
     for i in 0..map.height() {
         let mut state = WalkThruState::Outside;
+        let mut row = String::new();
         for j in 0..map.width() {
-            let tile = map.get(&Coord(i, j)).unwrap();
+            let tile = map[Coord(i, j)];
             let in_pipe = pipe_map.get(&Coord(i, j)).is_some();
             if state == WalkThruState::InsideAfterF {
                 // must be in pipe
@@ -320,7 +282,7 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
                     Tile::J => WalkThruState::Outside,
                     _ => panic!("no"),
                 };
-                print!("p");
+                row.push('p');
             } else if state == WalkThruState::OutsideAfterF {
                 state = match tile {
                     Tile::Starter | Tile::Horizontal => WalkThruState::OutsideAfterF, // st?
@@ -328,7 +290,7 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
                     Tile::J => WalkThruState::Inside,
                     _ => panic!("no"),
                 };
-                print!("π");
+                row.push('π');
             } else if state == WalkThruState::InsideAfterL {
                 // must be in pipe
                 state = match tile {
@@ -337,7 +299,7 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
                     Tile::J => WalkThruState::Inside,
                     _ => panic!("no"),
                 };
-                print!("l");
+                row.push('l');
             } else if state == WalkThruState::OutsideAfterL {
                 state = match tile {
                     Tile::Starter | Tile::Horizontal => WalkThruState::OutsideAfterL, // st?
@@ -345,13 +307,13 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
                     Tile::J => WalkThruState::Outside,
                     _ => panic!("no"),
                 };
-                print!("q");
+                row.push('q');
             } else if state == WalkThruState::Inside && !in_pipe {
                 inside += 1; // the only case
-                print!("*");
+                row.push('*');
             } else if state == WalkThruState::Inside {
                 // we are in_pipe
-                print!("P");
+                row.push('P');
                 state = match tile {
                     Tile::Ground => panic!(
                         "this isn't the world I want to live in. {} {} {} {:?}",
@@ -368,7 +330,7 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
                 };
             } else if in_pipe && state == WalkThruState::Outside {
                 // we're outside
-                print!("P");
+                row.push('P');
                 state = match tile {
                     Tile::Ground => panic!("life has too much pain anyway :("),
                     Tile::Horizontal => WalkThruState::Outside,
@@ -380,10 +342,10 @@ pub fn calculate_insides(map: &Map, pipe_map: &HashMap<Coord, bool>) -> usize {
                 };
             } else {
                 // else we're outside and not in pipe so irrelevant
-                print!(".");
+                row.push('.');
             }
         }
-        println!();
+        crate::trace!("{}", row);
     }
     inside
 }
@@ -1,16 +1,29 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 pub struct Day11;
 
+crate::register_day!(Day11, 2023, 11, "Cosmic Expansion", tags: &["grid", "geometry"]);
+
 impl Day for Day11 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res)
+    type Input = Image;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_image(input))
+    }
+
+    fn part1(&self, image: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(image, Question::First)?.into())
+    }
+
+    fn part2(&self, image: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(image, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "...#......
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "...#......
 .......#..
 #.........
 ..........
@@ -19,8 +32,10 @@ impl Day for Day11 {
 .........#
 ..........
 .......#..
-#...#....."
-            .to_string()
+#...#.....",
+        )
+        .part1(374)
+        .part2(82000210)]
     }
 }
 
@@ -36,7 +51,13 @@ fn between(r: usize, a: usize, b: usize) -> bool {
     a.min(b) < r && r < a.max(b)
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
+pub struct Image {
+    galaxies: Vec<(usize, usize)>,
+    empty_rows: Vec<usize>,
+    empty_cols: Vec<usize>,
+}
+
+fn read_image(input: &str) -> Image {
     let galaxies = input
         .lines()
         .enumerate()
@@ -60,15 +81,28 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
         .columns(width)
         .enumerate()
         .filter(|(x, col)| {
-            println!("COL {}: {}", x, col);
+            crate::trace!("COL {}: {}", x, col);
             col.chars().all(|ch| ch != '#')
         })
         .map(|(col, _)| col)
         .collect_vec();
-    println!(
+    crate::debug!(
         "EMPTY COLS {} ",
         empty_cols.iter().map(|c| c.to_string()).join(", ")
     );
+    Image {
+        galaxies,
+        empty_rows,
+        empty_cols,
+    }
+}
+
+fn q(image: &Image, question: Question) -> Result<u128, String> {
+    let Image {
+        galaxies,
+        empty_rows,
+        empty_cols,
+    } = image;
     Ok(galaxies
         .iter()
         .combinations(2)
@@ -84,9 +118,15 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
             let skipped_rows = multiplier(skipped_rows, question);
             let skipped_cols = multiplier(skipped_cols, question);
             let total = manhattan + skipped_rows + skipped_cols;
-            println!(
+            crate::trace!(
                 "G({}, {}) <-> G({},{}) = {} + {} + {}",
-                ax, ay, bx, by, manhattan, skipped_rows, skipped_cols
+                ax,
+                ay,
+                bx,
+                by,
+                manhattan,
+                skipped_rows,
+                skipped_cols
             );
             total
         })
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use bit_vec::BitVec;
 use itertools::{all, Itertools};
 use std::num::ParseIntError;
@@ -7,20 +8,34 @@ use std::str::FromStr;
 // TODO rewrite, this doesn't work
 pub struct Day12;
 
+crate::register_day!(Day12, 2023, 12, "Hot Springs", parts: 1, tags: &["dp"]);
+
 impl Day for Day12 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res)
+    type Input = Vec<Row>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, rows: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(rows, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "???.### 1,1,3
+    fn part2(&self, rows: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(rows, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
 ????.#...#... 4,1,1
 ????.######..#####. 1,6,5
-?###???????? 3,2,1"
-            .to_string()
+?###???????? 3,2,1",
+        )
+        .part1(21)
+        .part2(525152)]
     }
 }
 
@@ -51,7 +66,8 @@ impl ToString for Point {
     }
 }
 
-struct Row {
+#[derive(Clone)]
+pub struct Row {
     points: Vec<Point>,
     pattern: Vec<usize>,
 }
@@ -156,19 +172,18 @@ fn count_bits(n: u32) -> u32 {
     count
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let rows: Result<Vec<Row>, String> = input.lines().map(|l| l.parse()).collect();
-    let rows = rows?;
+fn q(rows: &[Row], question: Question) -> Result<u128, String> {
     let n = rows.len();
     Ok(rows
-        .into_iter()
+        .iter()
+        .cloned()
         .enumerate()
         .map(|(idx, r)| {
             let s = match question {
                 Question::First => r.variations() as u128,
                 Question::Second => r.times_five().variations() as u128,
             };
-            println!("{}/{}", idx, n);
+            crate::trace!("{}/{}", idx, n);
             s
         })
         .sum())
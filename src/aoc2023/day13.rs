@@ -1,5 +1,6 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use bit_vec::BitVec;
 use itertools::Itertools;
 use std::iter::FromIterator;
@@ -7,14 +8,29 @@ use std::str::FromStr;
 
 pub struct Day13;
 
+crate::register_day!(Day13, 2023, 13, "Point of Incidence", tags: &["grid"]);
+
 impl Day for Day13 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res)
+    type Input = Vec<Map>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input
+            .split("\n\n")
+            .map(|s| s.parse())
+            .collect::<Result<_, String>>()?)
+    }
+
+    fn part1(&self, maps: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(maps, Question::First)?.into())
+    }
+
+    fn part2(&self, maps: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(maps, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "#.##..##.
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "#.##..##.
 ..#.##.#.
 ##......#
 ##......#
@@ -29,12 +45,14 @@ impl Day for Day13 {
 #####.##.
 ..##..###
 #....#..#
-"
-        .to_string()
+",
+        )
+        .part1(405)
+        .part2(400)]
     }
 }
 
-struct Map {
+pub struct Map {
     rows: Vec<BitVec>,
     columns: Vec<BitVec>,
 }
@@ -121,9 +139,7 @@ fn mirror_diffs(vs: &Vec<BitVec>, breakpoint: usize) -> usize {
     panic!("shouldn't get here")
 }
 
-fn q(input: &str, question: Question) -> Result<usize, String> {
-    let maps: Result<Vec<Map>, String> = input.split("\n\n").map(|s| s.parse()).collect();
-    let maps = maps?;
+fn q(maps: &[Map], question: Question) -> Result<usize, String> {
     let sum: Result<Vec<usize>, String> = maps
         .iter()
         .map(|m| {
@@ -1,23 +1,33 @@
-use crate::common::day::{Day, Question};
-use clap::ValueHint::DirPath;
-use itertools::Itertools;
+use crate::common::cycle;
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
+use crate::common::geom::Direction;
+use crate::common::map::Grid;
+use crate::common::step;
 use std::fmt::{Display, Formatter};
-use std::ptr::write;
-use std::str::FromStr;
 
 pub struct Day14;
 
+crate::register_day!(Day14, 2023, 14, "Parabolic Reflector Dish", tags: &["grid", "simulation", "cycle"]);
+
 impl Day for Day14 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(map)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "O....#....
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(map)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "O....#....
 O.OO#....#
 .....##...
 OO.#O....O
@@ -26,13 +36,15 @@ O.#..O.#.#
 ..O..#O..O
 .......O..
 #....###..
-#OO..#...."
-            .to_string()
+#OO..#....",
+        )
+        .part1(136)
+        .part2(64)]
     }
 }
 
-#[derive(Eq, PartialEq, Copy, Clone)]
-enum Piece {
+#[derive(Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Piece {
     O,
     Wall,
     Ground,
@@ -58,134 +70,52 @@ impl Display for Piece {
     }
 }
 
-struct Map(Vec<Vec<Piece>>);
-
-impl FromStr for Map {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Map(s
-            .lines()
-            .map(|line| line.chars().map(|ch| Piece::from(ch)).collect_vec())
-            .collect_vec()))
-    }
-}
-
-#[derive(Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn moved(&self, i: usize, j: usize) -> (usize, usize) {
-        match self {
-            Direction::Up => (i - 1, j),
-            Direction::Down => (i + 1, j),
-            Direction::Left => (i, j - 1),
-            Direction::Right => (i, j + 1),
-        }
-    }
-    fn next(self) -> Self {
-        match self {
-            Direction::Up => Direction::Left,
-            Direction::Down => Direction::Right,
-            Direction::Left => Direction::Down,
-            Direction::Right => Direction::Up,
-        }
-    }
-}
+pub type Map = Grid<Piece>;
 
-impl Map {
-    fn bounds_check(&self, direction: &Direction, i: usize, j: usize) -> bool {
-        match direction {
-            Direction::Up => i != 0,
-            Direction::Down => i != self.height() - 1,
-            Direction::Left => j != 0,
-            Direction::Right => j != self.width() - 1,
+fn tilt(map: &mut Map, direction: Direction) -> bool {
+    let mut changed = false;
+    for at in map.coords() {
+        if map[at] != Piece::O {
+            continue;
         }
-    }
-    fn tilt(&mut self, direction: &Direction) -> bool {
-        let mut changed = false;
-        for i in 0..self.height() {
-            for j in 0..self.width() {
-                if self.0[i][j] == Piece::O && self.bounds_check(direction, i, j) {
-                    let (nexti, nextj) = direction.moved(i, j);
-                    match self.0[nexti][nextj] {
-                        Piece::O => {}
-                        Piece::Wall => {}
-                        Piece::Ground => {
-                            self.0[nexti][nextj] = Piece::O;
-                            self.0[i][j] = Piece::Ground;
-                            changed = true;
-                        }
-                    }
-                }
+        if let Some(next) = map.step(at, direction.delta()) {
+            if map[next] == Piece::Ground {
+                map[next] = Piece::O;
+                map[at] = Piece::Ground;
+                changed = true;
             }
         }
-        changed
-    }
-    // I'm brainless now
-    fn count_north_load(&self) -> u128 {
-        let mut sum = 0u128;
-        for j in 0..self.width() {
-            for i in 0..self.height() {
-                if self.0[i][j] == Piece::O {
-                    sum += (self.height() - i) as u128;
-                }
-            }
-        }
-        sum
-    }
-
-    fn width(&self) -> usize {
-        self.0[0].len()
-    }
-
-    fn height(&self) -> usize {
-        self.0.len()
     }
+    changed
 }
 
-impl Clone for Map {
-    fn clone(&self) -> Self {
-        Map(self
-            .0
-            .iter()
-            .map(|row| row.iter().map(|p| p.clone()).collect_vec())
-            .collect_vec())
-    }
+fn count_north_load(map: &Map) -> u128 {
+    map.cells()
+        .filter(|&(_, piece)| *piece == Piece::O)
+        .map(|((_, y), _)| (map.height() - y) as u128)
+        .sum()
 }
 
-impl PartialEq for Map {
-    fn eq(&self, other: &Self) -> bool {
-        if self.height() != other.height() || self.width() != other.width() {
-            false
-        } else {
-            (0..self.height()).all(|i| (0..self.width()).all(|j| self.0[i][j] == other.0[i][j]))
-        }
-    }
-}
-
-impl Display for Map {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.0 {
-            for piece in row {
-                write!(f, "{}", piece);
+/// Every rock rolls north up to the wall or the rocks above it, column by column, so the load
+/// comes without tilting the whole map.
+fn q1(map: &Map) -> Result<u128, String> {
+    Ok(map
+        .columns()
+        .map(|column| {
+            let (mut free, mut load) = (0, 0);
+            for (y, piece) in column.enumerate() {
+                match piece {
+                    Piece::Wall => free = y + 1,
+                    Piece::O => {
+                        load += (map.height() - free) as u128;
+                        free += 1;
+                    }
+                    Piece::Ground => {}
+                }
             }
-            writeln!(f, "");
-        }
-        Ok(())
-    }
-}
-
-fn q1(input: &str) -> Result<u128, String> {
-    let mut map: Map = input.parse()?;
-    while map.tilt(&Direction::Up) {}
-    print!("{}", map);
-    Ok(map.count_north_load())
+            load
+        })
+        .sum())
 }
 
 // Up 1 5 9
@@ -193,62 +123,59 @@ fn q1(input: &str) -> Result<u128, String> {
 // down 3 7 11
 // right 4 8 12
 
-fn q2(input: &str) -> Result<u128, String> {
-    let mut map: Map = input.parse()?;
-    let mut last_cycle = vec![];
-    for i in 0..1_000_000_000 {
-        while map.tilt(&Direction::Up) {}
-        if i == 0 {
-            println!("AFTER UP:\n{}", map)
-        }
-        while map.tilt(&Direction::Left) {}
-        if i == 0 {
-            println!("AFTER LEFT:\n{}", map)
-        }
-        while map.tilt(&Direction::Down) {}
-        if i == 0 {
-            println!("AFTER DOWN:\n{}", map)
-        }
-        while map.tilt(&Direction::Right) {}
-        if let Some(pos) = last_cycle.iter().position(|lr: &Map| lr == &map) {
-            let last_identical = pos;
-            println!(
-                " Current map:\n{}\nCurrent cycle {}. Last identical at {}",
-                map,
+/// One spin cycle: tilting up, left, down and right. `i` counts the cycles, from 0.
+fn spin(map: &Map, i: usize) -> Map {
+    let mut map = map.clone();
+    while tilt(&mut map, Direction::Up) {}
+    if i == 0 {
+        crate::trace!("AFTER UP:\n{}", map)
+    }
+    step::tick(|| format!("cycle {}, tilted up", i + 1), || map.to_string());
+    while tilt(&mut map, Direction::Left) {}
+    if i == 0 {
+        crate::trace!("AFTER LEFT:\n{}", map)
+    }
+    step::tick(
+        || format!("cycle {}, tilted left", i + 1),
+        || map.to_string(),
+    );
+    while tilt(&mut map, Direction::Down) {}
+    if i == 0 {
+        crate::trace!("AFTER DOWN:\n{}", map)
+    }
+    step::tick(
+        || format!("cycle {}, tilted down", i + 1),
+        || map.to_string(),
+    );
+    while tilt(&mut map, Direction::Right) {}
+    step::tick(
+        || {
+            format!(
+                "cycle {}, tilted right, load {}",
                 i + 1,
-                last_identical + 1,
-            );
-            let cycle_len = i - last_identical;
-            println!("Cycle len: {}", cycle_len);
-            let target_mod = 1_000_000_000 % cycle_len;
-            let (_, final_map) = last_cycle
-                .iter()
-                .skip(last_identical)
-                .enumerate()
-                .filter(|(pos, lr)| {
-                    let my_mod = (last_identical + pos + 1) % cycle_len;
-                    println!(
-                        "Cycle number {} (mod {} = {}): load {}",
-                        last_identical + pos + 1,
-                        cycle_len,
-                        my_mod,
-                        lr.count_north_load()
-                    );
-                    my_mod == target_mod
-                })
-                .nth(0)
-                .unwrap();
-            println!("For 1_000_000_000 mod {} = {}", cycle_len, target_mod,);
-            return Ok(final_map.count_north_load());
-        } else {
-            println!("Current cycle at {}\n{}", i + 1, map);
-            last_cycle.push(map.clone());
-        }
-        if (i + 1 % 10_000_000 == 0) {
-            println!("{}%", i / 10_000_000);
-        }
-    }
-    Ok(map.count_north_load())
+                count_north_load(&map)
+            )
+        },
+        || map.to_string(),
+    );
+    map
+}
+
+fn q2(map: &Map) -> Result<u128, String> {
+    let mut i = 0;
+    let history = cycle::find(map.clone(), |map| {
+        let next = spin(map, i);
+        i += 1;
+        next
+    });
+    let cycle = history.cycle;
+    crate::debug!(
+        "Current cycle {}. Last identical at {}",
+        cycle.prefix + cycle.length,
+        cycle.prefix,
+    );
+    crate::debug!("Cycle len: {}", cycle.length);
+    Ok(count_north_load(history.state_at(1_000_000_000)))
 }
 
 // 40 - 280
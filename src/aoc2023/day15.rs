@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::iter::repeat;
 use std::num::ParseIntError;
@@ -7,17 +8,29 @@ use std::string::FromUtf8Error;
 
 pub struct Day15;
 
+crate::register_day!(Day15, 2023, 15, "Lens Library", tags: &["strings"]);
+
 impl Day for Day15 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Vec<Step>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input.trim().split(',').map(|step| step.parse()).collect()
+    }
+
+    fn part1(&self, steps: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(steps)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7".to_string()
+    fn part2(&self, steps: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(steps)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7")
+                .part1(1320)
+                .part2(145),
+        ]
     }
 }
 
@@ -31,9 +44,8 @@ fn hasher(s: &str) -> u16 {
     })
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let steps = input.split(",");
-    let vals = steps.map(|s| hasher(s) as u128);
+fn q1(steps: &[Step]) -> Result<u128, String> {
+    let vals = steps.iter().map(|s| hasher(&s.text) as u128);
     Ok(vals.sum())
 }
 
@@ -43,6 +55,24 @@ struct LabeledLens {
     lens: u8,
 }
 
+/// A step of the initialization sequence, the first question hashes its text as it is.
+pub struct Step {
+    text: String,
+    task: Task,
+}
+
+impl FromStr for Step {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Step {
+            text: s.to_string(),
+            task: s.parse()?,
+        })
+    }
+}
+
+#[derive(Clone)]
 enum Task {
     Add(LabeledLens),
     Remove(String),
@@ -71,10 +101,8 @@ impl FromStr for Task {
     }
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let steps = input.split(",");
-    let tasks: Result<Vec<Task>, String> = steps.map(|t| t.parse()).collect();
-    let tasks = tasks?;
+fn q2(steps: &[Step]) -> Result<u128, String> {
+    let tasks = steps.iter().map(|step| step.task.clone());
     let mut boxes: Vec<Vec<LabeledLens>> = repeat(vec![]).take(256).collect_vec();
     for task in tasks {
         match task {
@@ -1,21 +1,32 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
+use crate::common::geom::{Direction, Point2};
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::str::FromStr;
 
 pub struct Day16;
 
+crate::register_day!(Day16, 2023, 16, "The Floor Will Be Lava", tags: &["grid", "search"]);
+
 impl Day for Day16 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(map)?.into())
+    }
+
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(map)?.into())
     }
 
-    fn test_data(&self) -> String {
-        r#".|...\....
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            r#".|...\....
 |.-.\.....
 .....|-...
 ........|.
@@ -24,8 +35,10 @@ impl Day for Day16 {
 ..../.\\..
 .-.-/..|..
 .|....-|.\
-..//.|...."#
-            .to_string()
+..//.|...."#,
+        )
+        .part1(46)
+        .part2(51)]
     }
 }
 
@@ -50,7 +63,7 @@ impl From<char> for Piece {
     }
 }
 
-struct Map(Vec<Vec<Piece>>);
+pub struct Map(Vec<Vec<Piece>>);
 type HistoryMap = Vec<Vec<HashSet<Direction>>>;
 
 impl FromStr for Map {
@@ -73,49 +86,14 @@ impl Map {
         self.0.len()
     }
     fn next_step(&self, ray: &Ray) -> Option<(usize, usize)> {
-        match ray.direction {
-            Direction::Right => {
-                if ray.location.0 == self.width() - 1 {
-                    None
-                } else {
-                    Some((ray.location.0 + 1, ray.location.1))
-                }
-            }
-            Direction::Left => {
-                if ray.location.0 == 0 {
-                    None
-                } else {
-                    Some((ray.location.0 - 1, ray.location.1))
-                }
-            }
-            Direction::Up => {
-                if ray.location.1 == 0 {
-                    None
-                } else {
-                    Some((ray.location.0, ray.location.1 - 1))
-                }
-            }
-            Direction::Down => {
-                if ray.location.1 == self.height() - 1 {
-                    None
-                } else {
-                    Some((ray.location.0, ray.location.1 + 1))
-                }
-            }
-        }
+        Point2::from(ray.location)
+            .step(ray.direction, self.width(), self.height())
+            .map(|p| p.into())
     }
 }
 
 // I won't recurse bc I'm fairly certain it would lead to stack overflow :(((
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-enum Direction {
-    Right,
-    Left,
-    Up,
-    Down,
-}
-
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 struct Ray {
     direction: Direction,
@@ -133,7 +111,7 @@ fn track_ray(map: &Map, energy_map: &mut HistoryMap, initial: Ray) -> Vec<Ray> {
         match map.0[ray.location.1][ray.location.0] {
             Piece::Ground => {}
             Piece::SplitVertically => {
-                if ray.direction == Direction::Left || ray.direction == Direction::Right {
+                if ray.direction.is_horizontal() {
                     ray.direction = Direction::Up;
                     alternatives.push(Ray {
                         location: ray.location,
@@ -142,7 +120,7 @@ fn track_ray(map: &Map, energy_map: &mut HistoryMap, initial: Ray) -> Vec<Ray> {
                 }
             }
             Piece::SplitHorizontally => {
-                if ray.direction == Direction::Up || ray.direction == Direction::Down {
+                if !ray.direction.is_horizontal() {
                     ray.direction = Direction::Left;
                     alternatives.push(Ray {
                         location: ray.location,
@@ -151,20 +129,10 @@ fn track_ray(map: &Map, energy_map: &mut HistoryMap, initial: Ray) -> Vec<Ray> {
                 }
             }
             Piece::Slash => {
-                ray.direction = match ray.direction {
-                    Direction::Right => Direction::Up,
-                    Direction::Left => Direction::Down,
-                    Direction::Up => Direction::Right,
-                    Direction::Down => Direction::Left,
-                }
+                ray.direction = ray.direction.reflect_slash();
             }
             Piece::Backslash => {
-                ray.direction = match ray.direction {
-                    Direction::Right => Direction::Down,
-                    Direction::Left => Direction::Up,
-                    Direction::Up => Direction::Left,
-                    Direction::Down => Direction::Right,
-                }
+                ray.direction = ray.direction.reflect_backslash();
             }
         }
         let new_loc = map.next_step(&ray);
@@ -176,8 +144,7 @@ fn track_ray(map: &Map, energy_map: &mut HistoryMap, initial: Ray) -> Vec<Ray> {
     alternatives
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let map: Map = input.parse()?;
+fn q1(map: &Map) -> Result<u128, String> {
     let mut energy_map = (0..map.height())
         .map(|_| {
             (0..map.width())
@@ -218,8 +185,7 @@ fn run_rays(
         .sum::<usize>() as u128
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let map: Map = input.parse()?;
+fn q2(map: &Map) -> Result<u128, String> {
     let lefts = (0..map.height())
         .map(|y| Ray {
             location: (0, y),
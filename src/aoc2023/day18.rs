@@ -1,24 +1,35 @@
-use crate::aoc2023::day10::{calculate_insides, Coord, Map, Tile};
-use crate::common::day::{Day, Question};
+use crate::aoc2023::day10::{calculate_insides, draw_pipe_map, pipes, Coord, Map, Tile};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
+use crate::common::geom::Direction;
+use crate::common::map::Grid;
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::convert::TryFrom;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day18;
 
+crate::register_day!(Day18, 2023, 18, "Lavaduct Lagoon", tags: &["geometry"]);
+
 impl Day for Day18 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Vec<Move>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, moves: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(moves)?.into())
+    }
+
+    fn part2(&self, moves: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(moves)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "R 6 (#70c710)
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "R 6 (#70c710)
 D 5 (#0dc571)
 L 2 (#5713f0)
 D 2 (#d2c081)
@@ -31,72 +42,43 @@ U 2 (#caa171)
 R 2 (#7807d2)
 U 3 (#a77fa3)
 L 2 (#015232)
-U 2 (#7a21e3)"
-            .to_string()
+U 2 (#7a21e3)",
+        )
+        .part1(62)
+        .part2(952408144115u64)]
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    L,
-    R,
-    U,
-    D,
-}
-
 type Color = String;
 
-impl TryFrom<char> for Direction {
-    type Error = ();
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'L' => Ok(Direction::L),
-            'R' => Ok(Direction::R),
-            'U' => Ok(Direction::U),
-            'D' => Ok(Direction::D),
-            _ => Err(()),
-        }
-    }
-}
-
-impl FromStr for Direction {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() > 1 {
-            Err(())
-        } else {
-            TryFrom::try_from(s.chars().nth(0).unwrap())
-        }
-    }
-}
-
-impl ToString for Direction {
-    fn to_string(&self) -> String {
-        match self {
-            Direction::L => "L".to_string(),
-            Direction::R => "R".to_string(),
-            Direction::U => "U".to_string(),
-            Direction::D => "D".to_string(),
-        }
+/// The plan's `U`, `D`, `L` and `R`.
+fn parse_direction(s: &str) -> Result<Direction, Error> {
+    match s {
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "R" => Ok(Direction::Right),
+        _ => Err(Error::new(format!("{} is not a direction", s))),
     }
 }
 
 #[derive(Clone)]
-struct Move {
+pub struct Move {
     direction: Direction,
     color: Color,
     moves: usize,
 }
 
 impl FromStr for Move {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (direction, moves, color) = s.split(" ").collect_tuple().ok_or(())?;
-        let direction = direction.parse::<Direction>().map_err(|_| ())?;
-        let moves = moves.parse::<usize>().map_err(|_| ())?;
+        let (direction, moves, color) = s
+            .split(" ")
+            .collect_tuple()
+            .ok_or("expected a direction, a distance and a color")?;
+        let direction = parse_direction(direction)?;
+        let moves = moves.parse::<usize>()?;
         let color = color
             .trim_start_matches("(#")
             .trim_start_matches(")")
@@ -124,20 +106,9 @@ fn find_dims(moves: &Vec<Move>) -> Dimensions {
     let mut x = 0isize;
     let mut y = 0isize;
     for mv in moves.iter() {
-        match mv.direction {
-            Direction::L => {
-                x -= mv.moves as isize;
-            }
-            Direction::R => {
-                x += mv.moves as isize;
-            }
-            Direction::U => {
-                y -= mv.moves as isize;
-            }
-            Direction::D => {
-                y += mv.moves as isize;
-            }
-        }
+        let (dx, dy) = mv.direction.delta();
+        x += dx * mv.moves as isize;
+        y += dy * mv.moves as isize;
         if x < min_x {
             min_x = x;
         }
@@ -161,108 +132,78 @@ fn find_dims(moves: &Vec<Move>) -> Dimensions {
 
 fn build_maps(moves: &Vec<Move>) -> (Map, HashMap<Coord, bool>) {
     let dims = find_dims(moves);
-    let mut map = (0..dims.height)
-        .map(|y| (0..dims.width).map(|x| Tile::Ground).collect_vec())
-        .collect_vec();
+    let mut map = Grid::new(dims.width, dims.height, Tile::Ground);
 
     let mut x = dims.starter_x;
     let mut y = dims.starter_y;
-    let mut last_dir = Direction::U;
+    let mut last_dir = Direction::Up;
     let mut pipe_map = HashMap::new();
     for mv in moves.iter() {
         let pipe_tile = calculate_tile(&last_dir, mv);
-        map[y][x] = pipe_tile;
+        map[(x, y)] = pipe_tile;
         pipe_map.insert(Coord(y, x), true);
 
-        for i in 0..mv.moves {
-            match mv.direction {
-                Direction::L => {
-                    x -= 1;
-                    map[y][x] = Tile::Horizontal;
-                }
-                Direction::R => {
-                    x += 1;
-                    map[y][x] = Tile::Horizontal;
-                }
-                Direction::U => {
-                    y -= 1;
-                    map[y][x] = Tile::Vertical;
-                }
-                Direction::D => {
-                    y += 1;
-                    map[y][x] = Tile::Vertical;
-                }
-            }
+        for _ in 0..mv.moves {
+            (x, y) = mv
+                .direction
+                .step((x, y))
+                .expect("the map is sized to fit the trench");
+            map[(x, y)] = if mv.direction.is_horizontal() {
+                Tile::Horizontal
+            } else {
+                Tile::Vertical
+            };
             pipe_map.insert(Coord(y, x), true);
         }
         last_dir = mv.direction;
     }
     // finally
-    map[dims.starter_y][dims.starter_x] = calculate_tile(&last_dir, &moves[0]);
+    map[(dims.starter_x, dims.starter_y)] = calculate_tile(&last_dir, &moves[0]);
 
-    (From::from(map), pipe_map)
+    (map, pipe_map)
 }
 
 fn calculate_tile(last_dir: &Direction, mv: &Move) -> Tile {
     match (last_dir, mv.direction) {
-        (Direction::D, Direction::D) => Tile::Vertical,
-        (Direction::D, Direction::L) => Tile::J,
-        (Direction::D, Direction::R) => Tile::L,
-        (Direction::U, Direction::U) => Tile::Vertical,
-        (Direction::U, Direction::L) => Tile::Seven,
-        (Direction::U, Direction::R) => Tile::F,
-        (Direction::R, Direction::R) => Tile::Horizontal,
-        (Direction::R, Direction::U) => Tile::J,
-        (Direction::R, Direction::D) => Tile::Seven,
-        (Direction::L, Direction::L) => Tile::Horizontal,
-        (Direction::L, Direction::U) => Tile::L,
-        (Direction::L, Direction::D) => Tile::F,
-
-        (x, y) => panic!("incorrect combo {} {}", x.to_string(), y.to_string()),
+        (Direction::Down, Direction::Down) => Tile::Vertical,
+        (Direction::Down, Direction::Left) => Tile::J,
+        (Direction::Down, Direction::Right) => Tile::L,
+        (Direction::Up, Direction::Up) => Tile::Vertical,
+        (Direction::Up, Direction::Left) => Tile::Seven,
+        (Direction::Up, Direction::Right) => Tile::F,
+        (Direction::Right, Direction::Right) => Tile::Horizontal,
+        (Direction::Right, Direction::Up) => Tile::J,
+        (Direction::Right, Direction::Down) => Tile::Seven,
+        (Direction::Left, Direction::Left) => Tile::Horizontal,
+        (Direction::Left, Direction::Up) => Tile::L,
+        (Direction::Left, Direction::Down) => Tile::F,
+
+        (x, y) => panic!("incorrect combo {} {}", x, y),
     }
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let moves = input
-        .lines()
-        .map(|l| l.parse::<Move>())
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|_| "move coll error".to_string())?;
-    let (map, pipe_map) = build_maps(&moves);
-    println!("MAP:\n{}", map);
-    map.draw_pipe_map(&pipe_map);
+fn q1(moves: &Vec<Move>) -> Result<u128, String> {
+    let (map, pipe_map) = build_maps(moves);
+    crate::debug!("MAP:\n{}", map);
+    draw_pipe_map(&map, &pipe_map);
     let insides = calculate_insides(&map, &pipe_map);
-    let outsides = map.pipes();
+    let outsides = pipes(&map);
     Ok(insides as u128 + outsides as u128)
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-struct Span {
-    from: usize,
-    to: usize,
-}
-
-impl Span {
-    fn length(&self) -> usize {
-        self.to - self.from + 1
-    }
-}
-fn q2(input: &str) -> Result<u128, String> {
-    let moves = input
-        .lines()
-        .map(|l| l.parse::<Move>())
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|_| "move coll error".to_string())?;
+/// The real instructions are in the colors, and far too long to draw: the shoelace formula gives
+/// the area inside the middle of the trench, Pick's theorem adds the outer half of the trench.
+fn q2(moves: &[Move]) -> Result<u128, String> {
     let moves = moves
-        .into_iter()
+        .iter()
         .map(|mv| {
             let dist = usize::from_str_radix(&mv.color[0..5], 16)
                 .map_err(|e: ParseIntError| e.to_string());
             let dir = match mv.color.chars().nth(5).ok_or("No fifth".to_string())? {
-                '0' => Ok(Direction::R),
-                '1' => Ok(Direction::D),
-                '2' => Ok(Direction::U),
-                '3' => Ok(Direction::L),
+                '0' => Ok(Direction::Right),
+                '1' => Ok(Direction::Down),
+                '2' => Ok(Direction::Left),
+                '3' => Ok(Direction::Up),
                 _ => Err("Invalid direction".to_string()),
             };
             dist.and_then(|d| {
@@ -274,55 +215,17 @@ fn q2(input: &str) -> Result<u128, String> {
             })
         })
         .collect::<Result<Vec<_>, _>>()?;
-    let dims = find_dims(&moves);
-    println!("Dims: {:?}", dims);
-    let mut horizontals = HashMap::new();
-    let mut verticals = HashMap::new();
-    let mut x = dims.starter_x;
-    let mut y = dims.starter_y;
+    let (mut x, mut y) = (0i128, 0i128);
+    let mut twice_area = 0i128;
+    let mut trench = 0i128;
     for mv in moves.iter() {
-        if mv.direction == Direction::L || mv.direction == Direction::R {
-            let endx = if mv.direction == Direction::L {
-                x - mv.moves
-            } else {
-                x + mv.moves
-            };
-            let span = Span {
-                from: x.min(endx),
-                to: x.max(endx),
-            };
-            horizontals
-                .entry(y)
-                .and_modify(|v: &mut Vec<Span>| v.push(span))
-                .or_insert(vec![span]);
-        } else {
-            let endy = if mv.direction == Direction::U {
-                y - mv.moves
-            } else {
-                y + mv.moves
-            };
-            let span = Span {
-                from: y.min(endy),
-                to: y.max(endy),
-            };
-            verticals
-                .entry(x)
-                .and_modify(|v: &mut Vec<Span>| v.push(span))
-                .or_insert(vec![span]);
-        }
+        let length = mv.moves as i128;
+        let (dx, dy) = mv.direction.delta();
+        let (next_x, next_y) = (x + dx as i128 * length, y + dy as i128 * length);
+        twice_area += x * next_y - next_x * y;
+        trench += length;
+        (x, y) = (next_x, next_y);
     }
-
-    let mut current_in_spans: Vec<Span> = vec![];
-    let mut sum = 0u128;
-    for row_idx in 0..dims.height {
-        for span in horizontals.get(&row_idx).unwrap_or(&vec![]) {
-            sum += span.length() as u128;
-        }
-        for span in current_in_spans.iter() {
-            sum += span.length() as u128; // todo ??
-        }
-        // verticals.iter().filter(|(col_idx,v)|)
-    }
-
-    Ok(sum)
+    crate::debug!("Area {}, trench {}", twice_area.abs() / 2, trench);
+    Ok((twice_area.abs() / 2 + trench / 2 + 1) as u128)
 }
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::hash_map::Values;
@@ -8,17 +9,35 @@ use std::str::FromStr;
 
 pub struct Day19;
 
+crate::register_day!(Day19, 2023, 19, "Aplenty", tags: &["parsing", "ranges"]);
+
 impl Day for Day19 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = (HashMap<String, Workflow>, Vec<Part>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let (workflows, parts) = parse_input(input)?;
+        Ok((build_workflow_map(workflows), parts))
+    }
+
+    fn part1(
+        &self,
+        (workflow_map, parts): &Self::Input,
+        _params: &Params,
+    ) -> Result<Answer, Error> {
+        Ok(q1(workflow_map, parts)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "px{a<2006:qkq,m>2090:A,rfg}
+    fn part2(
+        &self,
+        (workflow_map, parts): &Self::Input,
+        _params: &Params,
+    ) -> Result<Answer, Error> {
+        Ok(q2(workflow_map)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
 rfg{s<537:gd,x>2440:R,A}
@@ -34,13 +53,15 @@ hdj{m>838:A,pv}
 {x=1679,m=44,a=2067,s=496}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}"
-            .to_string()
+{x=2127,m=1623,a=2188,s=1013}",
+        )
+        .part1(19114)
+        .part2(167409079868000u64)]
     }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-struct Part {
+pub struct Part {
     x: usize,
     m: usize,
     a: usize,
@@ -76,7 +97,7 @@ struct ConditionIfClause {
     action: Action,
 }
 
-struct Workflow {
+pub struct Workflow {
     name: String,
     ifs: Vec<ConditionIfClause>,
 }
@@ -218,7 +239,7 @@ fn process_part(wfmap: &HashMap<String, Workflow>, part: &Part) -> Result<Action
             .get(&current)
             .ok_or(format!("Workflow {} not found", current))?;
         for cic in workflow.ifs.iter() {
-            println!("In {:?}", cic);
+            crate::trace!("In {:?}", cic);
             let action = match cic.condition.clone() {
                 Condition::Always => Some(cic.action.clone()),
                 Condition::GreaterThan(meas, value) => {
@@ -252,10 +273,7 @@ fn process_part(wfmap: &HashMap<String, Workflow>, part: &Part) -> Result<Action
     }
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let (workflows, parts) = parse_input(input)?;
-    let workflow_map: HashMap<String, Workflow> = build_workflow_map(workflows);
-
+fn q1(workflow_map: &HashMap<String, Workflow>, parts: &[Part]) -> Result<u128, String> {
     let accepteds = parts
         .iter()
         .filter(|&part| process_part(&workflow_map, part).unwrap() == Action::Accept);
@@ -334,9 +352,7 @@ struct Work {
     spans: PartSpan,
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let (workflows, parts) = parse_input(input)?;
-    let workflow_map: HashMap<String, Workflow> = build_workflow_map(workflows);
+fn q2(workflow_map: &HashMap<String, Workflow>) -> Result<u128, String> {
     let mut work_queue = vec![Work {
         workflow: "in".to_string(),
         cond_idx: 0,
@@ -378,7 +394,7 @@ fn q2(input: &str) -> Result<u128, String> {
         }
     }
     for winning_span in &win_conditions {
-        println!("{:?}", winning_span)
+        crate::trace!("{:?}", winning_span)
     }
     Ok(win_conditions.iter().map(|wc| wc.combinations()).sum())
 }
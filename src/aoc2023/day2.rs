@@ -1,5 +1,6 @@
 use crate::aoc2023::day1::Day1;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::fmt::format;
 use std::num::ParseIntError;
@@ -8,22 +9,33 @@ use std::str::FromStr;
 
 pub struct Day2;
 
+crate::register_day!(Day2, 2023, 2, "Cube Conundrum", tags: &["parsing"]);
+
 impl Day for Day2 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{}", result);
+    type Input = Vec<Game>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
     }
 
-    fn test_data(&self) -> String {
-        return "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    fn part1(&self, games: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(games).into())
+    }
+
+    fn part2(&self, games: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(games).into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            .to_string();
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        )
+        .part1(8)
+        .part2(2286)]
     }
 }
 
@@ -99,7 +111,7 @@ impl Draw {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     draws: Vec<Draw>,
     id: u32,
 }
@@ -159,14 +171,7 @@ impl Game {
     }
 }
 
-fn q1(input: &str) -> u32 {
-    let games: Result<Vec<Game>, String> = input.lines().map(|line| line.parse::<Game>()).collect();
-    if games.is_err() {
-        // I'm worryingly golangified
-        println!("Error reading: {}", games.unwrap_err());
-        return 0;
-    }
-    let games = games.unwrap();
+fn q1(games: &[Game]) -> u32 {
     let possible_games = games.iter().filter(|g| {
         g.possible(&Draw {
             reds: 12,
@@ -178,13 +183,6 @@ fn q1(input: &str) -> u32 {
     sum
 }
 
-fn q2(input: &str) -> u32 {
-    let games: Result<Vec<Game>, String> = input.lines().map(|line| line.parse::<Game>()).collect();
-    if games.is_err() {
-        // I'm worryingly golangified
-        println!("Error reading: {}", games.unwrap_err());
-        return 0;
-    }
-    let games = games.unwrap();
+fn q2(games: &[Game]) -> u32 {
     games.iter().map(|g| g.power()).sum()
 }
@@ -1,30 +1,38 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::{HashMap, VecDeque};
 use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::Write;
 use std::str::FromStr;
 
 pub struct Day20;
 
+crate::register_day!(Day20, 2023, 20, "Pulse Propagation", tags: &["simulation", "cycle"]);
+
 impl Day for Day20 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Machine;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_machine(input)?)
+    }
+
+    fn part1(&self, machine: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(machine)?.into())
+    }
+
+    fn part2(&self, machine: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(machine)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "broadcaster -> a, b, c
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "broadcaster -> a, b, c
 %a -> b
 %b -> c
 %c -> inv
-&inv -> a, rx"
-            .to_string()
+&inv -> a, rx",
+        )]
     }
 }
 
@@ -39,7 +47,7 @@ enum ModuleType {
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Module {
+pub struct Module {
     name: String,
     module_type: ModuleType,
     links: Vec<String>,
@@ -254,9 +262,9 @@ fn broadcast_ping(machine: &mut Machine) -> (u128, u128) {
     (highs, lows)
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let mut machine = read_machine(input)?;
-    println!("{:?}", machine);
+fn q1(machine: &Machine) -> Result<u128, String> {
+    let mut machine = machine.clone();
+    crate::debug!("{:?}", machine);
     let (highs, lows) = (0..1000).fold((0, 0), |(highs, lows), _| {
         let (newhighs, newlows) = broadcast_ping(&mut machine);
         (highs + newhighs, lows + newlows)
@@ -264,14 +272,14 @@ fn q1(input: &str) -> Result<u128, String> {
     Ok(highs * lows)
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let mut machine = read_machine(input)?;
+fn q2(machine: &Machine) -> Result<u128, String> {
+    let mut machine = machine.clone();
     for (name, module) in machine.iter() {
         if module.links.iter().any(|l| l == "rx") {
-            println!("rx input is {}", name);
+            crate::trace!("rx input is {}", name);
         }
         if let ModuleType::Conjunction(cmap) = &module.module_type {
-            println!("Conjunction {}: {}", name, cmap.keys().join(", "));
+            crate::trace!("Conjunction {}: {}", name, cmap.keys().join(", "));
         }
     }
     // below: after looking at my machine, just hacking into it to see what's up
@@ -293,7 +301,7 @@ fn q2(input: &str) -> Result<u128, String> {
                     if value.is_some() {
                         let x: (usize, usize) = value.unwrap();
                         if n > x.1 {
-                            println!(
+                            crate::trace!(
                                 "New ping for {}: cycle {} -- distance {}",
                                 module_name,
                                 cnt,
@@ -303,7 +311,7 @@ fn q2(input: &str) -> Result<u128, String> {
                         }
                     } else {
                         if n > 0 {
-                            println!("First ping for {}: cycle {}", module_name, cnt);
+                            crate::trace!("First ping for {}: cycle {}", module_name, cnt);
                             *value = Some((cnt, n));
                         }
                     }
@@ -317,10 +325,10 @@ fn q2(input: &str) -> Result<u128, String> {
 
     Ok(3)
 }
-fn q2_brute_force(input: &str) -> Result<u128, String> {
+fn q2_brute_force(machine: &Machine) -> Result<u128, String> {
     // needs years to run
-    let mut machine = read_machine(input)?;
-    println!("{:?}", machine);
+    let mut machine = machine.clone();
+    crate::debug!("{:?}", machine);
     machine.insert(
         "rx".to_string(),
         Module {
@@ -339,12 +347,8 @@ fn q2_brute_force(input: &str) -> Result<u128, String> {
                 break;
             }
         }
-        if cnt % 10_000 == 0 {
-            print!(".");
-            io::stdout().flush().unwrap();
-            if cnt % 1_000_000 == 0 {
-                println!("{} million", cnt / 1_000_000);
-            }
+        if cnt % 1_000_000 == 0 {
+            crate::debug!("{} million", cnt / 1_000_000);
         }
     }
     Ok(cnt)
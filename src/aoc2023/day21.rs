@@ -1,22 +1,37 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
+use crate::common::extrapolate;
+use crate::common::map::{Coord, Grid};
+use crate::common::search;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use std::ops::Index;
-use std::str::FromStr;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 
 pub struct Day21;
 
+crate::register_day!(Day21, 2023, 21, "Step Counter", tags: &["grid", "search"]);
+
 impl Day for Day21 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Area;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        let area: Area = input.parse()?;
+        find_starter(&area)?;
+        Ok(area)
+    }
+
+    fn part1(&self, area: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        Ok(q1(area, params.get("steps")?)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "...........
+    fn part2(&self, area: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        Ok(q2(area, params.get("steps")?)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::new(
+                "...........
 .....###.#.
 .###.##..#.
 ..#.#...#..
@@ -26,227 +41,131 @@ impl Day for Day21 {
 .......##..
 .##.#.####.
 .##..##.##.
-..........."
-            .to_string()
+...........",
+            )
+            .param("steps", 6)
+            .part1(16)
+            .part2(16),
+            // Shaped like the real input, the start's row and column are clear, so the steps are
+            // extrapolated from the plots after 5, 16, 27 and 38 steps.
+            Example::named(
+                "extrapolated",
+                "...........
+.#.#...#.#.
+..#.....#..
+.#..#.#..#.
+....#.#....
+.....S.....
+....#.#....
+.#..#.#..#.
+..#.....#..
+.#.#...#.#.
+...........",
+            )
+            .param("steps", 93)
+            .part1(56)
+            .part2(7104),
+        ]
+    }
+
+    fn params(&self, question: Question) -> Params {
+        match question {
+            Question::First => Params::new().with("steps", 64),
+            Question::Second => Params::new().with("steps", 26501365),
+        }
     }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
-enum Field {
+pub enum Field {
     Plot,
     Rock,
     Starter,
 }
 
-impl From<char> for Field {
-    fn from(ch: char) -> Self {
+impl TryFrom<char> for Field {
+    type Error = String;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            '.' => Field::Plot,
-            '#' => Field::Rock,
-            _ => Field::Starter,
+            '.' => Ok(Field::Plot),
+            '#' => Ok(Field::Rock),
+            'S' => Ok(Field::Starter),
+            _ => Err(format!("'{}' is neither a plot, a rock nor the start", ch)),
         }
     }
 }
 
-struct Area(Vec<Vec<Field>>);
-
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
-struct Coord(usize, usize);
-
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
-struct InfCoord(isize, isize);
-
-type FieldMap = HashSet<Coord>;
-
-fn read_map(input: &str) -> Area {
-    let area = input
-        .lines()
-        .map(|line| line.chars().map(|ch| Field::from(ch)).collect_vec())
-        .collect_vec();
-    Area(area)
-}
-
-// maybe unused?
-fn rock_map(area: &Area) -> FieldMap {
-    (0..area.len())
-        .map(|row| {
-            (0..area[row].len()).filter_map(move |col| match area[row][col] {
-                Field::Plot | Field::Starter => None,
-                Field::Rock => Some(Coord(col, row)),
-            })
-        })
-        .flatten()
-        .collect()
-}
+pub type Area = Grid<Field>;
 
-// maybe unused?
-fn plot_map(area: &Area) -> FieldMap {
-    (0..area.len())
-        .map(|row| {
-            (0..area[row].len()).filter_map(move |col| match area[row][col] {
-                Field::Plot | Field::Starter => Some(Coord(col, row)),
-                Field::Rock => None,
-            })
-        })
-        .flatten()
-        .collect()
-}
+/// A plot on the area repeated forever in every direction.
+type InfCoord = (isize, isize);
 
-fn find_starter(area: &Area) -> Coord {
-    for row in 0..area.len() {
-        for col in 0..area[row].len() {
-            if area[row][col] == Field::Starter {
-                return Coord(col, row);
-            }
-        }
-    }
-    panic!("No starter found")
+fn find_starter(area: &Area) -> Result<Coord, Error> {
+    area.find(&Field::Starter)
+        .ok_or_else(|| Error::new("there's no starting position S"))
 }
 
-impl Area {
-    fn len(&self) -> usize {
-        self.0.len()
-    }
-    fn in_bounds_coord(&self, coord: (isize, isize)) -> Option<Coord> {
-        if coord.0 < 0 || coord.1 < 0 {
-            None
-        } else if coord.1 as usize >= self.len() || coord.0 as usize >= self[coord.1 as usize].len()
-        {
-            None
-        } else {
-            Some(Coord(coord.0 as usize, coord.1 as usize))
-        }
-    }
-    fn valid_neighbors(&self, coord: &Coord) -> Vec<Coord> {
-        [
-            (coord.0 as isize - 1, coord.1 as isize),
-            (coord.0 as isize + 1, coord.1 as isize),
-            (coord.0 as isize, coord.1 as isize - 1),
-            (coord.0 as isize, coord.1 as isize + 1),
-        ]
-        .into_iter()
-        .filter_map(|icoords| self.in_bounds_coord(*icoords))
-        .filter(|c| self[c] != Field::Rock)
+fn valid_neighbors(area: &Area, coord: Coord) -> Vec<Coord> {
+    area.neighbors(coord)
+        .filter(|&c| area[c] != Field::Rock)
         .collect_vec()
-    }
-    fn valid_neighbors_infinity(&self, coord: &InfCoord) -> Vec<InfCoord> {
-        [
-            InfCoord(coord.0 - 1, coord.1),
-            InfCoord(coord.0 + 1, coord.1),
-            InfCoord(coord.0, coord.1 - 1),
-            InfCoord(coord.0, coord.1 + 1),
-        ]
-        .into_iter()
-        .filter(|c| self[*c] != Field::Rock)
-        .map(|c| *c)
-        .collect_vec()
-    }
-}
-
-fn print_area(area: &Area, currs: &HashSet<Coord>) {
-    for row in 0..area.0.len() {
-        for col in 0..area.0[row].len() {
-            if currs.contains(&Coord(col, row)) {
-                print!("O");
-            } else {
-                print!(
-                    "{}",
-                    match area.0[row][col] {
-                        Field::Plot => ".",
-                        Field::Rock => "#",
-                        Field::Starter => "S",
-                    }
-                );
-            }
-        }
-        println!();
-    }
-}
-
-impl Index<usize> for Area {
-    type Output = Vec<Field>;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.0[index]
-    }
-}
-
-impl Index<&Coord> for Area {
-    type Output = Field;
-
-    fn index(&self, index: &Coord) -> &Self::Output {
-        &self.0[index.1][index.0]
-    }
-}
-
-// https://stackoverflow.com/questions/31210357/is-there-a-modulus-not-remainder-function-operation-
-fn modulo(a: isize, b: usize) -> usize {
-    let b = b as isize;
-    (((a % b) + b) % b) as usize
 }
 
-impl Index<&InfCoord> for Area {
-    type Output = Field;
-
-    fn index(&self, index: &InfCoord) -> &Self::Output {
-        let i1 = modulo(index.1, self.0.len());
-        let i0 = modulo(index.0, self.0[0].len());
-        &self.0[i1][i0]
-    }
+fn valid_neighbors_infinity(area: &Area, &(x, y): &InfCoord) -> Vec<InfCoord> {
+    [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+        .iter()
+        .copied()
+        .filter(|&c| *area.get_wrapping(c) != Field::Rock)
+        .collect_vec()
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let area = read_map(input);
-    // let rocks = rock_map(&area);
-    let starter = find_starter(&area);
-    const STEPS: usize = 64;
-    let finals = (0..STEPS).fold(HashSet::from([starter]), |valids, i| {
-        // println!("BEFORE STEP {}", i);
-        // print_area(&area, &valids);
+fn q1(area: &Area, steps: usize) -> Result<u128, Error> {
+    let starter = find_starter(area)?;
+    let finals = (0..steps).fold(HashSet::from([starter]), |valids, _| {
         valids
             .iter()
-            .map(|coord| area.valid_neighbors(coord))
-            .flatten()
+            .flat_map(|&coord| valid_neighbors(area, coord))
             .collect()
     });
-    // println!("FINAL");
-    // print_area(&area, &finals);
-    println!("{:?}", finals);
+    crate::debug!("{:?}", finals);
     Ok(finals.len() as u128)
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    // we're only going to debug here
-    let area = read_map(input);
-    let starter = find_starter(&area);
-    let starter = InfCoord(starter.0 as isize, starter.1 as isize);
-    let side = area.len();
-    let steps: usize = 10 * side + 1;
-    let mut last = 0;
-    let mut lastdiff = 0isize;
-    let mut lastdiffdiff = 0isize;
-    let finals = (0..steps).fold(HashSet::from([starter]), |valids, i| {
-        if i % side == 65 {
-            let diff = valids.len() - last;
-            let diff2 = lastdiff - diff as isize;
-            println!(
-                "AFTER STEP {} number of valids: {} [diff={}] [diff2={}]",
-                i,
-                valids.len(),
-                diff,
-                diff2,
-            );
-            last = valids.len();
-            lastdiff = diff as isize;
-        }
-        valids
-            .iter()
-            .map(|coord| area.valid_neighbors_infinity(coord))
-            .flatten()
-            .collect()
-    });
-    // println!("FINAL");
-    // print_area(&area, &finals);
-    // println!("{:?}", finals);
-    Ok(finals.len() as u128)
+/// On the real input the start's row and column are free of rocks, so the reachable plots after
+/// `steps % side + k * side` steps grow as a polynomial in `k`. A few of those are walked, the
+/// rest is extrapolated.
+fn q2(area: &Area, steps: usize) -> Result<u128, Error> {
+    let starter = find_starter(area)?;
+    let starter = (starter.0 as isize, starter.1 as isize);
+    let side = area.height();
+    let sampled = (0..4).map(|k| steps % side + k * side).collect_vec();
+    let walked = steps.min(*sampled.last().unwrap());
+    let distances = search::bfs(
+        [starter],
+        |coord| {
+            valid_neighbors_infinity(area, coord)
+                .into_iter()
+                .filter(|c| c.0.abs_diff(starter.0) + c.1.abs_diff(starter.1) <= walked)
+        },
+        |_| false,
+    );
+    let reachable = |steps: usize| {
+        distances
+            .costs()
+            .filter(|&(_, d)| d <= steps && d % 2 == steps % 2)
+            .count() as i128
+    };
+    if steps <= walked {
+        return Ok(reachable(steps) as u128);
+    }
+    let samples = sampled
+        .iter()
+        .map(|&s| {
+            let plots = reachable(s);
+            crate::debug!("After {} steps: {} plots", s, plots);
+            (s as i128, plots)
+        })
+        .collect_vec();
+    Ok(extrapolate::extrapolate(&samples, steps as i128)? as u128)
 }
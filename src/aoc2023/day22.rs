@@ -1,96 +1,56 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
+use crate::common::geom::Point3;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
-use std::str::FromStr;
+use std::ops::{Index, IndexMut};
 
 pub struct Day22;
 
+crate::register_day!(Day22, 2023, 22, "Sand Slabs", parts: 1, tags: &["simulation", "graph"]);
+
 impl Day for Day22 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = HashMap<BlockId, Block>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(idx, l)| (idx, Block::new(idx, l)))
+            .collect())
+    }
+
+    fn part1(&self, blocks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(blocks)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "1,0,1~1,2,1
+    fn part2(&self, blocks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(blocks)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "1,0,1~1,2,1
 0,0,2~2,0,2
 0,2,3~2,2,3
 0,0,4~0,2,4
 2,0,5~2,2,5
 0,1,6~2,1,6
-1,1,8~1,1,9"
-            .to_string()
-    }
-}
-
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-struct Coord {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-impl Add for Coord {
-    type Output = Coord;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Coord {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-        }
+1,1,8~1,1,9",
+        )
+        .part1(5)
+        .part2(7)]
     }
 }
 
-impl Sub for Coord {
-    type Output = Coord;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Coord {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
-    }
-}
-
-impl SubAssign for Coord {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-        self.z -= rhs.z;
-    }
-}
-impl AddAssign for Coord {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z == rhs.z;
-    }
-}
-
-impl FromStr for Coord {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y, z) = s
-            .split(",")
-            .map(|s| s.parse().unwrap())
-            .collect_tuple()
-            .ok_or(())?;
-        Ok(Coord { x, y, z })
-    }
-}
+type Coord = Point3<usize>;
 
 const UNIT_Z: Coord = Coord { x: 0, y: 0, z: 1 };
 
 type BlockId = usize;
 
 #[derive(Clone)]
-struct Block {
+pub struct Block {
     id: BlockId,
     coords: Vec<Coord>,
 }
@@ -198,16 +158,17 @@ impl State {
 
     fn print_from_x(&self) {
         for z in (1..self.z_dim).rev() {
+            let mut row = String::new();
             'xloop: for x in 0..self.x_dim {
                 for y in 0..self.y_dim {
                     if let Some(block_id) = self.get_point(Coord { x, y, z }) {
-                        print!("{}", block_id);
+                        row += &block_id.to_string();
                         continue 'xloop;
                     }
                 }
-                print!(".");
+                row.push('.');
             }
-            println!(" {}", z);
+            crate::trace!("{} {}", row, z);
         }
     }
 
@@ -260,10 +221,10 @@ impl State {
         let block = &self.blocks.get(&block_id).unwrap();
         let block_coords = &block.coords;
         if block.is_vertical() {
-            println!("{} is vertical", block_id);
+            crate::trace!("{} is vertical", block_id);
             let below = block_coords.iter().min_by(|a, b| a.z.cmp(&b.z)).unwrap();
             let below = *below - UNIT_Z;
-            println!("{:?} bleow: {:?}", below, self[below]);
+            crate::trace!("{:?} bleow: {:?}", below, self[below]);
             if let Some(bid) = self[below] {
                 HashSet::from([bid])
             } else {
@@ -307,13 +268,8 @@ impl IndexMut<BlockId> for State {
     }
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let blocks = input
-        .lines()
-        .enumerate()
-        .map(|(idx, l)| (idx, Block::new(idx, l)))
-        .collect();
-    let mut state = State::new(blocks);
+fn q1(blocks: &HashMap<BlockId, Block>) -> Result<u128, String> {
+    let mut state = State::new(blocks.clone());
     state.print_from_x();
 
     let n = state.blocks.len();
@@ -336,7 +292,7 @@ fn q1(input: &str) -> Result<u128, String> {
                     .for_each(|mut coord| *coord -= UNIT_Z);
             }
         }
-        println!();
+        crate::trace!("");
         state.print_from_x();
         if !dropped {
             break;
@@ -345,12 +301,12 @@ fn q1(input: &str) -> Result<u128, String> {
 
     let supps = (0..n).map(|x| state.supported_by(x)).collect_vec();
     for (i, supp) in supps.iter().enumerate() {
-        println!("Block {}:\t{:?}", i, supp);
+        crate::trace!("Block {}:\t{:?}", i, supp);
     }
     let mut important = HashSet::new();
     for (i, supp) in supps.iter().enumerate() {
         if supp.len() == 1 {
-            println!(
+            crate::trace!(
                 "{} is supported only 1: by {}",
                 i,
                 supp.iter().nth(0).unwrap()
@@ -358,9 +314,9 @@ fn q1(input: &str) -> Result<u128, String> {
             important.insert(supp.iter().nth(0).unwrap());
         }
     }
-    println!("{:?} LEN: {}", important, important.len());
+    crate::debug!("{:?} LEN: {}", important, important.len());
 
-    println!("Q1: {}", (n - important.len()) as u128);
+    crate::debug!("Q1: {}", (n - important.len()) as u128);
     let support_vec = (0..n).map(|x| state.supports(x)).collect_vec();
     let mut q2_map: Vec<Option<usize>> = vec![None; n];
     for id in 0..n {
@@ -390,6 +346,6 @@ fn rec_q2(
     }
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    todo!()
+fn q2(_blocks: &HashMap<BlockId, Block>) -> Result<u128, String> {
+    Err("not solved yet".to_string())
 }
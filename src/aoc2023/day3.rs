@@ -1,24 +1,30 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::str::FromStr;
 
 pub struct Day3;
 
+crate::register_day!(Day3, 2023, 3, "Gear Ratios", tags: &["grid"]);
+
 impl Day for Day3 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        if result.is_err() {
-            println!("Error: {}", result.unwrap_err());
-        } else {
-            println!("{}", result.unwrap());
-        }
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(map)?.into())
+    }
+
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(map)?.into())
     }
 
-    fn test_data(&self) -> String {
-        return "467..114..
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "467..114..
 ...*......
 ..35..633.
 ......#...
@@ -27,8 +33,10 @@ impl Day for Day3 {
 ..592.....
 ......755.
 ...$.*....
-.664.598.."
-            .to_string();
+.664.598..",
+        )
+        .part1(4361)
+        .part2(467835)]
     }
 }
 
@@ -41,7 +49,7 @@ struct Number {
 }
 
 #[derive(Debug)]
-struct Map {
+pub struct Map {
     map: Vec<Vec<char>>,
     rows: usize,
     cols: usize,
@@ -133,7 +141,7 @@ impl Number {
             }
         }
         if self.value == 617 {
-            println!(
+            crate::trace!(
                 "row={}, to_col+1={}, is_symbol={}",
                 row,
                 to_col + 1,
@@ -157,12 +165,11 @@ fn char_is_symbol(ch: char) -> bool {
     !(ch.is_ascii_digit() || ch == '.')
 }
 
-fn q1(input: &str) -> Result<u32, String> {
-    let map: Map = input.parse()?;
+fn q1(map: &Map) -> Result<u32, String> {
     let numbers = map.find_numbers();
-    println!("Numbers: {:?}", numbers);
+    crate::debug!("Numbers: {:?}", numbers);
     let parts = numbers.iter().filter(|number| number.is_part(&map));
-    println!(
+    crate::debug!(
         "Parts: {:?}",
         numbers
             .iter()
@@ -174,8 +181,7 @@ fn q1(input: &str) -> Result<u32, String> {
     Ok(sum_value)
 }
 
-fn q2(input: &str) -> Result<u32, String> {
-    let map: Map = input.parse()?;
+fn q2(map: &Map) -> Result<u32, String> {
     let numbers = map.find_numbers();
 
     let mut sum: u32 = 0;
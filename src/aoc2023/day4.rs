@@ -4,35 +4,43 @@ use std::iter::FromIterator;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_each_line, Error};
 
 pub struct Day4;
 
+crate::register_day!(Day4, 2023, 4, "Scratchcards", tags: &["parsing", "dp"]);
+
 impl Day for Day4 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        if result.is_err() {
-            println!("Error: {}", result.unwrap_err());
-        } else {
-            println!("{}", result.unwrap());
-        }
+    type Input = Vec<Card>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, cards: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(cards)?.into())
+    }
+
+    fn part2(&self, cards: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(cards)?.into())
     }
 
-    fn test_data(&self) -> String {
-        return "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
-            .to_string();
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        )
+        .part1(13)
+        .part2(30)]
     }
 }
 
-struct Card {
+pub struct Card {
     id: usize,
     winning: Vec<usize>,
     picked: Vec<usize>,
@@ -117,16 +125,12 @@ impl BuyAheadState {
     }
 }
 
-fn q1(input: &str) -> Result<u32, String> {
-    let cards: Result<Vec<Card>, String> = input.lines().map(|x| x.parse()).collect();
-    let cards = cards?;
+fn q1(cards: &[Card]) -> Result<u32, String> {
     let points = cards.iter().map(|c| c.points()).sum();
     Ok(points)
 }
 
-fn q2(input: &str) -> Result<u32, String> {
-    let cards: Result<Vec<Card>, String> = input.lines().map(|x| x.parse()).collect();
-    let cards = cards?;
+fn q2(cards: &[Card]) -> Result<u32, String> {
     let finalstate = cards.iter().fold(
         BuyAheadState {
             future: vec![],
@@ -1,25 +1,31 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day5;
 
+crate::register_day!(Day5, 2023, 5, "If You Give A Seed A Fertilizer", tags: &["ranges"]);
+
 impl Day for Day5 {
-    fn question(&self, input: &str, question: Question) {
-        let result = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input), // WIP :(
-        };
-        if result.is_err() {
-            println!("Error: {}", result.unwrap_err());
-        } else {
-            println!("{}", result.unwrap());
-        }
+    type Input = (Vec<u128>, Vec<Map>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_all(input)?)
+    }
+
+    fn part1(&self, (seeds, maps): &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(seeds, maps)?.into())
+    }
+
+    fn part2(&self, (seeds, maps): &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(seeds, maps)?.into())
     }
 
-    fn test_data(&self) -> String {
-        return "seeds: 79 14 55 13
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
@@ -51,8 +57,10 @@ temperature-to-humidity map:
 
 humidity-to-location map:
 60 56 37
-56 93 4"
-            .to_string();
+56 93 4",
+        )
+        .part1(35)
+        .part2(46)]
     }
 }
 
@@ -166,7 +174,7 @@ impl Mapping {
     }
 }
 
-struct Map {
+pub struct Map {
     source_type: String, // it seems these don't quite matter
     destination_type: String,
     mappings: Vec<Mapping>,
@@ -239,16 +247,14 @@ fn read_all(input: &str) -> Result<(Vec<u128>, Vec<Map>), String> {
     Ok((seeds, maps))
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let (seeds, maps) = read_all(input)?;
-    let final_seeds = maps.iter().fold(seeds, |seeds, map| {
+fn q1(seeds: &[u128], maps: &[Map]) -> Result<u128, String> {
+    let final_seeds = maps.iter().fold(seeds.to_vec(), |seeds, map| {
         seeds.iter().map(|seed| map.transform(seed)).collect_vec()
     });
     Ok(final_seeds.iter().min().ok_or("no minimum?")?.clone())
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let (seeds, maps) = read_all(input)?;
+fn q2(seeds: &[u128], maps: &[Map]) -> Result<u128, String> {
     let seed_pairs = seeds
         .iter()
         .batching(|it| match it.next() {
@@ -268,7 +274,7 @@ fn q2(input: &str) -> Result<u128, String> {
         })
         .collect_vec();
     let final_spans = maps.iter().fold(spans, |spans, map| {
-        println!(
+        crate::trace!(
             "Spans before {}-to-{}:\n{}\n",
             map.source_type,
             map.destination_type,
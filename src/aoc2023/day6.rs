@@ -1,39 +1,70 @@
 // I've done this with pen n paper and calculated in python at work but let's redo it
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::num::ParseIntError;
 
 pub struct Day6;
 
+crate::register_day!(Day6, 2023, 6, "Wait For It", tags: &["math"]);
+
 impl Day for Day6 {
-    fn question(&self, input: &str, question: Question) {
-        match q(input, question) {
-            Ok(n) => println!("{}", n),
-            Err(err) => println!("{}", err),
-        }
+    type Input = Vec<Race>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        read_races(input)
+    }
+
+    fn part1(&self, races: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(races, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "Time:      7  15   30
-Distance:  9  40  200"
-            .to_string()
+    fn part2(&self, races: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(races, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Time:      7  15   30
+Distance:  9  40  200",
+        )
+        .part1(288)
+        .part2(71503)]
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let races = read_races(input, question)?;
-    println!("{:?}", races);
+fn q(races: &[Race], question: Question) -> Result<u128, String> {
+    let races = match question {
+        Question::First => races.to_vec(),
+        Question::Second => vec![Race::kerned(races)?],
+    };
+    crate::debug!("{:?}", races);
     Ok(races.iter().map(|r| r.race_solutions()).product())
 }
 
-#[derive(Debug)]
-struct Race {
+#[derive(Debug, Clone)]
+pub struct Race {
     time: u128,
     distance: u128,
 }
 
 impl Race {
+    /// The one race the sheet is about once the spaces between the numbers are ignored.
+    fn kerned(races: &[Race]) -> Result<Race, String> {
+        let join = |field: fn(&Race) -> u128| {
+            races
+                .iter()
+                .map(|race| field(race).to_string())
+                .join("")
+                .parse()
+                .map_err(|e: ParseIntError| e.to_string())
+        };
+        Ok(Race {
+            time: join(|race| race.time)?,
+            distance: join(|race| race.distance)?,
+        })
+    }
     fn solve_equation(&self) -> (f64, f64) {
         let discriminant = self.time * self.time - 4 * self.distance;
         let discriminant = discriminant as f64;
@@ -50,45 +81,33 @@ impl Race {
     }
 }
 
-fn read_races(input: &str, question: Question) -> Result<Vec<Race>, String> {
-    let (time_line, dist_line) = input.lines().collect_tuple().ok_or("More lines")?;
-    let time_parts = time_line.split_ascii_whitespace().skip(1);
-    let dist_parts = dist_line.split_ascii_whitespace().skip(1);
-    match question {
-        Question::First => {
-            let td: Result<Vec<(u128, u128)>, String> = time_parts
-                .zip(dist_parts)
-                .map(|(t, d)| {
-                    let t: Result<u128, String> =
-                        t.parse().map_err(|e: ParseIntError| e.to_string());
-                    let d: Result<u128, String> =
-                        d.parse().map_err(|e: ParseIntError| e.to_string());
-                    match (t, d) {
-                        (Err(x), Err(y)) => Err(x + &y),
-                        (Err(x), Ok(_)) => Err(x),
-                        (Ok(_), Err(x)) => Err(x),
-                        (Ok(x), Ok(y)) => Ok((x, y)),
-                    }
-                })
-                .collect();
-            let td = td?;
-            Ok(td
-                .into_iter()
-                .map(|(time, distance)| Race { time, distance })
-                .collect_vec())
-        }
-        Question::Second => {
-            let time = time_parts
-                .into_iter()
-                .join("")
-                .parse()
-                .map_err(|e: ParseIntError| e.to_string())?;
-            let distance = dist_parts
-                .into_iter()
-                .join("")
-                .parse()
-                .map_err(|e: ParseIntError| e.to_string())?;
-            Ok(vec![Race { time, distance }])
-        }
+fn read_races(input: &str) -> Result<Vec<Race>, Error> {
+    let (time_line, dist_line) = input
+        .lines()
+        .collect_tuple()
+        .ok_or("not a line of times and a line of distances")?;
+    let numbers = |line: &str, y: usize, label: &str| -> Result<Vec<u128>, Error> {
+        line.strip_prefix(label)
+            .ok_or_else(|| Error::new(format!("no {}", label)).on_line(y))?
+            .split_ascii_whitespace()
+            .map(|n| {
+                n.parse()
+                    .map_err(|e: ParseIntError| Error::from(e).on_line(y))
+            })
+            .collect()
+    };
+    let times = numbers(time_line, 0, "Time:")?;
+    let distances = numbers(dist_line, 1, "Distance:")?;
+    if times.len() != distances.len() {
+        return Err(Error::new(format!(
+            "{} times but {} distances",
+            times.len(),
+            distances.len()
+        )));
     }
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
 }
@@ -1,29 +1,40 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::num::ParseIntError;
+use std::str::FromStr;
 use std::string::ToString;
 
 pub struct Day7;
 
+crate::register_day!(Day7, 2023, 7, "Camel Cards", tags: &["sorting"]);
+
 impl Day for Day7 {
-    fn question(&self, input: &str, question: Question) {
-        let result = q(input, question);
-        if result.is_err() {
-            println!("Error: {}", result.unwrap_err());
-        } else {
-            println!("{}", result.unwrap());
-        }
+    type Input = Vec<Deal>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, deals: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(deals, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "32T3K 765
+    fn part2(&self, deals: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(deals, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
-QQQJA 483"
-            .to_string()
+QQQJA 483",
+        )
+        .part1(6440)
+        .part2(5905)]
     }
 }
 
@@ -126,20 +137,43 @@ impl Hand {
         }?;
         Ok(Hand { cards, bid, value })
     }
-    fn build(s: &str, question: Question) -> Result<Self, String> {
-        let (cards, bid_str) = s
-            .split_ascii_whitespace()
-            .collect_tuple()
-            .ok_or("Not two".to_string())?;
-        let bid = bid_str.parse().map_err(|e: ParseIntError| e.to_string())?;
+    /// The hand dealt, what it's worth depends on the question: `J` is a joker in the second.
+    fn build(deal: &Deal, question: Question) -> Result<Self, String> {
         Hand::new(
-            cards.chars().map(|ch| Card(ch, question)).collect_vec(),
-            bid,
+            deal.cards
+                .chars()
+                .map(|ch| Card(ch, question))
+                .collect_vec(),
+            deal.bid,
             question,
         )
     }
 }
 
+/// The cards of a hand and its bid, as on a line of the input.
+pub struct Deal {
+    cards: String,
+    bid: u128,
+}
+
+impl FromStr for Deal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (cards, bid) = s
+            .split_ascii_whitespace()
+            .collect_tuple()
+            .ok_or("not a hand and a bid")?;
+        if cards.len() != 5 || !cards.chars().all(|ch| "23456789TJQKA".contains(ch)) {
+            return Err(Error::new(format!("{} is not a hand of 5 cards", cards)));
+        }
+        Ok(Deal {
+            cards: cards.to_string(),
+            bid: bid.parse()?,
+        })
+    }
+}
+
 impl Eq for Hand {}
 
 impl PartialEq<Self> for Hand {
@@ -179,10 +213,10 @@ impl Ord for Hand {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let hands: Result<Vec<Hand>, String> = input
-        .lines()
-        .map(|line| Hand::build(line, question))
+fn q(deals: &[Deal], question: Question) -> Result<u128, String> {
+    let hands: Result<Vec<Hand>, String> = deals
+        .iter()
+        .map(|deal| Hand::build(deal, question))
         .collect();
     let hands = hands?;
     let val = hands
@@ -190,7 +224,7 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
         .sorted()
         .enumerate()
         .map(|(i, h)| {
-            println!(
+            crate::trace!(
                 "Hand #{}: {} -- {:?}",
                 i,
                 h.cards.iter().map(|c| c.0).collect::<String>(),
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
@@ -7,37 +8,66 @@ use std::str::FromStr;
 
 pub struct Day8;
 
+crate::register_day!(Day8, 2023, 8, "Haunted Wasteland", tags: &["graph", "cycle", "math"]);
+
 impl Day for Day8 {
-    fn question(&self, input: &str, question: Question) {
-        let result = q(input, question);
-        if result.is_err() {
-            println!("Error: {}", result.unwrap_err());
-        } else {
-            println!("{}", result.unwrap());
-        }
+    type Input = (HashMap<String, Node>, Vec<Move>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_network(input)?)
+    }
+
+    fn part1(&self, (node_map, moves): &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(node_map, moves)?.into())
+    }
+
+    fn part2(&self, (node_map, moves): &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(node_map, moves)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "LLR
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example::named(
+                "RL",
+                "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)",
+            )
+            .part1(2),
+            Example::named(
+                "LLR",
+                "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)"
-            .to_string()
-        //         "RL
-        //
-        // AAA = (BBB, CCC)
-        // BBB = (DDD, EEE)
-        // CCC = (ZZZ, GGG)
-        // DDD = (DDD, DDD)
-        // EEE = (EEE, EEE)
-        // GGG = (GGG, GGG)
-        // ZZZ = (ZZZ, ZZZ)"
-        //             .to_string()
+ZZZ = (ZZZ, ZZZ)",
+            )
+            .part1(6),
+            Example::named(
+                "ghosts",
+                "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+            )
+            .part2(6),
+        ]
     }
 }
 
-enum Move {
+pub enum Move {
     Left,
     Right,
 }
@@ -54,7 +84,7 @@ impl TryFrom<char> for Move {
     }
 }
 
-struct Node {
+pub struct Node {
     id: String,
     left: String,
     right: String,
@@ -82,7 +112,7 @@ impl FromStr for Node {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
+fn read_network(input: &str) -> Result<(HashMap<String, Node>, Vec<Move>), String> {
     let (moves, nodes) = input
         .split("\n\n")
         .collect_tuple()
@@ -96,13 +126,10 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
             .into_iter()
             .map(|node| ((&node.id).to_string(), node)),
     );
-    match question {
-        Question::First => q1(node_map, moves),
-        Question::Second => q2(node_map, moves),
-    }
+    Ok((node_map, moves))
 }
 
-fn q1(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u128, String> {
+fn q1(node_map: &HashMap<String, Node>, moves: &[Move]) -> Result<u128, String> {
     // let's just
     let mut current = "AAA";
     let mut steps = 0u128;
@@ -123,31 +150,61 @@ fn q1(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u128, String>
     }
 }
 
-// This is just brute force least common multiple for the patterns discovered in q2_find_series
-fn q2(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u128, String> {
-    let mut x: u128 = 19783;
-    const other_cycles: [u128; 5] = [15989, 18157, 12737, 14363, 19241];
-    loop {
-        x += 19783;
-        let hits = other_cycles.iter().filter(|t| x % **t == 0).count();
-        if hits == 5 {
-            println!("GOTIT");
-            return Ok(x);
-        }
-        if hits >= 3 {
-            println!("{} at {}", hits + 1, x);
+/// Each ghost comes back to its Z node every as many steps as it took to get there first, as
+/// q2_find_series found out, so they all meet at the least common multiple of those.
+fn q2(node_map: &HashMap<String, Node>, moves: &[Move]) -> Result<u128, String> {
+    let mut all = 1;
+    for start in node_map.keys().filter(|id| id.ends_with("A")) {
+        let steps = steps_to_z(node_map, moves, start)?;
+        crate::debug!("{} reaches a Z node in {} steps", start, steps);
+        all = lcm(all, steps);
+    }
+    Ok(all)
+}
+
+fn steps_to_z(
+    node_map: &HashMap<String, Node>,
+    moves: &[Move],
+    start: &str,
+) -> Result<u128, String> {
+    let mut current = start;
+    // past this the ghost is going round in a loop without a Z node
+    let limit = (node_map.len() * moves.len()) as u128;
+    for (steps, which_way) in (1..=limit).zip(moves.iter().cycle()) {
+        let node = node_map
+            .get(current)
+            .ok_or(format!("node {} not found", current))?;
+        current = match which_way {
+            Move::Left => &node.left,
+            Move::Right => &node.right,
+        };
+        if current.ends_with("Z") {
+            return Ok(steps);
         }
     }
+    Err(format!("{} never reaches a Z node", start))
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: u128, b: u128) -> u128 {
+    a / gcd(a, b) * b
 }
 
 // Ran this to discover the patterns.
-fn q2_find_series(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u128, String> {
+fn q2_find_series(node_map: &HashMap<String, Node>, moves: &[Move]) -> Result<u128, String> {
     let mut currents = node_map.keys().filter(|id| id.ends_with("A")).collect_vec();
     let mut steps = 0u128;
     for c in currents {
         let mut current_move = moves.iter().enumerate().cycle();
         let mut current = c;
-        println!("Starting node: {}", current);
+        crate::trace!("Starting node: {}", current);
         steps = 0;
         let mut last = None;
         loop {
@@ -161,7 +218,7 @@ fn q2_find_series(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u
             };
             steps += 1;
             if current.ends_with("Z") {
-                println!(
+                crate::trace!(
                     "Move {}: at {}. In the cycle: {}. Since last: {}",
                     steps,
                     current,
@@ -180,7 +237,7 @@ fn q2_find_series(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u
 }
 
 // horrifically slow and cannot find it
-fn q2_slow(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u128, String> {
+fn q2_slow(node_map: &HashMap<String, Node>, moves: &[Move]) -> Result<u128, String> {
     let mut currents = node_map.keys().filter(|id| id.ends_with("A")).collect_vec();
     let mut current_move = moves.iter().cycle();
     let mut steps = 0u128;
@@ -202,7 +259,7 @@ fn q2_slow(node_map: HashMap<String, Node>, moves: Vec<Move>) -> Result<u128, St
         steps += 1;
         let hits = currents.iter().filter(|id| id.ends_with("Z")).count();
         if hits > 2 {
-            println!(
+            crate::trace!(
                 "Move #{}: I'm at {}; {} hits",
                 steps,
                 currents.iter().join(", "),
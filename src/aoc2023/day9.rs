@@ -1,27 +1,40 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day9;
 
+crate::register_day!(Day9, 2023, 9, "Mirage Maintenance", tags: &["math"]);
+
 impl Day for Day9 {
-    fn question(&self, input: &str, question: Question) {
-        match q(input, question) {
-            Ok(n) => println!("{}", n),
-            Err(err) => println!("{}", err),
-        }
+    type Input = Vec<Series>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, series_plural: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(series_plural, Question::First)?.into())
+    }
+
+    fn part2(&self, series_plural: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(series_plural, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "0 3 6 9 12 15
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "0 3 6 9 12 15
 1 3 6 10 15 21
-10 13 16 21 30 45"
-            .to_string()
+10 13 16 21 30 45",
+        )
+        .part1(114)
+        .part2(2)]
     }
 }
 
-struct Series {
+pub struct Series {
     numbers: Vec<i128>,
 }
 
@@ -59,7 +72,7 @@ impl Series {
         } else {
             let derived = self.derive();
             let derived_next = derived.next_num(question);
-            println!(
+            crate::trace!(
                 "Deriving for {}: {}",
                 self.numbers.iter().map(|n| n.to_string()).join(","),
                 derived_next
@@ -72,9 +85,7 @@ impl Series {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<i128, String> {
-    let series_plural: Result<Vec<Series>, _> = input.lines().map(|l| l.parse()).collect();
-    let series_plural = series_plural?;
+fn q(series_plural: &[Series], question: Question) -> Result<i128, String> {
     let nexts = series_plural
         .iter()
         .map(|series| series.next_num(question))
@@ -1,27 +1,43 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::num::ParseIntError;
 
 pub struct Day1;
 
+crate::register_day!(Day1, 2024, 1, "Historian Hysteria", tags: &["sorting"]);
+
 impl Day for Day1 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = (Vec<i128>, Vec<i128>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_lists(input)?)
+    }
+
+    fn part1(&self, lists: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(lists, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "3   4
+    fn part2(&self, lists: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(lists, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "3   4
 4   3
 2   5
 1   3
 3   9
-3   3"
-            .to_string()
+3   3",
+        )
+        .part1(11)
+        .part2(31)]
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
+/// Both lists, sorted.
+fn read_lists(input: &str) -> Result<(Vec<i128>, Vec<i128>), String> {
     // let lists = input.lines().map(|l| l.split("   ").map(|s| s.parse().map_err(|e: ParseIntError| e.to_string())));
     let pairs: Vec<(&str, &str)> = input
         .lines()
@@ -43,17 +59,20 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
         .collect();
     let list1 = list1?;
     let list1: Vec<i128> = list1.into_iter().sorted().collect();
+    Ok((list0, list1))
+}
 
+fn q((list0, list1): &(Vec<i128>, Vec<i128>), question: Question) -> Result<u128, String> {
     if question == Question::First {
-        Ok((0..pairs.len())
+        Ok((0..list0.len())
             .map(|i| (list0.get(i).unwrap() - list1.get(i).unwrap()).abs())
             .map(|x| {
-                println!("DIF  {}", x);
+                crate::trace!("DIF  {}", x);
                 x
             })
             .sum::<i128>() as u128)
     } else {
-        let counter1 = list1.into_iter().counts();
+        let counter1 = list1.iter().counts();
         Ok(list0
             .iter()
             .map(|n| counter1.get(n).unwrap_or(&0).clone() as u128 * *n as u128)
@@ -1,25 +1,42 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_grid, Error};
+use crate::common::geom::Direction;
 use itertools::Itertools;
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 pub struct Day10;
 
+crate::register_day!(Day10, 2024, 10, "Hoof It", tags: &["grid", "search"]);
+
 impl Day for Day10 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(map).map(|(score, _)| score)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "89010123
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(map).map(|(_, rating)| rating)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "89010123
 78121874
 87430965
 96549874
 45678903
 32019012
 01329801
-10456732"
-            .to_string()
+10456732",
+        )
+        .part1(36)
+        .part2(81)]
     }
 }
 
@@ -30,38 +47,32 @@ struct Point {
     score: u128,
 }
 
-impl From<char> for Point {
-    fn from(ch: char) -> Self {
-        let height = ch.to_digit(10).unwrap() as u8;
-        Point {
+impl TryFrom<char> for Point {
+    type Error = String;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        let height = ch
+            .to_digit(10)
+            .ok_or_else(|| format!("'{}' is not a height", ch))? as u8;
+        Ok(Point {
             height,
             reachables: vec![],
             score: 0,
-        }
+        })
     }
 }
 
-struct Map(Vec<Vec<Point>>);
+#[derive(Clone)]
+pub struct Map(Vec<Vec<Point>>);
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Map(s
-            .lines()
-            .map(|l| l.chars().map(|ch| From::from(ch)).collect_vec())
-            .collect_vec()))
+        Ok(Map(parse_grid(s)?))
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 impl Map {
     fn width(&self) -> usize {
         self.0[0].len()
@@ -70,46 +81,28 @@ impl Map {
         self.0.len()
     }
     fn get_neighbor(&self, x: usize, y: usize, dir: Direction) -> Option<Point> {
-        match (x, y, dir) {
-            (0, _, Direction::Left) => None,
-            (x, _, Direction::Right) if x == self.width() - 1 => None,
-            (_, 0, Direction::Up) => None,
-            (_, y, Direction::Down) if y == self.height() - 1 => None,
-            (x, y, Direction::Up) => Some(self.0[y - 1][x].clone()),
-            (x, y, Direction::Down) => Some(self.0[y + 1][x].clone()),
-            (x, y, Direction::Left) => Some(self.0[y][x - 1].clone()),
-            (x, y, Direction::Right) => Some(self.0[y][x + 1].clone()),
-        }
+        let (x, y) = dir.step((x, y))?;
+        self.0.get(y)?.get(x).cloned()
     }
     fn calculate_for_height(&mut self, digit: u8) {
         for y in 0..self.height() {
             for x in 0..self.width() {
                 if self.0[y][x].height == digit {
-                    self.0[y][x].reachables = vec![
-                        Direction::Up,
-                        Direction::Down,
-                        Direction::Left,
-                        Direction::Right,
-                    ]
-                    .into_iter()
-                    .map(|dir| self.get_neighbor(x, y, dir))
-                    .filter(|pt| pt.is_some() && pt.clone().unwrap().height == digit + 1)
-                    .map(|pt| pt.unwrap().reachables)
-                    .flatten()
-                    .unique()
-                    .collect_vec();
-
-                    self.0[y][x].score = vec![
-                        Direction::Up,
-                        Direction::Down,
-                        Direction::Left,
-                        Direction::Right,
-                    ]
-                    .into_iter()
-                    .map(|dir| self.get_neighbor(x, y, dir))
-                    .filter(|pt| pt.is_some() && pt.clone().unwrap().height == digit + 1)
-                    .map(|pt| pt.unwrap().score)
-                    .sum()
+                    self.0[y][x].reachables = Direction::ALL
+                        .iter()
+                        .map(|&dir| self.get_neighbor(x, y, dir))
+                        .filter(|pt| pt.is_some() && pt.clone().unwrap().height == digit + 1)
+                        .map(|pt| pt.unwrap().reachables)
+                        .flatten()
+                        .unique()
+                        .collect_vec();
+
+                    self.0[y][x].score = Direction::ALL
+                        .iter()
+                        .map(|&dir| self.get_neighbor(x, y, dir))
+                        .filter(|pt| pt.is_some() && pt.clone().unwrap().height == digit + 1)
+                        .map(|pt| pt.unwrap().score)
+                        .sum()
                 }
             }
         }
@@ -152,31 +145,34 @@ impl Map {
     }
     fn print_reachables(&self) {
         for i in 0..self.height() {
-            for j in 0..self.width() {
-                print!("{}  ", self.0[i][j].reachables.len());
-            }
-            println!();
+            crate::trace!(
+                "{}",
+                (0..self.width())
+                    .map(|j| self.0[i][j].reachables.len())
+                    .join("  ")
+            );
         }
     }
     fn print_scores(&self) {
         for i in 0..self.height() {
-            for j in 0..self.width() {
-                print!("{}  ", self.0[i][j].score);
-            }
-            println!();
+            crate::trace!(
+                "{}",
+                (0..self.width()).map(|j| self.0[i][j].score).join("  ")
+            );
         }
     }
 }
 
-fn q(input: &str, question: Question) -> Result<(u128, u128), String> {
-    let mut map: Map = input.parse()?;
+/// The scores and the ratings of the trailheads come out of the same pass.
+fn q(map: &Map) -> Result<(u128, u128), String> {
+    let mut map = map.clone();
     map.set_scores(9, 1);
     for i in (0..9).rev() {
         map.calculate_for_height(i);
 
-        println!("==== MAP AT {} ====", i);
+        crate::trace!("==== MAP AT {} ====", i);
         map.print_scores();
-        println!();
+        crate::trace!("");
     }
     Ok((map.sum_reachables(0), map.sum_scores(0)))
 }
@@ -1,19 +1,30 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day11;
 
+crate::register_day!(Day11, 2024, 11, "Plutonian Pebbles", tags: &["dp"]);
+
 impl Day for Day11 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q2(input, question);
-        println!("{:?}", res);
+    type Input = Vec<Stone>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input.split(" ").map(|s| Ok(s.parse()?)).collect()
+    }
+
+    fn part1(&self, stones: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(stones, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "125 17".to_string()
+    fn part2(&self, stones: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(stones, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new("125 17").part1(55312).part2(65601038650482u64)]
     }
 }
 
@@ -33,20 +44,15 @@ fn even_digits(stone: Stone) -> Option<(Stone, Stone)> {
     }
 }
 
-fn q1(input: &str, question: Question) -> Result<u128, String> {
-    let stones: Result<Vec<Stone>, String> = input
-        .split(" ")
-        .map(|s| s.parse().map_err(|e: ParseIntError| e.to_string()))
-        .collect();
-    let stones = stones?;
+fn q1(stones: &[Stone], question: Question) -> Result<u128, String> {
     let target = match question {
         Question::First => 25,
         Question::Second => 75,
     };
-    let end_stones = (0usize..target).fold(stones, |stones, i| {
-        println!("{} {}", i, stones.len());
+    let end_stones = (0usize..target).fold(stones.to_vec(), |stones, i| {
+        crate::trace!("{} {}", i, stones.len());
         for (k, v) in stones.iter().counts().iter() {
-            println!("{}: {}", k, v);
+            crate::trace!("{}: {}", k, v);
         }
         stones
             .into_iter()
@@ -81,18 +87,14 @@ fn update_with_count(mut map: &mut HashMap<Stone, StoneNode>, key: Stone, count:
         });
 }
 
-fn q2(input: &str, question: Question) -> Result<u128, String> {
-    let stones: Result<Vec<Stone>, String> = input
-        .split(" ")
-        .map(|s| s.parse().map_err(|e: ParseIntError| e.to_string()))
-        .collect();
-    let stones = stones?;
+fn q2(stones: &[Stone], question: Question) -> Result<u128, String> {
     let target = match question {
         Question::First => 25,
         Question::Second => 75,
     };
     let mut stonemap: HashMap<Stone, StoneNode> = stones
-        .into_iter()
+        .iter()
+        .copied()
         .map(|stone| {
             (
                 stone,
@@ -107,7 +109,7 @@ fn q2(input: &str, question: Question) -> Result<u128, String> {
     let end_stones = (0usize..target).fold(stonemap, |stones, i| {
         let mut newmap = HashMap::new();
         stones.iter().for_each(|(stone, node)| {
-            println!("ALRIGHT dealing with {}", stone.clone());
+            crate::trace!("ALRIGHT dealing with {}", stone.clone());
             if let Some(n1) = node.next1.clone() {
                 update_with_count(&mut newmap, n1, node.count.clone());
                 if let Some(n2) = node.next2.clone() {
@@ -138,9 +140,9 @@ fn q2(input: &str, question: Question) -> Result<u128, String> {
             node.next1 = stones.get(stone).map(|n| n.next1.clone()).flatten();
             node.next2 = stones.get(stone).map(|n| n.next2.clone()).flatten();
         });
-        println!("\n\nAt {}", i);
+        crate::trace!("\n\nAt {}", i);
         for (k, v) in &newmap {
-            println!("{}: {}", k, v.count);
+            crate::trace!("{}: {}", k, v.count);
         }
         newmap
     });
@@ -1,18 +1,37 @@
 // ugly and unmaintainable but works ':)
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
 pub struct Day12;
 
+crate::register_day!(Day12, 2024, 12, "Garden Groups", tags: &["grid", "search"]);
+
 impl Day for Day12 {
-    fn question(&self, input: &str, question: Question) {
-        println!("{:?}", q(input));
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input
+            .lines()
+            .map(|line| line.chars().map(|ch| ch).collect_vec())
+            .collect_vec())
+    }
+
+    fn part1(&self, char_map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        let (price, _) = q(char_map);
+        Ok(price.into())
+    }
+
+    fn part2(&self, char_map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        let (_, discounted) = q(char_map);
+        Ok(discounted.into())
     }
 
-    fn test_data(&self) -> String {
-        "RRRRIICCFF
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "RRRRIICCFF
 RRRRIICCCF
 VVRRRCCFFF
 VVRCCCJFFF
@@ -21,8 +40,10 @@ VVIVCCJJEE
 VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
-MMMISSJEEE"
-            .to_string()
+MMMISSJEEE",
+        )
+        .part1(1930)
+        .part2(1206)]
     }
 }
 
@@ -107,11 +128,7 @@ impl Region {
     }
 }
 
-fn q(input: &str) -> (u128, u128) {
-    let char_map = input
-        .lines()
-        .map(|line| line.chars().map(|ch| ch).collect_vec())
-        .collect_vec();
+fn q(char_map: &Vec<Vec<char>>) -> (u128, u128) {
     let mut regions = Vec::new();
     let mut visited = HashSet::new();
     for (i, row) in char_map.iter().enumerate() {
@@ -121,7 +138,7 @@ fn q(input: &str) -> (u128, u128) {
             }
             let mut new_region = Region {
                 name: ch.clone(),
-                coords: find_region(&char_map, (&i, &j), ch, &mut visited),
+                coords: find_region(char_map, (&i, &j), ch, &mut visited),
             };
 
             regions.push(new_region);
@@ -131,7 +148,7 @@ fn q(input: &str) -> (u128, u128) {
     let mut res = 0;
     let mut res2 = 0;
     for r in regions {
-        println!(
+        crate::trace!(
             "{}: {:?} per {} are {} side {}",
             r.name,
             r.coords
@@ -1,6 +1,7 @@
 // originally in the office in python but why not rewrite it
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use regex::Regex;
 use std::any::Any;
 use std::num::ParseIntError;
@@ -8,14 +9,29 @@ use std::str::FromStr;
 
 pub struct Day13;
 
+crate::register_day!(Day13, 2024, 13, "Claw Contraption", tags: &["math"]);
+
 impl Day for Day13 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Vec<Machine>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input
+            .split("\n\n")
+            .map(|s| s.parse())
+            .collect::<Result<_, String>>()?)
+    }
+
+    fn part1(&self, machines: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(machines, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "Button A: X+94, Y+34
+    fn part2(&self, machines: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(machines, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
 
@@ -29,12 +45,15 @@ Prize: X=7870, Y=6450
 
 Button A: X+69, Y+23
 Button B: X+27, Y+71
-Prize: X=18641, Y=10279"
-            .to_string()
+Prize: X=18641, Y=10279",
+        )
+        .part1(480)
+        .part2(875318608908u64)]
     }
 }
 
-struct Machine {
+#[derive(Clone)]
+pub struct Machine {
     ax: u128,
     ay: u128,
     bx: u128,
@@ -129,11 +148,10 @@ impl Machine {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let machines: Result<Vec<Machine>, String> = input.split("\n\n").map(|s| s.parse()).collect();
-    let machines = machines?;
+fn q(machines: &[Machine], question: Question) -> Result<u128, String> {
     let machines: Vec<Machine> = machines
-        .into_iter()
+        .iter()
+        .cloned()
         .map(|m| {
             if question == Question::First {
                 m
@@ -1,22 +1,33 @@
-use crate::common::day::{Day, Question};
-use image::Rgb;
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day14;
 
+crate::register_day!(Day14, 2024, 14, "Restroom Redoubt", tags: &["grid", "simulation"]);
+
 impl Day for Day14 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Vec<Robot>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, robots: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        let (width, height) = (params.get("width")?, params.get("height")?);
+        Ok(q1(robots, width, height, params.get("seconds")?)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "p=0,4 v=3,-3
+    fn part2(&self, robots: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        let (width, height) = (params.get("width")?, params.get("height")?);
+        Ok(q2(robots, width, height)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
 p=2,0 v=2,-1
@@ -27,12 +38,23 @@ p=3,0 v=-1,-2
 p=9,3 v=2,3
 p=7,3 v=-1,2
 p=2,4 v=2,-3
-p=9,5 v=-3,-3"
-            .to_string()
+p=9,5 v=-3,-3",
+        )
+        .param("width", 11)
+        .param("height", 7)
+        .part1(12)]
+    }
+
+    fn params(&self, _question: Question) -> Params {
+        Params::new()
+            .with("width", 101)
+            .with("height", 103)
+            .with("seconds", 100)
     }
 }
 
-struct Robot {
+#[derive(Clone)]
+pub struct Robot {
     x: isize,
     y: isize,
     vx: isize,
@@ -102,56 +124,28 @@ fn print_robots(robots: &Vec<Robot>, width: &usize, height: &usize) {
         map[robot.y as usize][robot.x as usize] += 1;
     }
     for row in map {
-        for ch in row {
-            print!(
-                "{}",
-                if ch == 0 {
+        crate::trace!(
+            "{}",
+            row.iter()
+                .map(|&ch| if ch == 0 {
                     ".".to_string()
                 } else {
                     ch.to_string()
-                }
-            );
-        }
-        println!();
-    }
-}
-
-fn paint_robots(robots: &Vec<Robot>, width: &usize, height: &usize, filename: &str) {
-    let mut image_buf = image::ImageBuffer::new(width.clone() as u32, height.clone() as u32);
-    let mut dst_sum = 0f32;
-    for robot in robots {
-        let pix = image_buf.get_pixel_mut(robot.x as u32, robot.y as u32);
-        *pix = image::Rgb([255u8, 255u8, 255u8]);
-        let distance_from_center = f32::sqrt(
-            (robot.x - width.clone() as isize / 2).pow(2) as f32
-                + (robot.y - height.clone() as isize / 2).pow(2) as f32,
+                })
+                .join("")
         );
-        dst_sum += distance_from_center;
-    }
-    let mean_dist = dst_sum / robots.len() as f32;
-    if mean_dist < 28f32 {
-        println!("{} -- {}", filename, mean_dist);
-        image_buf.save(filename).unwrap();
     }
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let robots: Result<Vec<Robot>, String> = input.lines().map(|l| l.parse()).collect();
-    let robots = robots?;
-    let (width, height) = if robots[0].x == 0 {
-        // test data lol
-        (11usize, 7usize)
-    } else {
-        (101usize, 103usize)
-    };
-    print_robots(&robots, &width, &height);
-    let robots = (0..100).fold(robots, |robots, i| {
+fn q1(robots: &Vec<Robot>, width: usize, height: usize, seconds: usize) -> Result<u128, String> {
+    print_robots(robots, &width, &height);
+    let robots = (0..seconds).fold(robots.to_vec(), |robots, i| {
         robots
             .into_iter()
             .map(|r| r.one_move(width, height))
             .collect()
     });
-    println!("===========");
+    crate::trace!("===========");
     print_robots(&robots, &width, &height);
     let quadrants =
         robots.iter().fold(
@@ -175,21 +169,19 @@ fn q1(input: &str) -> Result<u128, String> {
     Ok(quadrants.0 * quadrants.1 * quadrants.2 * quadrants.3 as u128)
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let mut robots: Result<Vec<Robot>, String> = input.lines().map(|l| l.parse()).collect();
-    let mut robots = robots?;
-    let (width, height) = (101usize, 103usize);
-    for i in 0..10000 {
-        paint_robots(
-            &robots,
-            &width,
-            &height,
-            &format!("test{:0>4}.png", i.to_string()),
-        );
+/// The robots make out the tree the first second no two of them share a tile. They're back where
+/// they started after `width * height` seconds, so it's then or never.
+fn q2(robots: &[Robot], width: usize, height: usize) -> Result<u128, String> {
+    let mut robots = robots.to_vec();
+    for second in 0..width * height {
+        if robots.iter().map(|r| (r.x, r.y)).all_unique() {
+            print_robots(&robots, &width, &height);
+            return Ok(second as u128);
+        }
         robots = robots
             .into_iter()
-            .map(|r| r.one_move(width.clone(), height.clone()))
+            .map(|r| r.one_move(width, height))
             .collect_vec();
     }
-    Ok((1))
+    Err("the robots never spread out".to_string())
 }
@@ -1,19 +1,35 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_grid, Error};
+use crate::common::geom::Direction;
+use crate::common::step;
 use itertools::Itertools;
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub struct Day15;
 
+crate::register_day!(Day15, 2024, 15, "Warehouse Woes", tags: &["grid", "simulation"]);
+
 impl Day for Day15 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Warehouse;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        read_input(input)
+    }
+
+    fn part1(&self, warehouse: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(warehouse, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "##########
+    fn part2(&self, warehouse: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(warehouse, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "##########
 #..O..O.O#
 #......O.#
 #.OO..O.O#
@@ -33,8 +49,10 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 >^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
-            .to_string()
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^",
+        )
+        .part1(10092)
+        .part2(9021)]
     }
 }
 
@@ -48,15 +66,17 @@ enum Pixel {
     BoxRight,
 }
 
-impl From<char> for Pixel {
-    fn from(ch: char) -> Self {
+impl TryFrom<char> for Pixel {
+    type Error = Error;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         use Pixel::*;
         match ch {
-            '#' => Wall,
-            '.' => Empty,
-            '@' => Robot,
-            'O' => Box,
-            _ => panic!("Illegal char in map {}", ch),
+            '#' => Ok(Wall),
+            '.' => Ok(Empty),
+            '@' => Ok(Robot),
+            'O' => Ok(Box),
+            _ => Err(Error::new(format!("Illegal char in map {}", ch))),
         }
     }
 }
@@ -94,19 +114,19 @@ impl Pixel {
     }
 }
 
+#[derive(Clone)]
 struct Map {
     map: Vec<Vec<Pixel>>,
     robot: (usize, usize), // cache it
 }
 
 impl Map {
-    fn read_map(s: &str, question: Question) -> Result<Self, String> {
-        let map = s
-            .lines()
+    fn read_map(s: &str, question: Question) -> Result<Self, Error> {
+        let map = parse_grid::<Pixel>(s)?
+            .into_iter()
             .map(|line| {
-                line.chars()
-                    .map(|ch| {
-                        let pix = Pixel::from(ch);
+                line.into_iter()
+                    .map(|pix| {
                         if question == Question::Second {
                             pix.double_pixel()
                         } else {
@@ -140,7 +160,7 @@ impl Map {
             }
         }
 
-        println!("UMMM {:?}", robot);
+        crate::trace!("UMMM {:?}", robot);
         let robot = robot.ok_or("No robot?".to_string())?;
         // let (x, (y, _)) = robot;
         // let robot = (x, y);
@@ -148,76 +168,30 @@ impl Map {
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-enum Move {
-    Up,
-    Left,
-    Right,
-    Down,
-}
-
-impl From<char> for Move {
-    fn from(ch: char) -> Self {
-        use Move::*;
-        match ch {
-            '^' => Up,
-            '<' => Left,
-            '>' => Right,
-            'v' => Down,
-            _ => panic!("Illegal char in map {}", ch),
-        }
-    }
-}
-
-impl Move {
-    fn transform(&self, (x, y): &(usize, usize)) -> Option<(usize, usize)> {
-        match self {
-            Move::Up => {
-                if *y == 0 {
-                    None
-                } else {
-                    Some((x.clone(), y - 1))
-                }
-            }
-            Move::Left => {
-                if *x == 0 {
-                    None
-                } else {
-                    Some((x - 1, y.clone()))
-                }
-            }
-            Move::Right => Some((x + 1, y.clone())),
-            Move::Down => Some((x.clone(), y + 1)),
-        }
-    }
-    fn inverse_transform(&self, coords: &(usize, usize)) -> Option<(usize, usize)> {
-        match self {
-            Move::Up => Move::Down.transform(coords),
-            Move::Left => Move::Right.transform(coords),
-            Move::Right => Move::Left.transform(coords),
-            Move::Down => Move::Up.transform(coords),
-        }
-    }
-    fn horizontal(&self) -> bool {
-        *self == Move::Left || *self == Move::Right
-    }
-}
-
-fn read_moves(s: &str) -> Vec<Move> {
+fn read_moves(s: &str) -> Result<Vec<Direction>, Error> {
     s.chars()
         .filter(|ch| *ch != '\n')
-        .map(|ch| From::from(ch))
-        .collect_vec()
+        .map(Direction::try_from)
+        .collect()
 }
 
-fn read_input(input: &str, question: Question) -> Result<(Map, Vec<Move>), String> {
+/// Part 2 plays on a map twice as wide, both maps are read up front.
+pub struct Warehouse {
+    map: Map,
+    wide_map: Map,
+    moves: Vec<Direction>,
+}
+
+fn read_input(input: &str) -> Result<Warehouse, Error> {
     let (map, moves) = input
         .split("\n\n")
         .collect_tuple()
         .ok_or("can't split into map n moves")?;
-    let map = Map::read_map(map, question)?;
-    let moves = read_moves(moves);
-    Ok((map, moves))
+    Ok(Warehouse {
+        map: Map::read_map(map, Question::First)?,
+        wide_map: Map::read_map(map, Question::Second)?,
+        moves: read_moves(moves)?,
+    })
 }
 
 impl Map {
@@ -228,10 +202,10 @@ impl Map {
     fn set(&mut self, (x, y): (usize, usize), px: Pixel) {
         self.map[y][x] = px;
     }
-    fn next_step(&mut self, mv: Move) {
-        let mut first_empty = mv.transform(&self.robot);
+    fn next_step(&mut self, mv: Direction) {
+        let mut first_empty = mv.step(self.robot);
         while first_empty.is_some() && self.get(&first_empty.unwrap()).moveable() {
-            first_empty = mv.transform(&first_empty.unwrap());
+            first_empty = mv.step(first_empty.unwrap());
         }
         if first_empty.is_none() {
             return;
@@ -240,13 +214,13 @@ impl Map {
         if self.get(&first_empty) == Pixel::Empty {
             // moveables
             while self.get(&first_empty) != Pixel::Robot {
-                let prev = mv.inverse_transform(&first_empty).unwrap();
+                let prev = mv.opposite().step(first_empty).unwrap();
                 self.set(first_empty, self.get(&prev));
                 first_empty = prev;
             }
             // clean up
             self.set(first_empty, Pixel::Empty);
-            self.robot = mv.transform(&first_empty).unwrap();
+            self.robot = mv.step(first_empty).unwrap();
         }
     }
     fn box_coords(&self) -> u128 {
@@ -263,12 +237,11 @@ impl Map {
             .flatten()
             .sum()
     }
-    fn q_2_next_step(&mut self, mv: Move) {
+    fn q_2_next_step(&mut self, mv: Direction) {
         if let Some(moveables) = self.q_2_moveable(&self.robot, mv) {
             let mut new_map = self.map.clone();
             for moveable in moveables {
-                new_map[moveable.1][moveable.0] =
-                    self.get(&mv.inverse_transform(&moveable).unwrap())
+                new_map[moveable.1][moveable.0] = self.get(&mv.opposite().step(moveable).unwrap())
             }
             self.map = new_map;
             self.set(self.robot, Pixel::Empty);
@@ -283,7 +256,7 @@ impl Map {
                     }
                 }
             }
-            if !mv.horizontal() {
+            if !mv.is_horizontal() {
                 if self.get(&(self.robot.0 + 1, self.robot.1)) == Pixel::BoxRight {
                     self.map[self.robot.1][self.robot.0 + 1] = Pixel::Empty;
                 }
@@ -292,15 +265,15 @@ impl Map {
                     self.map[self.robot.1][self.robot.0 - 1] = Pixel::Empty;
                 }
             }
-            self.robot = mv.transform(&self.robot).unwrap();
+            self.robot = mv.step(self.robot).unwrap();
         }
     }
     fn q_2_moveable(
         &self,
         coords: &(usize, usize),
-        direction: Move,
+        direction: Direction,
     ) -> Option<HashSet<(usize, usize)>> {
-        match direction.transform(coords) {
+        match direction.step(*coords) {
             None => None,
             Some(px) => match self.get(&px) {
                 Pixel::Wall => None,
@@ -317,14 +290,14 @@ impl Map {
                     }
                 }
                 Pixel::Robot => panic!("what's going on {:?} {:?}", px, coords),
-                Pixel::BoxLeft if direction.horizontal() => {
+                Pixel::BoxLeft if direction.is_horizontal() => {
                     self.q_2_moveable(&px, direction).map(|set| {
                         set.union(&HashSet::from([px.clone()]))
                             .map(|x| *x)
                             .collect()
                     })
                 }
-                Pixel::BoxRight if direction.horizontal() => {
+                Pixel::BoxRight if direction.is_horizontal() => {
                     self.q_2_moveable(&px, direction).map(|set| {
                         set.union(&HashSet::from([px.clone()]))
                             .map(|x| *x)
@@ -382,13 +355,25 @@ impl Display for Map {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let (mut map, moves) = read_input(input, question)?;
-    for mv in moves {
-        // println!("move {:?}", mv);
-        // println!("rob {:?}", map.robot);
-        // println!("{}", map);
+fn q(warehouse: &Warehouse, question: Question) -> Result<u128, String> {
+    let mut map = match question {
+        Question::First => warehouse.map.clone(),
+        Question::Second => warehouse.wide_map.clone(),
+    };
+    for (i, &mv) in warehouse.moves.iter().enumerate() {
         map.q_2_next_step(mv);
+        step::tick(
+            || {
+                format!(
+                    "move {}/{} {:?}, robot at {:?}",
+                    i + 1,
+                    warehouse.moves.len(),
+                    mv,
+                    map.robot
+                )
+            },
+            || map.to_string(),
+        );
     }
     Ok(map.box_coords())
 }
@@ -1,56 +1,78 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day17;
 
+crate::register_day!(Day17, 2024, 17, "Chronospatial Computer", parts: 1, tags: &["interpreter"]);
+
 impl Day for Day17 {
-    fn question(&self, input: &str, question: Question) {
-        let machine: Machine = input.parse().unwrap();
-        let outputs = machine.run();
-        println!("{}", outputs.iter().join(","));
-
-        let mut q2_machine = machine.clone();
-        // bit brute forced but the machine goes by octal digits and the worst case is it's influenced
-        // by 3 digits in total, so... yeah. I tried it on pen & paper, failed tho
-        for i in 0..0o1000 {
-            q2_machine.state.reg_a = i + 0o6562166052247000; // + 0o61176574462000000;
-            let outputs = q2_machine.run();
-            if outputs.len() >= 5
-                && outputs
-                    .iter()
-                    .rev()
-                    .zip(machine.code.iter().rev())
-                    .take(16)
-                    .all(|(x, y)| *x == *y)
-            {
-                println!(
-                    "{} {:o} -- {}",
-                    q2_machine.state.reg_a,
-                    q2_machine.state.reg_a,
-                    outputs.iter().join(",")
-                );
-            }
+    type Input = Machine;
 
-            // if outputs == machine.code {
-            //     println!("Found: {}", i);
-            //     return;
-            // }
-            // if i % 100000 == 0 {
-            //     println!("past {}", i);
-            // }
-        }
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, machine: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        solve(machine, Question::First)
+    }
+
+    fn part2(&self, machine: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        solve(machine, Question::Second)
     }
 
-    fn test_data(&self) -> String {
-        "Register A: 729
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "Register A: 729
 Register B: 0
 Register C: 0
 
-Program: 0,1,5,4,3,0"
-            .to_string()
+Program: 0,1,5,4,3,0",
+        )
+        .part1("4,6,3,5,6,3,5,2,1,0")]
+    }
+}
+
+fn solve(machine: &Machine, question: Question) -> Result<Answer, Error> {
+    let outputs = machine.run();
+    if question == Question::First {
+        return Ok(Answer::text(outputs.iter().join(",")));
+    }
+
+    let mut q2_machine = machine.clone();
+    // bit brute forced but the machine goes by octal digits and the worst case is it's influenced
+    // by 3 digits in total, so... yeah. I tried it on pen & paper, failed tho
+    for i in 0..0o1000 {
+        q2_machine.state.reg_a = i + 0o6562166052247000; // + 0o61176574462000000;
+        let outputs = q2_machine.run();
+        if outputs.len() >= 5
+            && outputs
+                .iter()
+                .rev()
+                .zip(machine.code.iter().rev())
+                .take(16)
+                .all(|(x, y)| *x == *y)
+        {
+            return Ok(
+                Answer::from(q2_machine.state.reg_a).with_diagnostic(format!(
+                    "{:o} -- {}",
+                    q2_machine.state.reg_a,
+                    outputs.iter().join(",")
+                )),
+            );
+        }
+
+        // if outputs == machine.code {
+        //     println!("Found: {}", i);
+        //     return;
+        // }
+        // if i % 100000 == 0 {
+        //     println!("past {}", i);
+        // }
     }
+    Err(Error::new("no quine found"))
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -62,7 +84,7 @@ struct MachineState {
 }
 
 #[derive(Clone)]
-struct Machine {
+pub struct Machine {
     state: MachineState,
     code: Vec<u8>,
 }
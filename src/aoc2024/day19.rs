@@ -1,34 +1,32 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use regex::Regex;
 use std::cmp::Ordering;
+use std::str::FromStr;
 
 pub struct Day19;
 
+crate::register_day!(Day19, 2024, 19, "Linen Layout", tags: &["strings", "dp"]);
+
 impl Day for Day19 {
-    fn question(&self, input: &str, question: Question) {
-        let availables: Vec<_> = input.lines().nth(0).unwrap().split(", ").collect_vec();
-        let to_design = input.lines().skip(2).collect_vec();
-        if question == Question::Second {
-            q2(input, availables, to_design);
-            return;
-        }
-        let re = Regex::new(&format!("^({})*$", availables.join("|"))).unwrap();
-        let n = to_design
-            .iter()
-            .filter(|av| {
-                // let ma = re.find(av);
-                // if let Some(ma) = ma {
-                //     println!("{}: {}", av, ma.as_str());
-                // }
-                re.is_match(av)
-            })
-            .count();
-        println!("{}", n);
+    type Input = Onsen;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input.parse()
     }
 
-    fn test_data(&self) -> String {
-        "r, wr, b, g, bwu, rb, gb, br
+    fn part1(&self, onsen: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(onsen, Question::First))
+    }
+
+    fn part2(&self, onsen: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(onsen, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "r, wr, b, g, bwu, rb, gb, br
 
 brwrr
 bggr
@@ -37,11 +35,53 @@ rrbgbr
 ubwu
 bwurrg
 brgr
-bbrgwb"
-            .to_string()
+bbrgwb",
+        )
+        .part1(6)
+        .part2(16)]
     }
 }
 
+/// The towel patterns on offer, and the designs to make of them.
+pub struct Onsen {
+    towels: Vec<String>,
+    designs: Vec<String>,
+}
+
+impl FromStr for Onsen {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (towels, designs) = s
+            .split_once("\n\n")
+            .ok_or("no empty line between the towels and the designs")?;
+        Ok(Onsen {
+            towels: towels.trim().split(", ").map(String::from).collect(),
+            designs: designs.lines().map(String::from).collect(),
+        })
+    }
+}
+
+fn q(onsen: &Onsen, question: Question) -> Answer {
+    let availables = onsen.towels.iter().map(String::as_str).collect_vec();
+    let to_design = onsen.designs.iter().map(String::as_str).collect_vec();
+    if question == Question::Second {
+        return q2(availables, to_design).into();
+    }
+    let re = Regex::new(&format!("^({})*$", availables.join("|"))).unwrap();
+    let n = to_design
+        .iter()
+        .filter(|av| {
+            // let ma = re.find(av);
+            // if let Some(ma) = ma {
+            //     println!("{}: {}", av, ma.as_str());
+            // }
+            re.is_match(av)
+        })
+        .count();
+    n.into()
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum Possibility<'a> {
     AllOpen(usize),
@@ -83,7 +123,7 @@ fn merge_states(states: Vec<Possibility>) -> Vec<Possibility> {
     merged_states
 }
 
-fn q2(input: &str, availables: Vec<&str>, to_design: Vec<&str>) {
+fn q2(availables: Vec<&str>, to_design: Vec<&str>) -> usize {
     let mut count = 0usize;
     for target in to_design {
         let mut state = vec![Possibility::AllOpen(1)];
@@ -134,7 +174,7 @@ fn q2(input: &str, availables: Vec<&str>, to_design: Vec<&str>) {
             })
             .sum::<usize>();
         count += hits;
-        println!("{}: {}", target, hits);
+        crate::trace!("{}: {}", target, hits);
     }
-    println!("{}", count);
+    count
 }
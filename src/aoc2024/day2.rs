@@ -1,30 +1,45 @@
 // baaaaaaaaaaaaaaad traveling day
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub struct Day2;
 
+crate::register_day!(Day2, 2024, 2, "Red-Nosed Reports", tags: &["parsing"]);
+
 impl Day for Day2 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Vec<Report>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, reports: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(reports, Question::First)?.into())
+    }
+
+    fn part2(&self, reports: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(reports, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "7 6 4 2 1
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
 1 3 2 4 5
 8 6 4 4 1
-1 3 6 7 9"
-            .to_string()
+1 3 6 7 9",
+        )
+        .part1(2)
+        .part2(4)]
     }
 }
 
-struct Report {
+pub struct Report {
     nums: Vec<u128>,
 }
 
@@ -79,11 +94,11 @@ impl Report {
                     } else if dir == Direction::Increase && prev > n {
                         (false, n, dir)
                     } else {
-                        println!("OKAY {} {} {:?}", prev, n, dir);
+                        crate::trace!("OKAY {} {} {:?}", prev, n, dir);
                         (true, n, dir)
                     }
                 });
-        println!("{}", good);
+        crate::trace!("{}", good);
         good
     }
     fn permutate(&self) -> impl Iterator<Item = Report> + '_ {
@@ -99,18 +114,16 @@ impl Report {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let reports: Result<Vec<Report>, String> = input.lines().map(|l| l.parse()).collect();
-    let reports = reports?;
-    println!("{}", reports.len());
+fn q(reports: &[Report], question: Question) -> Result<u128, String> {
+    crate::debug!("{}", reports.len());
     if question == Question::First {
-        Ok(reports.into_iter().filter(|r| r.pass()).count() as u128)
+        Ok(reports.iter().filter(|r| r.pass()).count() as u128)
     } else {
         Ok(reports
-            .into_iter()
+            .iter()
             .map(|r| {
                 r.permutate().any(|r2| {
-                    println!("PERMI {:?} {}", r2.nums, r2.pass());
+                    crate::trace!("PERMI {:?} {}", r2.nums, r2.pass());
                     r2.pass()
                 })
             })
@@ -1,97 +1,115 @@
 // doesn't work bc dorsnt' find the optimal -- it's not even looking
 
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_grid, Error};
 use itertools::{repeat_n, Itertools};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 pub struct Day21;
 
+crate::register_day!(Day21, 2024, 21, "Keypad Conundrum", parts: 0, tags: &["search", "dp"]);
+
 impl Day for Day21 {
-    fn question(&self, input: &str, question: Question) {
-        let tasks = input
-            .lines()
-            .map(|task| task.chars().map(|ch| Button::from(ch)).collect_vec())
+    type Input = Vec<Vec<Button>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_grid(input)?
+            .into_iter()
             .map(|mv| prepend_a(mv))
-            .collect_vec();
-        let main_board = Board {
-            map: HashMap::from([
-                (Button::Digit(7), (0, 0)),
-                (Button::Digit(8), (1, 0)),
-                (Button::Digit(9), (2, 0)),
-                (Button::Digit(4), (0, 1)),
-                (Button::Digit(5), (1, 1)),
-                (Button::Digit(6), (2, 1)),
-                (Button::Digit(1), (0, 2)),
-                (Button::Digit(2), (1, 2)),
-                (Button::Digit(3), (2, 2)),
-                (Button::Digit(0), (1, 3)),
-                (Button::A, (2, 3)),
-            ]),
-        };
-        let control_board = Board {
-            map: HashMap::from([
-                (Button::Up, (1, 0)),
-                (Button::A, (2, 0)),
-                (Button::Left, (0, 1)),
-                (Button::Down, (1, 1)),
-                (Button::Right, (2, 1)),
-            ]),
-        };
-        let mut result = 0;
-        for task in tasks {
-            let raw = raw_num(&task);
-            let moves = main_board.run_task(task);
-            println!("{}", print_moves(&moves));
-            let moves2 = control_board.run_task(prepend_a(moves));
-            println!("{}", print_moves(&moves2));
-            let moves3 = control_board.run_task(prepend_a(moves2));
-            println!("{}", print_moves(&moves3));
-            println!("{}", moves3.len());
-            println!("{}", raw);
-            result += moves3.len() as u128 * raw;
-
-            println!("Reversing...");
-            let removes2 = control_board.play_moves(moves3);
-            if removes2.is_none() {
-                println!("Megafail");
-                continue;
-            }
-            let removes2 = removes2.unwrap();
-            println!("{}", print_moves(&removes2));
-            let removes1 = control_board.play_moves(removes2);
-            if removes1.is_none() {
-                println!("Megafail");
-                continue;
-            }
-            let removes1 = removes1.unwrap();
-            println!("{}", print_moves(&removes1));
-            let reraw = main_board.play_moves(removes1);
-            if reraw.is_none() {
-                println!("Megafail");
-                continue;
-            }
-            println!("{}", print_moves(&reraw.unwrap()));
+            .collect_vec())
+    }
 
-            println!();
-        }
+    fn part1(&self, tasks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(tasks))
+    }
 
-        println!("{}", result);
+    fn part2(&self, _tasks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
     }
 
-    fn test_data(&self) -> String {
-        "029A
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "029A
 980A
 179A
 456A
-379A"
-            .to_string()
+379A",
+        )
+        .part1(126384)]
     }
 }
 
+fn q1(tasks: &[Vec<Button>]) -> Answer {
+    let main_board = Board {
+        map: HashMap::from([
+            (Button::Digit(7), (0, 0)),
+            (Button::Digit(8), (1, 0)),
+            (Button::Digit(9), (2, 0)),
+            (Button::Digit(4), (0, 1)),
+            (Button::Digit(5), (1, 1)),
+            (Button::Digit(6), (2, 1)),
+            (Button::Digit(1), (0, 2)),
+            (Button::Digit(2), (1, 2)),
+            (Button::Digit(3), (2, 2)),
+            (Button::Digit(0), (1, 3)),
+            (Button::A, (2, 3)),
+        ]),
+    };
+    let control_board = Board {
+        map: HashMap::from([
+            (Button::Up, (1, 0)),
+            (Button::A, (2, 0)),
+            (Button::Left, (0, 1)),
+            (Button::Down, (1, 1)),
+            (Button::Right, (2, 1)),
+        ]),
+    };
+    let mut result = 0;
+    for task in tasks.iter().cloned() {
+        let raw = raw_num(&task);
+        let moves = main_board.run_task(task);
+        crate::trace!("{}", print_moves(&moves));
+        let moves2 = control_board.run_task(prepend_a(moves));
+        crate::trace!("{}", print_moves(&moves2));
+        let moves3 = control_board.run_task(prepend_a(moves2));
+        crate::trace!("{}", print_moves(&moves3));
+        crate::trace!("{}", moves3.len());
+        crate::trace!("{}", raw);
+        result += moves3.len() as u128 * raw;
+
+        crate::trace!("Reversing...");
+        let removes2 = control_board.play_moves(moves3);
+        if removes2.is_none() {
+            crate::trace!("Megafail");
+            continue;
+        }
+        let removes2 = removes2.unwrap();
+        crate::trace!("{}", print_moves(&removes2));
+        let removes1 = control_board.play_moves(removes2);
+        if removes1.is_none() {
+            crate::trace!("Megafail");
+            continue;
+        }
+        let removes1 = removes1.unwrap();
+        crate::trace!("{}", print_moves(&removes1));
+        let reraw = main_board.play_moves(removes1);
+        if reraw.is_none() {
+            crate::trace!("Megafail");
+            continue;
+        }
+        crate::trace!("{}", print_moves(&reraw.unwrap()));
+
+        crate::trace!("");
+    }
+
+    result.into()
+}
+
 type Coord = (isize, isize);
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
-enum Button {
+pub enum Button {
     Up,
     Down,
     Left,
@@ -100,12 +118,14 @@ enum Button {
     Digit(usize),
 }
 
-impl From<char> for Button {
-    fn from(ch: char) -> Self {
+impl TryFrom<char> for Button {
+    type Error = String;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            n if n >= '0' && n <= '9' => Button::Digit(n.to_digit(10).unwrap() as usize),
-            'A' => Button::A,
-            _ => panic!("uuuf"),
+            n if n >= '0' && n <= '9' => Ok(Button::Digit(n.to_digit(10).unwrap() as usize)),
+            'A' => Ok(Button::A),
+            _ => Err(format!("'{}' isn't a key on the door's keypad", ch)),
         }
     }
 }
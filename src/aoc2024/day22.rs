@@ -1,20 +1,35 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet, VecDeque};
 
 pub struct Day22;
 
+crate::register_day!(Day22, 2024, 22, "Monkey Market", tags: &["simulation"]);
+
 impl Day for Day22 {
-    fn question(&self, input: &str, question: Question) {
-        println!("{:?}", q(input, question));
+    type Input = Vec<u128>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, starts: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(starts, Question::First)?.into())
+    }
+
+    fn part2(&self, starts: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(starts, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "1
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "1
 10
 100
-2024"
-            .to_string()
+2024",
+        )
+        .part1(37327623)]
     }
 }
 
@@ -35,12 +50,7 @@ fn next_random(x: u128) -> u128 {
     prune(mix(x_2, c))
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let starts: Result<Vec<u128>, _> = input
-        .lines()
-        .map(|s| s.parse::<u128>().map_err(|e| e.to_string()))
-        .collect();
-    let starts = starts?;
+fn q(starts: &[u128], question: Question) -> Result<u128, String> {
     if question == Question::First {
         let ends = starts
             .iter()
@@ -49,16 +59,16 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
         Ok(ends.into_iter().sum())
     } else {
         let mut winnings_map = HashMap::new();
-        for start in starts {
+        for &start in starts {
             let mut seen_map = HashSet::new();
             let mut queue = VecDeque::new();
             let mut old_secret = start;
-            // println!("{}: {}", old_secret, old_secret % 10);
+            crate::trace!("{}: {}", old_secret, old_secret % 10);
             for i in 1..2000 {
                 let new_secret = next_random(old_secret);
                 let winnings = new_secret % 10;
                 let delta = winnings as i32 - (old_secret % 10) as i32;
-                // println!("{}: {} ({})", new_secret, winnings, delta);
+                crate::trace!("{}: {} ({})", new_secret, winnings, delta);
                 queue.push_front(delta);
                 if queue.len() == 5 {
                     queue.pop_back();
@@ -72,18 +82,17 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
                             .entry(tup)
                             .and_modify(|x| *x += winnings.clone())
                             .or_insert(winnings);
-                        // println!("Inserted {:?} with {}", tup, winnings);
+                        crate::trace!("Inserted {:?} with {}", tup, winnings);
                     }
                 }
                 old_secret = new_secret;
             }
-            // println!("\n\n\n=========================================\n=========================================\n=========================================\n\n\n\n")
         }
         winnings_map
             .iter()
             .max_by_key(|(_, v)| **v)
             .map(|n| {
-                println!("{:?}", n);
+                crate::debug!("Best sequence {:?}", n);
                 *n.1
             })
             .ok_or("no max?".to_string())
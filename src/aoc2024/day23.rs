@@ -1,22 +1,34 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
 pub struct Day23;
 
+crate::register_day!(Day23, 2024, 23, "LAN Party", tags: &["graph"]);
+
 impl Day for Day23 {
-    fn question(&self, input: &str, question: Question) {
-        if question == Question::First {
-            let cnt = Day23::q1(input);
-            println!("{}", cnt);
-        } else {
-            Day23::q2(input);
+    type Input = HashMap<String, HashSet<String>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(Day23::read_connections(input))
+    }
+
+    fn part1(&self, connections: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(Day23::q1(connections).into())
+    }
+
+    fn part2(&self, connections: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        match Day23::q2(connections) {
+            Some(password) => Ok(password.into()),
+            None => Err(Error::new("no single largest LAN party")),
         }
     }
 
-    fn test_data(&self) -> String {
-        "kh-tc
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "kh-tc
 qp-kh
 de-cg
 ka-co
@@ -47,14 +59,15 @@ kh-ta
 co-tc
 wh-qp
 tb-vc
-td-yn"
-            .to_string()
+td-yn",
+        )
+        .part1(7)
+        .part2("co,de,ka,ta")]
     }
 }
 
 impl Day23 {
-    fn q1(input: &str) -> usize {
-        let connections = Day23::read_connections(input);
+    fn q1(connections: &HashMap<String, HashSet<String>>) -> usize {
         let mut triplets: HashSet<(String, String, String)> = HashSet::new();
         for conn in connections.clone() {
             let combos: HashSet<(String, String, String)> = conn
@@ -81,14 +94,14 @@ impl Day23 {
                     || t.2.chars().nth(0).unwrap() == 't'
             })
             .map(|trip| {
-                println!("{:?}", trip);
+                crate::trace!("{:?}", trip);
                 trip
             })
             .count();
         cnt
     }
-    fn q2(input: &str) {
-        let connections = Day23::read_connections(input);
+    fn q2(connections: &HashMap<String, HashSet<String>>) -> Option<String> {
+        let mut password = None;
         let mut size = 1usize;
         let all_computers: HashSet<_> = connections.keys().map(|x| x.clone()).collect();
         let mut friends: HashSet<_> = connections
@@ -113,17 +126,17 @@ impl Day23 {
                     }
                 }
             }
-            println!("Size {}: {}", size, new_friends.len());
+            crate::trace!("Size {}: {}", size, new_friends.len());
             if new_friends.len() == 1 {
                 let ordered = new_friends
                     .iter()
                     .map(|c| c.0.iter().sorted().join(","))
                     .nth(0)
                     .unwrap();
-                println!("{}", ordered);
+                password = Some(ordered);
             }
             if new_friends.len() == 0 {
-                return;
+                return password;
             }
             friends = new_friends;
         }
@@ -1,30 +1,34 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use std::time::SystemTime;
 use utf_railroad::{Choice, Diagram, NonTerminal, Terminal};
 
 pub struct Day24;
 
+crate::register_day!(Day24, 2024, 24, "Crossed Wires", parts: 1, tags: &["graph", "simulation"]);
+
 impl Day for Day24 {
-    fn question(&self, input: &str, question: Question) {
-        let mut machine = Machine::from_str(input);
-        if let Err(e) = machine {
-            println!("Error parsing: {}", e);
-            return;
-        }
-        if question == Question::First {
-            q1(machine.unwrap());
-        } else {
-            q2(machine.unwrap());
-        }
+    type Input = Machine;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(Machine::from_str(input)?)
+    }
+
+    fn part1(&self, machine: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(machine))
     }
 
-    fn test_data(&self) -> String {
-        "x00: 1
+    fn part2(&self, machine: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        q2(machine)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "x00: 1
 x01: 0
 x02: 1
 x03: 1
@@ -70,12 +74,14 @@ bqk OR frj -> z07
 y03 OR x01 -> nrd
 hwm AND bqk -> z03
 tgd XOR rvg -> z12
-tnw OR pbm -> gnj"
-            .to_string()
+tnw OR pbm -> gnj",
+        )
+        .part1(2024)]
     }
 }
 
-struct Machine {
+#[derive(Clone)]
+pub struct Machine {
     state: HashMap<String, Option<bool>>,
     gates: Vec<Gate>,
 }
@@ -141,7 +147,7 @@ impl FromStr for Gate {
 }
 
 impl FromStr for Machine {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (initial_state, gates) = s
@@ -152,20 +158,21 @@ impl FromStr for Machine {
             state: HashMap::new(),
             gates: vec![],
         };
-        for l in initial_state.lines() {
+        for (i, l) in initial_state.lines().enumerate() {
             let (name, value) = l
                 .split(": ")
                 .collect_tuple()
-                .ok_or(format!("Can't split {}", l))?;
+                .ok_or_else(|| Error::new(format!("Can't split {}", l)).on_line(i))?;
             let value = match value {
                 "0" => Ok(false),
                 "1" => Ok(true),
-                _ => Err(format!("invalid initial value {}", value)),
+                _ => Err(Error::new(format!("invalid initial value {}", value)).on_line(i)),
             }?;
             out.state.insert(name.to_string(), Some(value));
         }
-        for l in gates.lines() {
-            let gate = Gate::from_str(l)?;
+        let first_gate_line = initial_state.lines().count() + 1;
+        for (i, l) in gates.lines().enumerate() {
+            let gate = Gate::from_str(l).map_err(|e| Error::new(e).on_line(first_gate_line + i))?;
             for register in [&gate.in1, &gate.in2, &gate.out] {
                 if !out.state.contains_key(register) {
                     out.state.insert(register.clone(), None);
@@ -282,8 +289,8 @@ impl Machine {
     }
 }
 
-fn q1(mut machine: Machine) {
-    let start = SystemTime::now();
+fn q1(machine: &Machine) -> Answer {
+    let mut machine = machine.clone();
     let mut changed = true;
     while changed {
         changed = machine.run_one_set();
@@ -291,21 +298,22 @@ fn q1(mut machine: Machine) {
     let x = machine.read_result('x');
     let y = machine.read_result('y');
     let z = machine.read_result('z');
-    println!("{} + {} =? {}", x, y, z);
-    println!("{:b}", z);
-    println!("{:b}", x + y);
-    println!("{}ms", start.elapsed().unwrap().as_millis())
+    Answer::from(z)
+        .with_diagnostic(format!("{} + {} =? {}", x, y, z))
+        .with_diagnostic(format!("{:b}", z))
+        .with_diagnostic(format!("{:b}", x + y))
 }
 
-fn q2(mut machine: Machine) {
+/// Not solved in code: the swapped wires are read off the diagrams, which `-v` prints.
+fn q2(machine: &Machine) -> Result<Answer, Error> {
+    let mut machine = machine.clone();
     let all_cells = machine.state.keys().sorted();
     // .filter(|s| s.chars().nth(0).unwrap() == 'z');
     for cell in all_cells {
         let (list, involveds, diagram) = machine.print_cell(cell);
-        println!("{} = {}", cell, list.last().unwrap());
-
+        crate::debug!("{} = {}", cell, list.last().unwrap());
         if cell.chars().nth(0).unwrap() == 'z' {
-            println!("{}", diagram);
+            crate::debug!("{}", diagram);
         }
         /* println!(
             "involveds {} = {}",
@@ -313,4 +321,7 @@ fn q2(mut machine: Machine) {
             involveds.iter().sorted().join(",")
         );*/
     }
+    Err(Error::new(
+        "the swapped wires have to be read off the diagrams, -v prints them",
+    ))
 }
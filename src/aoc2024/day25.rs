@@ -1,42 +1,32 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub struct Day25;
 
+crate::register_day!(Day25, 2024, 25, "Code Chronicle", tags: &["grid", "parsing"]);
+
 impl Day for Day25 {
-    fn question(&self, input: &str, question: Question) {
-        let things = read_input(input);
-        if let Err(e) = things {
-            println!("Failed to read things: {}", e);
-        } else {
-            let things = things.unwrap();
-            let (keys, locks) = things
-                .into_iter()
-                .fold((vec![], vec![]), |(keys, locks), item| {
-                    if let Thing::Key(ref n) = item {
-                        (vec![keys, vec![item]].concat(), locks)
-                    } else {
-                        (keys, vec![locks, vec![item]].concat())
-                    }
-                });
-            let fits = keys.iter().fold(0, |acc, key| {
-                acc + locks.iter().fold(0, |lock_acc, lock| {
-                    if fit(key, lock) {
-                        lock_acc + 1
-                    } else {
-                        lock_acc
-                    }
-                })
-            });
-            println!("{}", fits);
-        }
+    type Input = Vec<Thing>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_input(input)?)
+    }
+
+    fn part1(&self, things: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(things))
     }
 
-    fn test_data(&self) -> String {
-        "#####
+    fn part2(&self, things: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(things))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "#####
 .####
 .####
 .####
@@ -74,13 +64,37 @@ impl Day for Day25 {
 #....
 #.#..
 #.#.#
-#####"
-            .to_string()
+#####",
+        )
+        .part1(3)]
     }
 }
 
+fn q(things: &[Thing]) -> Answer {
+    let (keys, locks) = things
+        .iter()
+        .cloned()
+        .fold((vec![], vec![]), |(keys, locks), item| {
+            if let Thing::Key(ref n) = item {
+                (vec![keys, vec![item]].concat(), locks)
+            } else {
+                (keys, vec![locks, vec![item]].concat())
+            }
+        });
+    let fits = keys.iter().fold(0, |acc, key| {
+        acc + locks.iter().fold(0, |lock_acc, lock| {
+            if fit(key, lock) {
+                lock_acc + 1
+            } else {
+                lock_acc
+            }
+        })
+    });
+    fits.into()
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
-enum Thing {
+pub enum Thing {
     Key(Vec<usize>),
     Lock(Vec<usize>),
 }
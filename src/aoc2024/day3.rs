@@ -1,17 +1,33 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use regex::Regex;
 use std::str::Split;
 
 pub struct Day3;
 
+crate::register_day!(Day3, 2024, 3, "Mull It Over", tags: &["strings"]);
+
 impl Day for Day3 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(input, Question::First)?.into())
+    }
+
+    fn part2(&self, input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(input, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))".to_string()
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        )
+        .part1(161)
+        .part2(48)]
     }
 }
 
@@ -36,7 +52,7 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
         let (sum, _) = regexp
             .find_iter(input)
             .fold((0u128, true), |(sumsofar, switch), m| {
-                println!("{} {}", m.as_str(), switch);
+                crate::trace!("{} {}", m.as_str(), switch);
                 if m.as_str() == "do()" {
                     (sumsofar, true)
                 } else if m.as_str() == "don't()" {
@@ -1,21 +1,31 @@
-use crate::common::day::{Day, Question};
-use crate::common::map::Map;
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
+use crate::common::map::Grid;
 use serde_json::de::Read;
 use std::ops::Range;
 
 pub struct Day4;
 
+crate::register_day!(Day4, 2024, 4, "Ceres Search", tags: &["grid"]);
+
 impl Day for Day4 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Grid<char>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(map)?.into())
+    }
+
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(map)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "MMMSXXMASM
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "MMMSXXMASM
 MSAMXMSMSA
 AMXSXMAAMM
 MSAMASMSMX
@@ -24,8 +34,10 @@ XXAMMXXAMA
 SMSMSASXSS
 SAXAMASAAA
 MAMMMXMMMM
-MXMXAXMASX"
-            .to_string()
+MXMXAXMASX",
+        )
+        .part1(18)
+        .part2(9)]
     }
 }
 
@@ -99,26 +111,30 @@ impl Iterator for DiagonalWalkthrough {
     }
 }
 
-fn walk_map<I>(map: &Map<char>, iter: I) -> u128
+fn walk_map<I>(map: &Grid<char>, iter: I) -> u128
 where
     I: Iterator<Item = Pointer>,
 {
-    println!("=====================");
+    crate::debug!("=====================");
     let (res, _, _) = iter.fold(
         (0u128, State::X, State::S),
         |(finds, state, revstate), pointer| match pointer {
             Pointer::Point(x, y) => {
-                let point_ch = map.get_no_floor(x, y);
-                println!(
+                let point_ch = map[(x, y)];
+                crate::trace!(
                     "{} {} {} hoping for {:?} rev {:?}",
-                    x, y, point_ch, state, revstate
+                    x,
+                    y,
+                    point_ch,
+                    state,
+                    revstate
                 );
                 let (finds, state) = match (point_ch, state) {
                     ('X', _) => (finds, State::M),
                     ('M', State::M) => (finds, State::A),
                     ('A', State::A) => (finds, State::S),
                     ('S', State::S) => {
-                        println!("OLE!");
+                        crate::trace!("OLE!");
                         (finds + 1, State::X)
                     }
                     _ => (finds, State::X),
@@ -128,7 +144,7 @@ where
                     ('A', State::A) => (finds, State::M),
                     ('M', State::M) => (finds, State::X),
                     ('X', State::X) => {
-                        println!("REVOLE!");
+                        crate::trace!("REVOLE!");
                         (finds + 1, State::S)
                     }
                     _ => (finds, State::S),
@@ -138,15 +154,14 @@ where
             Pointer::EndOfRow => (finds, State::X, State::S),
         },
     );
-    println!("RES {}", res);
+    crate::debug!("RES {}", res);
     res
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let map: Map<char> = input.parse()?;
+fn q1(map: &Grid<char>) -> Result<u128, String> {
     let rot_map = map.rotate();
-    println!("{}", map);
-    println!("======\n{}", rot_map);
+    crate::debug!("{}", map);
+    crate::debug!("======\n{}", rot_map);
     Ok(walk_map(
         &map,
         RowWalkthrough {
@@ -182,20 +197,19 @@ fn q1(input: &str) -> Result<u128, String> {
     ))
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let map: Map<char> = input.parse()?;
+fn q2(map: &Grid<char>) -> Result<u128, String> {
     let mut found_without_thinking = 0u128;
     for x in 0..(map.width() - 2) {
         for y in 0..(map.height() - 2) {
             let test = format!(
                 "{}{}{}{}{}",
-                map.get_no_floor(x, y),
-                map.get_no_floor(x + 2, y),
-                map.get_no_floor(x + 1, y + 1),
-                map.get_no_floor(x, y + 2),
-                map.get_no_floor(x + 2, y + 2)
+                map[(x, y)],
+                map[(x + 2, y)],
+                map[(x + 1, y + 1)],
+                map[(x, y + 2)],
+                map[(x + 2, y + 2)]
             );
-            println!("{}", test);
+            crate::trace!("{}", test);
             if test == "MMASS" || test == "MSAMS" || test == "SMASM" || test == "SSAMM" {
                 found_without_thinking += 1;
             }
@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
@@ -7,17 +8,26 @@ use std::str::FromStr;
 
 pub struct Day5;
 
+crate::register_day!(Day5, 2024, 5, "Print Queue", tags: &["graph", "sorting"]);
+
 impl Day for Day5 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = (RuleBook, Vec<Book>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self, (rule_book, books): &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(rule_book, books)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "47|53
+    fn part2(&self, (rule_book, books): &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(rule_book, books)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "47|53
 97|13
 97|61
 97|47
@@ -44,8 +54,10 @@ impl Day for Day5 {
 75,29,13
 75,97,47,61,53
 61,13,29
-97,13,75,29,47"
-            .to_string()
+97,13,75,29,47",
+        )
+        .part1(143)
+        .part2(123)]
     }
 }
 
@@ -65,7 +77,7 @@ impl FromStr for Rule {
     }
 }
 
-struct Book(Vec<usize>);
+pub struct Book(Vec<usize>);
 
 impl Clone for Book {
     fn clone(&self) -> Self {
@@ -86,7 +98,7 @@ impl FromStr for Book {
     }
 }
 
-struct RuleBook(HashMap<usize, HashSet<usize>>);
+pub struct RuleBook(HashMap<usize, HashSet<usize>>);
 
 impl RuleBook {
     fn new(vec: Vec<Rule>) -> Self {
@@ -120,7 +132,7 @@ impl RuleBook {
     }
     fn passes(&self, book: &Book) -> bool {
         //ugh
-        println!("\n===\n");
+        crate::trace!("\n===\n");
         for bef in 0..(book.0.len() - 1) {
             for af in bef + 1..book.0.len() {
                 let ord = self.order(&book.0[bef], &book.0[af]);
@@ -139,9 +151,7 @@ impl RuleBook {
     }
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let (rule_book, books) = parse_input(input)?;
-
+fn q1(rule_book: &RuleBook, books: &[Book]) -> Result<u128, String> {
     let sum = books
         .iter()
         .filter(|book| rule_book.passes(book))
@@ -150,9 +160,7 @@ fn q1(input: &str) -> Result<u128, String> {
     Ok(sum)
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let (rule_book, books) = parse_input(input)?;
-
+fn q2(rule_book: &RuleBook, books: &[Book]) -> Result<u128, String> {
     let sum = books
         .iter()
         .filter(|book| !rule_book.passes(book))
@@ -1,23 +1,36 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::{parse_grid, Error};
+use crate::common::geom::Direction;
+use crate::common::step;
 use clap::builder::TypedValueParser;
 use itertools::Itertools;
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub struct Day6;
 
+crate::register_day!(Day6, 2024, 6, "Guard Gallivant", tags: &["grid", "simulation"]);
+
 impl Day for Day6 {
-    fn question(&self, input: &str, question: Question) {
-        let res = match question {
-            Question::First => q1(input),
-            Question::Second => q2(input),
-        };
-        println!("{:?}", res);
+    type Input = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(Map::from_str(input)?)
     }
 
-    fn test_data(&self) -> String {
-        "....#.....
+    fn part1(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q1(map)?.into())
+    }
+
+    fn part2(&self, map: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q2(map)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "....#.....
 .........#
 ..........
 ..#.......
@@ -26,39 +39,10 @@ impl Day for Day6 {
 .#..^.....
 ........#.
 #.........
-......#..."
-            .to_string()
-    }
-}
-
-#[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash)]
-enum Facing {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Display for Facing {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let ch = match self {
-            Facing::Up => '^',
-            Facing::Down => 'v',
-            Facing::Left => '<',
-            Facing::Right => '>',
-        };
-        write!(f, "{}", ch)
-    }
-}
-
-impl Facing {
-    fn turn_right(self) -> Self {
-        match self {
-            Facing::Left => Facing::Up,
-            Facing::Up => Facing::Right,
-            Facing::Right => Facing::Down,
-            Facing::Down => Facing::Left,
-        }
+......#...",
+        )
+        .part1(41)
+        .part2(6)]
     }
 }
 
@@ -80,34 +64,33 @@ impl Display for Tile {
     }
 }
 
-impl From<char> for Tile {
-    fn from(ch: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = Error;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            '.' => Tile::Empty,
-            '#' => Tile::Blocked,
-            '^' => Tile::Start,
-            _ => panic!("unknown tile {}", ch),
+            '.' => Ok(Tile::Empty),
+            '#' => Ok(Tile::Blocked),
+            '^' => Ok(Tile::Start),
+            _ => Err(Error::new(format!("unknown tile {}", ch))),
         }
     }
 }
 
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     map: Vec<Vec<Tile>>,
-    visited: Vec<Vec<HashSet<Facing>>>,
+    visited: Vec<Vec<HashSet<Direction>>>,
     pos: (usize, usize),
-    facing: Facing,
+    facing: Direction,
     looping: bool,
 }
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ls = s.lines().collect_vec();
-        let map: Vec<Vec<Tile>> = ls
-            .into_iter()
-            .map(|l| l.chars().map(|ch| ch.into()).collect_vec())
-            .collect_vec();
+        let map: Vec<Vec<Tile>> = parse_grid(s)?;
         let starter = map
             .iter()
             .enumerate()
@@ -126,14 +109,14 @@ impl FromStr for Map {
                     })
                 }
             })
-            .unwrap();
+            .ok_or("no starting position")?;
         let visited = map
             .iter()
             .map(|row| {
                 row.iter()
                     .map(|t| {
                         if *t == Tile::Start {
-                            HashSet::from([Facing::Up])
+                            HashSet::from([Direction::Up])
                         } else {
                             HashSet::new()
                         }
@@ -145,7 +128,7 @@ impl FromStr for Map {
             map,
             visited,
             pos: starter,
-            facing: Facing::Up,
+            facing: Direction::Up,
             looping: false,
         })
     }
@@ -160,16 +143,16 @@ impl Map {
     }
     fn next_step(&mut self) -> bool {
         match (self.pos, self.facing) {
-            ((_, 0), Facing::Left) => false,
-            ((_, w), Facing::Right) if w == self.width() - 1 => false,
-            ((0, _), Facing::Up) => false,
-            ((h, _), Facing::Down) if h == self.height() - 1 => false,
+            ((_, 0), Direction::Left) => false,
+            ((_, w), Direction::Right) if w == self.width() - 1 => false,
+            ((0, _), Direction::Up) => false,
+            ((h, _), Direction::Down) if h == self.height() - 1 => false,
             ((row, col), facing) => {
                 let next_pos = match facing {
-                    Facing::Up => (row - 1, col),
-                    Facing::Down => (row + 1, col),
-                    Facing::Left => (row, col - 1),
-                    Facing::Right => (row, col + 1),
+                    Direction::Up => (row - 1, col),
+                    Direction::Down => (row + 1, col),
+                    Direction::Left => (row, col - 1),
+                    Direction::Right => (row, col + 1),
                 };
                 if self.map[next_pos.0][next_pos.1] == Tile::Blocked {
                     self.facing = self.facing.turn_right();
@@ -220,7 +203,7 @@ impl Map {
                 row.iter()
                     .map(|t| {
                         if *t == Tile::Start {
-                            HashSet::from([Facing::Up])
+                            HashSet::from([Direction::Up])
                         } else {
                             HashSet::new()
                         }
@@ -256,27 +239,30 @@ impl Display for Map {
     }
 }
 
-fn q1(input: &str) -> Result<u128, String> {
-    let mut map = Map::from_str(input)?;
-    println!("{}", map);
+fn q1(map: &Map) -> Result<u128, String> {
+    let mut map = map.clone();
+    crate::debug!("{}", map);
     while map.next_step() {
-        println!("{} {}", map.pos.0, map.pos.1);
+        crate::trace!("{} {}", map.pos.0, map.pos.1);
+        step::tick(
+            || format!("guard at {:?}, {} visited", map.pos, map.count_visited()),
+            || map.to_string(),
+        );
     }
     Ok(map.count_visited())
 }
 
-fn q2(input: &str) -> Result<u128, String> {
-    let map = Map::from_str(input)?;
+fn q2(map: &Map) -> Result<u128, String> {
     let loopers = (0..map.width()).fold(0, |total_loopers, row| {
         let row_loopers = (0..map.height()).fold(0, |row_loopers, col| {
             let mut blocked_map = map.with_blockage((row, col));
             while blocked_map.next_step() {
                 if blocked_map.looping {
-                    println!("{} {} LOOP", row, col);
+                    crate::trace!("{} {} LOOP", row, col);
                     return row_loopers + 1;
                 }
             }
-            println!("{} {}", row, col);
+            crate::trace!("{} {}", row, col);
             row_loopers
         });
         total_loopers + row_loopers
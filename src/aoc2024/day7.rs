@@ -1,4 +1,5 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use clap::builder::TypedValueParser;
 use itertools::Itertools;
 use std::collections::HashSet;
@@ -8,14 +9,26 @@ use std::str::FromStr;
 
 pub struct Day7;
 
+crate::register_day!(Day7, 2024, 7, "Bridge Repair", tags: &["search"]);
+
 impl Day for Day7 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Vec<Equation>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, eqs: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(eqs, Question::First)?.into())
+    }
+
+    fn part2(&self, eqs: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(eqs, Question::Second)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "190: 10 19
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -23,12 +36,14 @@ impl Day for Day7 {
 161011: 16 10 13
 192: 17 8 14
 21037: 9 7 18 13
-292: 11 6 16 20"
-            .to_string()
+292: 11 6 16 20",
+        )
+        .part1(3749)
+        .part2(11387)]
     }
 }
 
-struct Equation {
+pub struct Equation {
     result: u128,
     nums: Vec<u128>,
 }
@@ -132,20 +147,18 @@ impl Equation {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let eqs: Result<Vec<Equation>, String> = input.lines().map(|l| l.parse()).collect();
-    let eqs = eqs?;
+fn q(eqs: &[Equation], question: Question) -> Result<u128, String> {
     let sum = eqs
         .iter()
         .filter(|e| {
             let ops = e.fits(0, 0, question == Question::Second);
             if ops.is_some() {
-                print!("{} = ", e.result);
                 let ops = ops.unwrap();
                 let mut ops_str = ops.iter().map(|op| op.to_string());
                 let intersperse_closure = || ops_str.next().unwrap_or("?".to_string());
-                println!(
-                    "{}",
+                crate::trace!(
+                    "{} = {}",
+                    e.result,
                     e.nums
                         .iter()
                         .map(|n| format!("{}", n))
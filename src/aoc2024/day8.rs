@@ -1,17 +1,30 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::HashSet;
 
 pub struct Day8;
 
+crate::register_day!(Day8, 2024, 8, "Resonant Collinearity", tags: &["grid", "geometry"]);
+
 impl Day for Day8 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Roof;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_roof(input))
+    }
+
+    fn part1(&self, roof: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(roof, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "............
+    fn part2(&self, roof: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(roof, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "............
 ........0...
 .....0......
 .......0....
@@ -22,8 +35,10 @@ impl Day for Day8 {
 ........A...
 .........A..
 ............
-............"
-            .to_string()
+............",
+        )
+        .part1(14)
+        .part2(34)]
     }
 }
 
@@ -98,11 +113,28 @@ fn get_antipodes(
         .collect()
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let antennas = collect_antennas(input);
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-    println!("{}", antennas.len());
+pub struct Roof {
+    antennas: Vec<Antenna>,
+    width: usize,
+    height: usize,
+}
+
+fn read_roof(input: &str) -> Roof {
+    Roof {
+        antennas: collect_antennas(input),
+        width: input.lines().next().unwrap().len(),
+        height: input.lines().count(),
+    }
+}
+
+fn q(roof: &Roof, question: Question) -> Result<u128, String> {
+    let Roof {
+        antennas,
+        width,
+        height,
+    } = roof;
+    let (width, height) = (*width, *height);
+    crate::debug!("{} antennas", antennas.len());
 
     // antennas.group_by(|x, y| x.name == y.name);
     let antipodes: Vec<(isize, isize)> = antennas
@@ -135,18 +167,20 @@ fn print_situation(antennas: Vec<Antenna>, height: usize, width: usize, question
             .map(|a| (a.x as isize, a.y as isize))
             .collect();
         for y in 0..height {
-            for x in 0..width {
-                if aps.contains(&(x as isize, y as isize)) {
-                    print!("#");
-                } else if ants.contains(&(x as isize, y as isize)) {
-                    print!("{}", ch);
-                } else {
-                    print!(".");
-                }
-            }
-            println!()
+            let row: String = (0..width)
+                .map(|x| {
+                    if aps.contains(&(x as isize, y as isize)) {
+                        '#'
+                    } else if ants.contains(&(x as isize, y as isize)) {
+                        ch
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            crate::trace!("{}", row);
         }
-        println!()
+        crate::trace!("")
     }
 }
 
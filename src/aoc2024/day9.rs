@@ -1,18 +1,30 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::{repeat_n, Itertools};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub struct Day9;
 
+crate::register_day!(Day9, 2024, 9, "Disk Fragmenter", tags: &["simulation"]);
+
 impl Day for Day9 {
-    fn question(&self, input: &str, question: Question) {
-        let res = q(input, question);
-        println!("{:?}", res);
+    type Input = Memory;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, memory: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(memory, Question::First)?.into())
     }
 
-    fn test_data(&self) -> String {
-        "2333133121414131402".to_string()
+    fn part2(&self, memory: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(memory, Question::Second)?.into())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new("2333133121414131402").part1(1928).part2(2858)]
     }
 }
 
@@ -36,7 +48,8 @@ impl Display for Slot {
     }
 }
 
-struct Memory {
+#[derive(Clone)]
+pub struct Memory {
     slots: Vec<Slot>,
     last_non_empty: usize,
 }
@@ -94,7 +107,7 @@ impl Memory {
         // not going to functional this
         let first_empty = self.first_empty().unwrap_or(self.slots.len() + 1);
         if first_empty > self.last_non_empty {
-            println!("A {} {}", first_empty, self.last_non_empty);
+            crate::trace!("A {} {}", first_empty, self.last_non_empty);
             return false;
         }
         self.slots[first_empty] = self.slots[self.last_non_empty];
@@ -195,13 +208,11 @@ impl FromStr for Memory {
     }
 }
 
-fn q(input: &str, question: Question) -> Result<u128, String> {
-    let mut memory: Memory = input.parse()?;
+fn q(memory: &Memory, question: Question) -> Result<u128, String> {
+    let mut memory = memory.clone();
     if question == Question::First {
         // println!("From: {}", memory);
-        while memory.move_one() {
-            print!(".");
-        }
+        while memory.move_one() {}
     } else {
         let mut id = memory.max_id();
         while id > 0 {
@@ -210,10 +221,10 @@ fn q(input: &str, question: Question) -> Result<u128, String> {
             // print!("         ");
             // print!("{}", " ".repeat(memory.last_non_empty));
             // println!("^");
-            println!("Done {} ", id);
+            crate::trace!("Done {} ", id);
             id -= 1;
         }
-        println!("To:      {}", memory);
+        crate::trace!("To:      {}", memory);
     }
     // println!("To:   {}", memory);
     Ok(memory.checksum())
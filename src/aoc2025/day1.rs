@@ -1,29 +1,31 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-// did in python first at work, this one doesn't work
 pub struct Day1;
 
+crate::register_day!(Day1, 2025, 1, "Secret Entrance", tags: &["simulation"]);
+
 impl Day for Day1 {
-    fn question(&self, input: &str, question: Question) {
-        let turns = input.lines().map(|l| Turn::from_str(l).unwrap());
-        let mut state = 50usize;
-        let mut zeros_q1 = 0u128;
-        let mut zeros_q2 = 0u128;
-        for turn in turns {
-            let (new_state, zeroes) = move_dial(&turn, state);
-            state = new_state;
-            zeros_q2 += zeroes as u128;
-            if new_state == 0 {
-                zeros_q1 += 1;
-            }
-        }
-        println!("{} {}", zeros_q1, zeros_q2);
+    type Input = Vec<Turn>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, turns: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(turns, Question::First))
+    }
+
+    fn part2(&self, turns: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(turns, Question::Second))
     }
 
-    fn test_data(&self) -> String {
-        "L68
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "L68
 L30
 R48
 L5
@@ -32,8 +34,28 @@ L55
 L1
 L99
 R14
-L82"
-        .to_string()
+L82",
+        )
+        .part1(3)
+        .part2(6)]
+    }
+}
+
+fn q(turns: &[Turn], question: Question) -> Answer {
+    let mut state = 50usize;
+    let mut zeros_q1 = 0u128;
+    let mut zeros_q2 = 0u128;
+    for turn in turns {
+        let (new_state, zeroes) = move_dial(turn, state);
+        state = new_state;
+        zeros_q2 += zeroes as u128;
+        if new_state == 0 {
+            zeros_q1 += 1;
+        }
+    }
+    match question {
+        Question::First => zeros_q1.into(),
+        Question::Second => zeros_q2.into(),
     }
 }
 
@@ -42,30 +64,32 @@ enum Direction {
     Left,
     Right,
 }
-struct Turn {
+pub struct Turn {
     direction: Direction,
     amount: usize,
 }
 
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Invalid direction"),
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(format!("'{}' is neither L nor R", value)),
         }
     }
 }
 
 impl FromStr for Turn {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let dir = Direction::from(s.chars().nth(0).unwrap());
-        let amount = s[1..].parse::<usize>().unwrap();
+        let mut chars = s.chars();
+        let dir = chars.next().ok_or("no turn")?;
         Ok(Turn {
-            direction: dir,
-            amount,
+            direction: Direction::try_from(dir)?,
+            amount: chars.as_str().parse()?,
         })
     }
 }
@@ -79,29 +103,23 @@ impl Display for Turn {
     }
 }
 
+/// Turns the dial from `x`, to where it stops and how many times it points at 0 on the way there,
+/// the stop included.
 fn move_dial(turn: &Turn, x: usize) -> (usize, usize) {
-    let virtual_result = match turn.direction {
-        Direction::Left => x as isize - turn.amount as isize,
-        Direction::Right => x as isize + turn.amount as isize,
-    };
-    let hundreds = virtual_result / 100;
-    let hundreds = hundreds.abs() + if virtual_result < 0 { 1 } else { 0 };
-    let result = if virtual_result < 0 {
-        100 - (virtual_result.abs() % 100)
-    } else {
-        virtual_result % 100
-    };
-    let hundreds = hundreds
-        - if x == 0 && result != 0 && turn.direction == Direction::Right && hundreds > 0 {
-            1
-        } else {
-            0
-        };
-    let hundreds = if result == 0 && turn.direction == Direction::Left {
-        hundreds + 1
-    } else {
-        hundreds
+    let (result, zeroes) = match turn.direction {
+        Direction::Right => ((x + turn.amount) % 100, (x + turn.amount) / 100),
+        Direction::Left => {
+            let result = (x + 100 - turn.amount % 100) % 100;
+            // From 0 the first click away doesn't pass it, the dial is back only after 100.
+            let to_first = if x == 0 { 100 } else { x };
+            let zeroes = if turn.amount < to_first {
+                0
+            } else {
+                (turn.amount - to_first) / 100 + 1
+            };
+            (result, zeroes)
+        }
     };
-    println!("Moved ({}) to {} with {} hundos", turn, result, hundreds);
-    (result as usize, hundreds as usize)
+    crate::debug!("Moved ({}) to {} passing 0 {} times", turn, result, zeroes);
+    (result, zeroes)
 }
@@ -1,32 +1,50 @@
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 
 pub struct Day3;
 
+crate::register_day!(Day3, 2025, 3, "Lobby", tags: &["math"]);
+
 impl Day for Day3 {
-    fn question(&self, input: &str, question: Question) {
-        let banks = input.lines().map(|l| Banks::new(l));
-        let max_nums = banks.map(|b| match question {
-            Question::First => b.q1(),
-            Question::Second => b.q2(12usize),
-        });
-        let max_nums = max_nums.map(|n| {
-            println!("{}", n);
-            n
-        });
-        let s = max_nums.sum::<u128>();
-        println!("{}", s);
+    type Input = Vec<Banks>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().map(|l| Banks::new(l)).collect())
     }
 
-    fn test_data(&self) -> String {
-        "987654321111111
+    fn part1(&self, banks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(banks, Question::First))
+    }
+
+    fn part2(&self, banks: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(banks, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "987654321111111
 811111111111119
 234234234234278
-818181911112111"
-            .to_string()
+818181911112111",
+        )
+        .part1(357)
+        .part2(3121910778619u64)]
     }
 }
 
-struct Banks(Vec<usize>);
+fn q(banks: &[Banks], question: Question) -> Answer {
+    let max_nums = banks.iter().map(|b| match question {
+        Question::First => b.q1(),
+        Question::Second => b.q2(12usize),
+    });
+    let max_nums = max_nums.map(|n| {
+        crate::trace!("{}", n);
+        n
+    });
+    max_nums.sum::<u128>().into()
+}
+
+pub struct Banks(Vec<usize>);
 
 fn first_max<'a, I>(iter: I, len: usize, min: usize) -> (usize, usize)
 where
@@ -72,7 +90,7 @@ impl Banks {
                 len - fistidxtocheck,
                 digits - i - 1,
             );
-            println!("{} {} {}", idx, val, self.0[idx + fistidxtocheck]);
+            crate::trace!("{} {} {}", idx, val, self.0[idx + fistidxtocheck]);
             lastidx = Some(idx + fistidxtocheck);
             num.push(val);
         }
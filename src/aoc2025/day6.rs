@@ -1,70 +1,104 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::str::FromStr;
 
 pub struct Day6;
 
+crate::register_day!(Day6, 2025, 6, "Trash Compactor", tags: &["parsing", "grid"]);
+
 impl Day for Day6 {
-    fn question(&self, input: &str, question: Question) {
-        let n = input.lines().count();
-        let num_map: Vec<Vec<u128>> = read_num_map(input, n, question);
-        println!("{:?}", num_map);
-        let ops: Vec<Operation> = input
-            .lines()
-            .last()
-            .unwrap()
-            .split_whitespace()
-            .map(|op| op.parse().unwrap())
-            .collect();
-
-        let result = ops.iter().enumerate().fold(0u128, |sum, (i, op)| {
-            let ns = num_map.iter().map(|row| row[i]);
-            let local_res = ns.fold(op.zero(), |acc, x| op.do_operation(acc, x));
-            println!("{}: {}", i, local_res);
-            sum + local_res
-        });
-        println!("Question: {}", result);
+    type Input = Worksheet;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        input.parse()
+    }
+
+    fn part1(&self, sheet: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        q(sheet, Question::First)
     }
 
-    fn test_data(&self) -> String {
-        "123 328  51 640
- 45 64  387 230
+    fn part2(&self, sheet: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        q(sheet, Question::Second)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "123 328  51 64 
+ 45 64  387 23 
   6 98  215 314
-*   +   *   +  "
-            .to_string()
+*   +   *   +  ",
+        )
+        .part1(4277556)
+        .part2(3263827)]
     }
 }
 
-fn read_num_map(input: &str, n: usize, question: Question) -> Vec<Vec<u128>> {
-    match question {
-        Question::First => input
-            .lines()
-            .take(n - 1)
-            .map(|l| {
-                l.split_whitespace()
-                    .map(|x| x.parse::<u128>().unwrap())
-                    .collect()
+/// The rows of numbers as they're written, the second question reads them by column, and the
+/// operators under them with the column each is in.
+pub struct Worksheet {
+    rows: Vec<String>,
+    ops: Vec<(usize, Operation)>,
+}
+
+impl FromStr for Worksheet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rows = s.lines().map(String::from).collect_vec();
+        let operators = rows.pop().ok_or("no operators")?;
+        let ops = operators
+            .char_indices()
+            .filter(|(_, ch)| !ch.is_whitespace())
+            .map(|(x, ch)| {
+                let op = ch
+                    .to_string()
+                    .parse()
+                    .map_err(|e: String| Error::at(rows.len(), x, e))?;
+                Ok((x, op))
             })
-            .collect(),
-        Question::Second => q2(input, n),
+            .collect::<Result<_, Error>>()?;
+        Ok(Worksheet { rows, ops })
     }
 }
 
-fn q2(input: &str, n: usize) -> Vec<Vec<u128>> {
-    let op_indices: HashSet<usize> = input
-        .lines()
-        .last()
-        .unwrap()
-        .chars()
+fn q(sheet: &Worksheet, question: Question) -> Result<Answer, Error> {
+    if question == Question::Second {
+        return Ok(q2(sheet).into());
+    }
+    let num_map: Vec<Vec<u128>> = read_num_map(&sheet.rows)?;
+    crate::debug!("{:?}", num_map);
+    let result = sheet
+        .ops
+        .iter()
         .enumerate()
-        .filter(|(_, ch)| *ch == '*' || *ch == '+')
-        .map(|(i, _)| i)
-        .collect();
-    let num_arrs = input
-        .lines()
-        .take(n - 1)
+        .fold(0u128, |sum, (i, (_, op))| {
+            let ns = num_map.iter().map(|row| row[i]);
+            let local_res = ns.fold(op.zero(), |acc, x| op.do_operation(acc, x));
+            crate::trace!("{}: {}", i, local_res);
+            sum + local_res
+        });
+    Ok(result.into())
+}
+
+fn read_num_map(rows: &[String]) -> Result<Vec<Vec<u128>>, Error> {
+    rows.iter()
+        .enumerate()
+        .map(|(y, l)| {
+            l.split_whitespace()
+                .map(|x| x.parse::<u128>().map_err(|e| Error::from(e).on_line(y)))
+                .collect()
+        })
+        .collect()
+}
+
+fn q2(sheet: &Worksheet) -> u128 {
+    let op_indices: HashSet<usize> = sheet.ops.iter().map(|&(x, _)| x).collect();
+    let num_arrs = sheet
+        .rows
+        .iter()
         .map(|l| {
             let (nums, current) =
                 l.chars()
@@ -79,7 +113,7 @@ fn q2(input: &str, n: usize) -> Vec<Vec<u128>> {
                             (nums, vec![current, vec![cnum]].concat())
                         }
                     });
-            println!("AAIIAIA {:?} {:?}", nums, current);
+            crate::trace!("AAIIAIA {:?} {:?}", nums, current);
             vec![nums, vec![current]].concat()
         })
         .collect_vec();
@@ -88,13 +122,13 @@ fn q2(input: &str, n: usize) -> Vec<Vec<u128>> {
         .enumerate()
         .map(|(i, op)| {
             let ns = num_arrs.iter().map(|row| row[i].clone()).collect_vec();
-            println!("A {}: {:?}", i, ns);
+            crate::trace!("A {}: {:?}", i, ns);
             let ns = (0..ns[0].len())
                 .map(|i| ns.iter().map(|n| n[i]).collect_vec())
                 .collect_vec();
-            println!("B {}: {:?}", i, ns);
+            crate::trace!("B {}: {:?}", i, ns);
             let ns = ns.iter().map(|digits| {
-                println!("My current digs are : {:?}", digits);
+                crate::trace!("My current digs are : {:?}", digits);
                 let len = digits.len();
                 digits
                     .iter()
@@ -107,23 +141,18 @@ fn q2(input: &str, n: usize) -> Vec<Vec<u128>> {
             ns.collect_vec()
         })
         .collect_vec();
-    let ops: Vec<Operation> = input
-        .lines()
-        .last()
-        .unwrap()
-        .split_whitespace()
-        .map(|op| op.parse().unwrap())
-        .collect();
-
-    let result = ops.iter().enumerate().fold(0u128, |sum, (i, op)| {
-        let local_res = all_nums[i]
-            .iter()
-            .fold(op.zero(), |acc, n| op.do_operation(acc, *n));
-        println!("{}: {}", i, local_res);
-        sum + local_res
-    });
-    println!("Question: {}", result);
-    vec![]
+    let result = sheet
+        .ops
+        .iter()
+        .enumerate()
+        .fold(0u128, |sum, (i, (_, op))| {
+            let local_res = all_nums[i]
+                .iter()
+                .fold(op.zero(), |acc, n| op.do_operation(acc, *n));
+            crate::trace!("{}: {}", i, local_res);
+            sum + local_res
+        });
+    result
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -1,58 +1,32 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::Error;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 pub struct Day7;
 
+crate::register_day!(Day7, 2025, 7, "Laboratories", tags: &["grid", "dp"]);
+
 impl Day for Day7 {
-    fn question(&self, input: &str, question: Question) {
-        let splitters = input
-            .lines()
-            .map(|l| l.parse::<SplitterRow>().unwrap())
-            .collect_vec();
-        let start = input
-            .lines()
-            .nth(0)
-            .unwrap()
-            .chars()
-            .enumerate()
-            .filter(|(_, ch)| *ch == 'S')
-            .map(|(i, _)| i)
-            .next()
-            .unwrap();
-        // sigh
-        let width = input.lines().nth(0).unwrap().len();
-        let mut splits = 0usize;
-        let mut currents = HashMap::new();
-        currents.insert(start, 1u128);
-        for row in splitters {
-            let mut newcurrents = HashMap::new();
-            for (curr, lives) in &currents {
-                if row.0.contains(curr) {
-                    splits += 1;
-                    if *curr > 0 {
-                        let entry = newcurrents.entry(curr - 1).or_insert(0u128);
-                        *entry += lives;
-                    }
-                    if *curr < width - 1 {
-                        let entry = newcurrents.entry(curr + 1).or_insert(0u128);
-                        *entry += lives;
-                    }
-                } else {
-                    let entry = newcurrents.entry(*curr).or_insert(0u128);
-                    *entry += lives;
-                }
-            }
-            currents = newcurrents;
-        }
-        let all_lives = currents.iter().map(|(_, v)| v).sum::<u128>();
-        println!("Question: q1 {} q2 {}", splits, all_lives);
+    type Input = Manifold;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(read_manifold(input)?)
+    }
+
+    fn part1(&self, manifold: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(manifold, Question::First))
     }
 
-    fn test_data(&self) -> String {
-        ".......S.......
+    fn part2(&self, manifold: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Ok(q(manifold, Question::Second))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            ".......S.......
 ...............
 .......^.......
 ...............
@@ -68,8 +42,80 @@ impl Day for Day7 {
 ...............
 .^.^.^.^.^...^.
 ...............
-"
-        .to_string()
+",
+        )
+        .part1(21)
+        .part2(40)]
+    }
+}
+
+pub struct Manifold {
+    splitters: Vec<SplitterRow>,
+    start: usize,
+    width: usize,
+}
+
+fn read_manifold(input: &str) -> Result<Manifold, String> {
+    let splitters = input
+        .lines()
+        .map(|l| {
+            l.parse::<SplitterRow>()
+                .map_err(|_| format!("can't read row {}", l))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let start = input
+        .lines()
+        .nth(0)
+        .ok_or("empty input")?
+        .chars()
+        .enumerate()
+        .filter(|(_, ch)| *ch == 'S')
+        .map(|(i, _)| i)
+        .next()
+        .ok_or("no start")?;
+    // sigh
+    let width = input.lines().nth(0).unwrap().len();
+    Ok(Manifold {
+        splitters,
+        start,
+        width,
+    })
+}
+
+fn q(manifold: &Manifold, question: Question) -> Answer {
+    let Manifold {
+        splitters,
+        start,
+        width,
+    } = manifold;
+    let (start, width) = (*start, *width);
+    let mut splits = 0usize;
+    let mut currents = HashMap::new();
+    currents.insert(start, 1u128);
+    for row in splitters {
+        let mut newcurrents = HashMap::new();
+        for (curr, lives) in &currents {
+            if row.0.contains(curr) {
+                splits += 1;
+                if *curr > 0 {
+                    let entry = newcurrents.entry(curr - 1).or_insert(0u128);
+                    *entry += lives;
+                }
+                if *curr < width - 1 {
+                    let entry = newcurrents.entry(curr + 1).or_insert(0u128);
+                    *entry += lives;
+                }
+            } else {
+                let entry = newcurrents.entry(*curr).or_insert(0u128);
+                *entry += lives;
+            }
+        }
+        currents = newcurrents;
+    }
+    let all_lives = currents.iter().map(|(_, v)| v).sum::<u128>();
+    match question {
+        Question::First => splits.into(),
+        Question::Second => all_lives.into(),
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
@@ -9,53 +10,26 @@ use std::str::FromStr;
 
 pub struct Day8;
 
+crate::register_day!(Day8, 2025, 8, "Playground", tags: &["graph", "geometry"]);
+
 impl Day for Day8 {
-    fn question(&self, input: &str, question: Question) {
-        let points: Result<Vec<_>, String> = input.lines().map(|l| l.parse::<Point3D>()).collect();
-        if let Err(e) = points {
-            println!("Error: {}", e);
-            return;
-        }
-        let points = points.unwrap();
-        let pairwise_vec = (0..points.len())
-            .map(|i| {
-                (i + 1..points.len())
-                    .map(|j| build_pairwise(&points, i, j))
-                    .collect_vec()
-            })
-            .concat();
-        let mut group_map: HashMap<usize, usize> = (0..points.len()).map(|i| (i, i)).collect();
-        for (i, pair) in pairwise_vec.iter().sorted().enumerate() {
-            println!("{}", pair.dist);
-            let gr1 = group_map.get(&pair.i1).unwrap();
-            let gr2 = group_map.get(&pair.i2).unwrap();
-            let smaller = gr1.clone().min(gr2.clone());
-            let bigger = gr1.clone().max(gr2.clone());
-            group_map
-                .iter_mut()
-                .filter(|(_, v)| **v == bigger)
-                .for_each(|(_, v)| {
-                    *v = smaller;
-                });
-
-            if i == 9 || i == 999 {
-                println!(
-                    "After {}: {} circuits, {} Q1s",
-                    i + 1,
-                    get_count(&group_map),
-                    get_q1(&group_map)
-                );
-            }
-            if get_count(&group_map) == 1 {
-                println!("Q1: {}", points[pair.i1].x * points[pair.i2].x);
-                break;
-            }
-        }
-        println!("Goodbye")
+    type Input = Vec<Point3D>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, points: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        q(points, Question::First, params.get("connections")?)
+    }
+
+    fn part2(&self, points: &Self::Input, params: &Params) -> Result<Answer, Error> {
+        q(points, Question::Second, params.get("connections")?)
     }
 
-    fn test_data(&self) -> String {
-        "162,817,812
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -74,12 +48,54 @@ impl Day for Day8 {
 941,993,340
 862,61,35
 984,92,344
-425,690,689"
-            .to_string()
+425,690,689",
+        )
+        .param("connections", 10)
+        .part1(40)
+        .part2(25272)]
+    }
+
+    fn params(&self, _question: Question) -> Params {
+        Params::new().with("connections", 1000)
+    }
+}
+
+fn q(points: &Vec<Point3D>, question: Question, connections: usize) -> Result<Answer, Error> {
+    let pairwise_vec = (0..points.len())
+        .map(|i| {
+            (i + 1..points.len())
+                .map(|j| build_pairwise(points, i, j))
+                .collect_vec()
+        })
+        .concat();
+    let mut group_map: HashMap<usize, usize> = (0..points.len()).map(|i| (i, i)).collect();
+    for (i, pair) in pairwise_vec.iter().sorted().enumerate() {
+        crate::trace!("{}", pair.dist);
+        let gr1 = group_map.get(&pair.i1).unwrap();
+        let gr2 = group_map.get(&pair.i2).unwrap();
+        let smaller = gr1.clone().min(gr2.clone());
+        let bigger = gr1.clone().max(gr2.clone());
+        group_map
+            .iter_mut()
+            .filter(|(_, v)| **v == bigger)
+            .for_each(|(_, v)| {
+                *v = smaller;
+            });
+
+        if question == Question::First && i == connections - 1 {
+            return Ok(Answer::from(get_q1(&group_map))
+                .with_diagnostic(format!("{} circuits", get_count(&group_map))));
+        }
+        if get_count(&group_map) == 1 {
+            return Ok((points[pair.i1].x * points[pair.i2].x).into());
+        }
     }
+    Err(Error::new(
+        "the junction boxes never end up in a single circuit",
+    ))
 }
 
-struct Point3D {
+pub struct Point3D {
     x: u32,
     y: u32,
     z: u32,
@@ -147,7 +163,7 @@ where
     V: Eq + Hash + Debug,
 {
     let group_counts: HashMap<&V, usize> = m.values().counts_by(|x| x);
-    println!(
+    crate::debug!(
         "{:?}",
         group_counts.values().sorted().rev().take(3).collect_vec()
     );
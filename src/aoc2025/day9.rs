@@ -1,5 +1,6 @@
 use crate::common::columns::Columnser;
-use crate::common::day::{Day, Question};
+use crate::common::day::{Answer, Day, Example, Params, Question};
+use crate::common::error::{parse_each_line, Error};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
@@ -9,73 +10,91 @@ use std::str::FromStr;
 
 pub struct Day9;
 
+crate::register_day!(Day9, 2025, 9, "Movie Theater", tags: &["geometry"]);
+
 impl Day for Day9 {
-    fn question(&self, input: &str, question: Question) {
-        let points: Result<Vec<_>, String> = input.lines().map(|l| l.parse::<Point2D>()).collect();
-        if let Err(e) = points {
-            println!("Error: {}", e);
-            return;
-        }
-        let points = points.unwrap();
-        let pairwise_vec = (0..points.len())
-            .map(|i| {
-                (i + 1..points.len())
-                    .filter_map(|j| {
-                        if !points[i].added && !points[j].added {
-                            Some(build_pairwise(&points, i, j))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect_vec()
-            })
-            .concat();
-        let q1 = pairwise_vec.iter().sorted().rev().nth(0).unwrap();
-        println!("q1: {}", q1.area);
-        for pw in pairwise_vec.iter().sorted().rev() {
-            let concave = points.iter().enumerate().any(|(i, p)| {
-                i != pw.i1 && i != pw.i2 && p.between(&points[pw.i1], &points[pw.i2])
-            });
-            if !concave {
-                println!("q2: {}", pw.area); // 4549093344 too high 1381772324 too low
-                println!(
-                    "{},{} {},{}",
-                    points[pw.i1].x, points[pw.i1].y, points[pw.i2].x, points[pw.i2].y
-                );
-                paint(&points, &points[pw.i1], &points[pw.i2]);
-                break;
-            }
-        }
+    type Input = Vec<Point2D>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_each_line(input)
+    }
+
+    fn part1(&self, points: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        q(points, Question::First)
     }
 
-    fn test_data(&self) -> String {
-        "7,1
+    fn part2(&self, points: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        q(points, Question::Second)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example::new(
+            "7,1
 11,1
 11,7
 9,7
 9,5
 2,5
 2,3
-7,3"
-        .to_string()
+7,3",
+        )
+        .part1(50)
+        .part2(24)]
     }
 }
 
-struct Point2D {
+fn q(points: &Vec<Point2D>, question: Question) -> Result<Answer, Error> {
+    let pairwise_vec = (0..points.len())
+        .map(|i| {
+            (i + 1..points.len())
+                .filter_map(|j| {
+                    if !points[i].added && !points[j].added {
+                        Some(build_pairwise(points, i, j))
+                    } else {
+                        None
+                    }
+                })
+                .collect_vec()
+        })
+        .concat();
+    let q1 = pairwise_vec.iter().sorted().rev().nth(0).unwrap();
+    if question == Question::First {
+        return Ok(q1.area.into());
+    }
+    let edges = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .collect_vec();
+    for pw in pairwise_vec.iter().sorted().rev() {
+        // The loop goes around the green tiles, the rectangle is all green when none of its edges
+        // cuts into it.
+        let cut = edges
+            .iter()
+            .any(|(p1, p2)| Point2D::cuts(p1, p2, &points[pw.i1], &points[pw.i2]));
+        if !cut {
+            return Ok(Answer::from(pw.area).with_diagnostic(format!(
+                "{},{} {},{}",
+                points[pw.i1].x, points[pw.i1].y, points[pw.i2].x, points[pw.i2].y
+            )));
+        }
+    }
+    Err(Error::new("no rectangle inside the loop"))
+}
+
+pub struct Point2D {
     x: u32,
     y: u32,
     added: bool,
 }
 
 impl Point2D {
-    fn between(&self, p1: &Point2D, p2: &Point2D) -> bool {
-        let x1 = p1.x;
-        let y1 = p1.y;
-        let x2 = p2.x;
-        let y2 = p2.y;
-        let x = self.x;
-        let y = self.y;
-        ((x1 < x && x < x2) || (x2 < x && x < x1)) && ((y1 < y && y < y2) || (y2 < y && y < y1))
+    /// Whether the straight edge from `e1` to `e2` goes through the inside of the rectangle with
+    /// corners `c1` and `c2`, running along its sides doesn't.
+    fn cuts(e1: &Point2D, e2: &Point2D, c1: &Point2D, c2: &Point2D) -> bool {
+        e1.x.max(e2.x) > c1.x.min(c2.x)
+            && e1.x.min(e2.x) < c1.x.max(c2.x)
+            && e1.y.max(e2.y) > c1.y.min(c2.y)
+            && e1.y.min(e2.y) < c1.y.max(c2.y)
     }
 }
 
@@ -124,44 +143,6 @@ impl PartialOrd for Pairwise {
 
 impl Ord for Pairwise {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.area.cmp(&self.area)
-    }
-}
-
-fn paint(points: &Vec<Point2D>, p1: &Point2D, p2: &Point2D) {
-    let width = points.iter().map(|p| p.x).max().unwrap() + 300;
-    let height = points.iter().map(|p| p.y).max().unwrap() + 300;
-    let mut image_buf =
-        image::ImageBuffer::new(width.clone() / 100 as u32, height.clone() / 100 as u32);
-    for i in 0..points.len() {
-        let mut x1 = points[i].x / 100;
-        let mut y1 = points[i].y / 100;
-        let mut x2 = points[(i + 1) % points.len()].x / 100;
-        let mut y2 = points[(i + 1) % points.len()].y / 100;
-        if x1 > x2 {
-            std::mem::swap(&mut x1, &mut x2);
-        }
-        if y1 > y2 {
-            std::mem::swap(&mut y1, &mut y2);
-        }
-        for y in y1..y2 + 1 {
-            for x in x1..x2 + 1 {
-                let point = image_buf.get_pixel_mut(x, y);
-                *point = image::Rgb([255u8, 0u8, 0u8]);
-            }
-        }
-        let point = image_buf.get_pixel_mut(points[i].x / 100, points[i].y / 100);
-        *point = image::Rgb([255u8, 255u8, 255u8]);
-    }
-    let smalx = p1.x.min(p2.x) / 100;
-    let smaly = p1.y.min(p2.y) / 100;
-    let bigx = p1.x.max(p2.x) / 100;
-    let bigy = p1.y.max(p2.y) / 100;
-    for x in smalx..bigx + 1 {
-        for y in smaly..bigy + 1 {
-            let point = image_buf.get_pixel_mut(x, y);
-            *point = image::Rgb([0u8, 0u8, 255u8]);
-        }
+        self.area.cmp(&other.area)
     }
-    image_buf.save("202509.png").unwrap();
 }
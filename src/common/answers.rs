@@ -0,0 +1,89 @@
+use crate::common::day::Question;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Parts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+    /// How long the question took when it was last verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2_ms: Option<f64>,
+}
+
+impl Parts {
+    pub fn get(&self, question: Question) -> Option<&String> {
+        match question {
+            Question::First => self.part1.as_ref(),
+            Question::Second => self.part2.as_ref(),
+        }
+    }
+    fn get_mut(&mut self, question: Question) -> &mut Option<String> {
+        match question {
+            Question::First => &mut self.part1,
+            Question::Second => &mut self.part2,
+        }
+    }
+    pub fn time_ms(&self, question: Question) -> Option<f64> {
+        match question {
+            Question::First => self.part1_ms,
+            Question::Second => self.part2_ms,
+        }
+    }
+    fn time_ms_mut(&mut self, question: Question) -> &mut Option<f64> {
+        match question {
+            Question::First => &mut self.part1_ms,
+            Question::Second => &mut self.part2_ms,
+        }
+    }
+}
+
+/// Known good answers, stored as `{"2024": {"1": {"part1": "...", "part2": "..."}}}`.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct AnswerStore(BTreeMap<u16, BTreeMap<u8, Parts>>);
+
+impl AnswerStore {
+    /// A missing file is an empty store.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let s = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, s + "\n").map_err(|e| format!("{}: {}", path.display(), e))
+    }
+    pub fn parts(&self, year: u16, day: u8) -> Option<&Parts> {
+        self.0.get(&year)?.get(&day)
+    }
+    pub fn get(&self, year: u16, day: u8, question: Question) -> Option<&String> {
+        self.parts(year, day)?.get(question)
+    }
+    pub fn set(&mut self, year: u16, day: u8, question: Question, answer: String) {
+        *self
+            .0
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+            .get_mut(question) = Some(answer);
+    }
+    pub fn set_time(&mut self, year: u16, day: u8, question: Question, ms: f64) {
+        *self
+            .0
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+            .time_ms_mut(question) = Some(ms);
+    }
+}
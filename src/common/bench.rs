@@ -0,0 +1,95 @@
+use crate::common::day::{Params, Question};
+use crate::common::error::Error;
+use crate::common::registry::Registration;
+use crate::common::runner::{catch_quietly, part};
+use itertools::Itertools;
+use serde::Serialize;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Stats {
+    pub name: String,
+    pub runs: usize,
+    #[serde(rename = "min_ms", serialize_with = "as_ms")]
+    pub min: Duration,
+    #[serde(rename = "median_ms", serialize_with = "as_ms")]
+    pub median: Duration,
+    #[serde(rename = "max_ms", serialize_with = "as_ms")]
+    pub max: Duration,
+}
+
+fn as_ms<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(d.as_secs_f64() * 1000.0)
+}
+
+/// Calls `f` `warmup` times untimed, then `runs` times timed.
+pub fn measure<T>(name: &str, warmup: usize, runs: usize, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..warmup {
+        black_box(f());
+    }
+    let times = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .sorted()
+        .collect_vec();
+    Stats {
+        name: name.to_string(),
+        runs: times.len(),
+        min: times[0],
+        median: times[times.len() / 2],
+        max: times[times.len() - 1],
+    }
+}
+
+/// Times parsing and the solved questions separately, the questions all reuse one parsed input.
+/// Each step is checked once before it's timed, there's no point timing how fast it fails.
+pub fn bench_day(
+    registration: &Registration,
+    input: &str,
+    overrides: &Params,
+    warmup: usize,
+    runs: usize,
+) -> Result<Vec<Stats>, Error> {
+    let day = registration.solution;
+    let parsed = day.parse_any(input)?;
+    let mut stats = vec![measure("parse", warmup, runs, || day.parse_any(input))];
+    for question in [Question::First, Question::Second] {
+        if !registration.solved(question) {
+            continue;
+        }
+        let params = day.params(question).merged(overrides);
+        catch_quietly(|| day.solve_any(parsed.as_ref(), question, &params))
+            .map_err(Error::new)
+            .and_then(|answer| answer)
+            .map_err(|e| Error::new(format!("part {}: {}", part(question), e)))?;
+        stats.push(measure(
+            &format!("part{}", part(question)),
+            warmup,
+            runs,
+            || day.solve_any(parsed.as_ref(), question, &params),
+        ));
+    }
+    Ok(stats)
+}
+
+pub fn print_stats(stats: &[Stats]) {
+    let ms = |d: &Duration| format!("{:.3}ms", d.as_secs_f64() * 1000.0);
+    println!(
+        "{:<6} | {:>4} | {:>12} | {:>12} | {:>12}",
+        "step", "runs", "min", "median", "max"
+    );
+    for s in stats {
+        println!(
+            "{:<6} | {:>4} | {:>12} | {:>12} | {:>12}",
+            s.name,
+            s.runs,
+            ms(&s.min),
+            ms(&s.median),
+            ms(&s.max)
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::common::answers::AnswerStore;
+use crate::common::day::Question;
+use crate::common::registry::DayMap;
+use crate::common::runner::print_rows;
+use itertools::Itertools;
+use serde::Serialize;
+
+/// A registered day with its last verified answers, one line of `--list-days`.
+#[derive(Serialize, Clone, Debug)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    /// How many parts are solved.
+    pub parts: u8,
+    pub finished: bool,
+    pub tags: &'static [&'static str],
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub part1_ms: Option<f64>,
+    pub part2_ms: Option<f64>,
+}
+
+/// The registered days, in order, of `year` and with `tag` if given.
+pub fn catalogue(
+    years: &phf::Map<u16, DayMap>,
+    store: &AnswerStore,
+    year: Option<u16>,
+    tag: Option<&str>,
+) -> Vec<Entry> {
+    years
+        .values()
+        .flat_map(|days| days.values())
+        .filter(|r| year.is_none_or(|y| r.year == y))
+        .filter(|r| tag.is_none_or(|t| r.tags.contains(&t)))
+        .sorted_by_key(|r| (r.year, r.day))
+        .map(|r| {
+            let parts = store.parts(r.year, r.day).cloned().unwrap_or_default();
+            Entry {
+                year: r.year,
+                day: r.day,
+                title: r.title,
+                parts: r.parts,
+                finished: r.finished(),
+                tags: r.tags,
+                part1_ms: parts.time_ms(Question::First),
+                part2_ms: parts.time_ms(Question::Second),
+                part1: parts.part1,
+                part2: parts.part2,
+            }
+        })
+        .collect()
+}
+
+fn parts_cell(e: &Entry) -> String {
+    match e.parts {
+        0 => "unfinished".to_string(),
+        1 => "part 1 only".to_string(),
+        _ => "1, 2".to_string(),
+    }
+}
+
+fn answer_cell(answer: &Option<String>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => "(grid)".to_string(),
+        Some(answer) => answer.clone(),
+        None => String::new(),
+    }
+}
+
+fn time_cell(e: &Entry) -> String {
+    match (e.part1_ms, e.part2_ms) {
+        (None, None) => String::new(),
+        (t1, t2) => format!("{:.3}ms", t1.unwrap_or(0.0) + t2.unwrap_or(0.0)),
+    }
+}
+
+pub fn print_catalogue(entries: &[Entry]) {
+    let rows = entries
+        .iter()
+        .map(|e| {
+            [
+                e.year.to_string(),
+                e.day.to_string(),
+                e.title.to_string(),
+                parts_cell(e),
+                answer_cell(&e.part1),
+                answer_cell(&e.part2),
+                time_cell(e),
+                e.tags.join(", "),
+            ]
+        })
+        .collect_vec();
+    print_rows(
+        &[
+            "year", "day", "title", "parts", "answer 1", "answer 2", "time", "tags",
+        ],
+        &rows,
+    );
+}
@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE: &str = "aoc.json";
+/// The environment variables override the settings from the config file.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Local settings, stored as `{"inputs": "../aoc-inputs", "session": "53616c74..."}`.
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct Config {
+    /// Where the `<year>/dayNN.txt` inputs live, `inputs` if not set.
+    pub inputs: Option<String>,
+    /// The `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    /// The site to fetch inputs from, adventofcode.com if not set.
+    pub base_url: Option<String>,
+    /// The answers to verify against, `answers.json` if not set.
+    pub answers: Option<String>,
+}
+
+impl Config {
+    /// A missing file is the default config.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+    pub fn inputs_root(&self) -> PathBuf {
+        match env::var_os(INPUTS_ENV) {
+            Some(root) => PathBuf::from(root),
+            None => PathBuf::from(self.inputs.as_deref().unwrap_or("inputs")),
+        }
+    }
+    pub fn answers_path(&self) -> PathBuf {
+        PathBuf::from(self.answers.as_deref().unwrap_or("answers.json"))
+    }
+    pub fn session(&self) -> Option<String> {
+        env::var(SESSION_ENV).ok().or_else(|| self.session.clone())
+    }
+    pub fn base_url(&self) -> String {
+        env::var(BASE_URL_ENV)
+            .ok()
+            .or_else(|| self.base_url.clone())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+    }
+}
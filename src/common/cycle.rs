@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+/// Where the states `start, step(start), step(step(start)), ...` start going round in a loop:
+/// step `prefix + length` is the same as step `prefix`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step in the same state as step `n`, always below `prefix + length`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.length
+        }
+    }
+    /// How many times the loop goes round between `reduce(n)` and `n`.
+    pub fn rounds(&self, n: usize) -> usize {
+        (n - self.reduce(n)) / self.length
+    }
+}
+
+/// A [`Cycle`] with the states up to and including the first repeat, `prefix + length + 1` of
+/// them.
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `n` steps.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+    /// `metric` after `n` steps, for something that isn't part of the loop's state but gains
+    /// the same every round, like the height of a tower.
+    pub fn extrapolate<M>(&self, n: usize, mut metric: impl FnMut(&S) -> M) -> M
+    where
+        M: Copy + Add<Output = M> + Sub<Output = M> + Mul<Output = M> + TryFrom<usize>,
+    {
+        let Cycle { prefix, length } = self.cycle;
+        let per_round = metric(&self.states[prefix + length]) - metric(&self.states[prefix]);
+        let rounds = self.cycle.rounds(n);
+        let rounds = M::try_from(rounds)
+            .unwrap_or_else(|_| panic!("{} rounds don't fit the metric", rounds));
+        metric(self.state_at(n)) + per_round * rounds
+    }
+}
+
+/// Steps from `start` until a state repeats, remembering every state on the way.
+pub fn find<S: Clone + Eq + Hash>(start: S, step: impl FnMut(&S) -> S) -> History<S> {
+    find_by_key(start, step, S::clone)
+}
+
+/// Steps from `start` until the `key` of a state repeats. Parts of the state that only grow,
+/// like a counter or a height, should be left out of the key.
+pub fn find_by_key<S, K: Eq + Hash>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> History<S> {
+    let mut seen = HashMap::new();
+    let mut states = vec![start];
+    loop {
+        let n = states.len() - 1;
+        if let Some(prefix) = seen.insert(key(&states[n]), n) {
+            return History {
+                cycle: Cycle {
+                    prefix,
+                    length: n - prefix,
+                },
+                states,
+            };
+        }
+        let next = step(&states[n]);
+        states.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5 over and over.
+    fn lasso(x: &usize) -> usize {
+        if *x == 5 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn finds_the_loop() {
+        let history = find(0, lasso);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                prefix: 3,
+                length: 3
+            }
+        );
+        assert_eq!(history.states, vec![0, 1, 2, 3, 4, 5, 3]);
+    }
+
+    #[test]
+    fn a_loop_from_the_start() {
+        let history = find(0, |x| (x + 1) % 4);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                prefix: 0,
+                length: 4
+            }
+        );
+        assert_eq!(*history.state_at(1_000_000_001), 1);
+    }
+
+    #[test]
+    fn states_far_ahead() {
+        let history = find(0, lasso);
+        for n in 0..50 {
+            let walked = (0..n).fold(0, |x, _| lasso(&x));
+            assert_eq!(*history.state_at(n), walked, "after {} steps", n);
+        }
+        assert_eq!(history.cycle.reduce(2), 2);
+        assert_eq!(history.cycle.reduce(10), 4);
+        assert_eq!(history.cycle.rounds(10), 2);
+    }
+
+    #[test]
+    fn keyed_loop_extrapolates_what_grows() {
+        // (position, height), the height grows by the position every step
+        let step = |&(at, height): &(usize, u64)| (lasso(&at), height + at as u64);
+        let history = find_by_key((0, 0), step, |&(at, _)| at);
+        assert_eq!(history.cycle.length, 3);
+        let walk = |n: usize| (0..n).fold((0, 0), |state, _| step(&state));
+        for &n in &[0, 2, 3, 7, 20, 101] {
+            assert_eq!(history.extrapolate(n, |&(_, height)| height), walk(n).1);
+        }
+        // 3 + 4 + 5 = 12 every round
+        assert_eq!(
+            history.extrapolate(3 + 3 * 1_000_000, |&(_, height)| height),
+            3 + 12 * 1_000_000
+        );
+    }
+}
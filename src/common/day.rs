@@ -1,10 +1,259 @@
+use crate::common::error::Error;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub enum Question {
     First,
     Second,
 }
 
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Value {
+    Number(i128),
+    Text(String),
+    /// Multi-line output that has to be read by a human, like the CRT of 2022 day 10.
+    Grid(String),
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Answer {
+    pub value: Value,
+    pub diagnostics: Vec<String>,
+}
+
+impl Answer {
+    pub fn number<T: TryInto<i128>>(n: T) -> Self {
+        match n.try_into() {
+            Ok(n) => Value::Number(n).into(),
+            Err(_) => panic!("answer does not fit into i128"),
+        }
+    }
+    pub fn text(s: impl Into<String>) -> Self {
+        Value::Text(s.into()).into()
+    }
+    pub fn grid(s: impl Into<String>) -> Self {
+        Value::Grid(s.into()).into()
+    }
+    pub fn with_diagnostic(mut self, diagnostic: impl Into<String>) -> Self {
+        self.diagnostics.push(diagnostic.into());
+        self
+    }
+}
+
+impl Value {
+    /// The answer as it would be typed into the puzzle page.
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            Value::Number(n) => n.to_string(),
+            Value::Text(s) | Value::Grid(s) => s.clone(),
+        }
+    }
+}
+
+impl From<Value> for Answer {
+    fn from(value: Value) -> Self {
+        Answer {
+            value,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::number(n)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::text(s)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(s) => write!(f, "{}", s),
+            Value::Grid(s) => write!(f, "\n{}", s),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// Named puzzle parameters, like the grid size that differs between the example and the real
+/// input.
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.set(key, value);
+        self
+    }
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+    /// Errors if the parameter is missing or doesn't parse, e.g. after a typo in `-p`.
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<T, Error>
+    where
+        T::Err: Display,
+    {
+        let value = self
+            .0
+            .get(key)
+            .ok_or_else(|| Error::new(format!("missing parameter {}", key)))?;
+        value
+            .parse()
+            .map_err(|e| Error::new(format!("parameter {}={}: {}", key, value, e)))
+    }
+    /// `other` wins where both have a value.
+    pub fn merged(&self, other: &Params) -> Params {
+        let mut merged = self.clone();
+        merged
+            .0
+            .extend(other.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+        merged
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self.0.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+/// An example from the puzzle text, with the answers the puzzle gives for it.
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub params: Params,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn new(input: impl Into<String>) -> Self {
+        Example::named("example", input)
+    }
+    pub fn named(name: impl Into<String>, input: impl Into<String>) -> Self {
+        Example {
+            name: name.into(),
+            input: input.into(),
+            params: Params::new(),
+            part1: None,
+            part2: None,
+        }
+    }
+    pub fn param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params.set(key, value);
+        self
+    }
+    pub fn part1(mut self, answer: impl ToString) -> Self {
+        self.part1 = Some(answer.to_string());
+        self
+    }
+    pub fn part2(mut self, answer: impl ToString) -> Self {
+        self.part2 = Some(answer.to_string());
+        self
+    }
+    pub fn expected(&self, question: Question) -> Option<&String> {
+        match question {
+            Question::First => self.part1.as_ref(),
+            Question::Second => self.part2.as_ref(),
+        }
+    }
+}
+
 pub trait Day {
-    fn question(&self, input: &str, question: Question);
-    fn test_data(&self) -> String;
+    type Input;
+    fn parse(&self, input: &str) -> Result<Self::Input, Error>;
+    fn part1(&self, input: &Self::Input, params: &Params) -> Result<Answer, Error>;
+    fn part2(&self, input: &Self::Input, params: &Params) -> Result<Answer, Error>;
+    fn examples(&self) -> Vec<Example>;
+    /// Parameters for the real input. The examples and `-p` override them.
+    fn params(&self, _question: Question) -> Params {
+        Params::new()
+    }
+}
+
+/// [`Day`] with the `Input` type erased, so that all days fit into one registry. `Sync` so that a
+/// batch can run them on a pool of threads.
+pub trait DynDay: Sync {
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, Error>;
+    /// `input` has to come from `parse_any` of the same day.
+    fn solve_any(
+        &self,
+        input: &dyn Any,
+        question: Question,
+        params: &Params,
+    ) -> Result<Answer, Error>;
+    fn examples(&self) -> Vec<Example>;
+    fn params(&self, question: Question) -> Params;
+
+    fn question(&self, input: &str, question: Question, params: &Params) -> Result<Answer, Error> {
+        let parsed = self.parse_any(input)?;
+        self.solve_any(parsed.as_ref(), question, params)
+    }
+}
+
+impl<D: Day + Sync> DynDay for D
+where
+    D::Input: 'static,
+{
+    fn parse_any(&self, input: &str) -> Result<Box<dyn Any>, Error> {
+        Ok(Box::new(self.parse(input)?))
+    }
+    fn solve_any(
+        &self,
+        input: &dyn Any,
+        question: Question,
+        params: &Params,
+    ) -> Result<Answer, Error> {
+        let input = input
+            .downcast_ref::<D::Input>()
+            .expect("input parsed by another day");
+        match question {
+            Question::First => self.part1(input, params),
+            Question::Second => self.part2(input, params),
+        }
+    }
+    fn examples(&self) -> Vec<Example> {
+        Day::examples(self)
+    }
+    fn params(&self, question: Question) -> Params {
+        Day::params(self, question)
+    }
 }
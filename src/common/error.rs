@@ -0,0 +1,112 @@
+use std::convert::{Infallible, TryFrom};
+use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// An error from reading the input or solving a question. `line` and `column` are 1-based, for
+/// showing to a human.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Error {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+    /// `line` and `column` are 0-based, as they come out of `enumerate`.
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error {
+            message: message.into(),
+            line: Some(line + 1),
+            column: Some(column + 1),
+        }
+    }
+    /// Sets the (0-based) line, unless the error already knows where it happened.
+    pub fn on_line(mut self, line: usize) -> Self {
+        if self.line.is_none() {
+            self.line = Some(line + 1);
+        }
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {}: {}", line, column, self.message)
+            }
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            (None, Some(column)) => write!(f, "column {}: {}", column, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::new(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::new(message)
+    }
+}
+
+/// For grids of cells that every character converts into.
+impl From<Infallible> for Error {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::new(e.to_string())
+    }
+}
+
+/// Parses every line of the input on its own, errors point at the line.
+pub fn parse_each_line<T: FromStr>(input: &str) -> Result<Vec<T>, Error>
+where
+    T::Err: Into<Error>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e: T::Err| e.into().on_line(i)))
+        .collect()
+}
+
+/// Reads a grid of characters, errors point at the character.
+pub fn parse_grid<T: TryFrom<char>>(input: &str) -> Result<Vec<Vec<T>>, Error>
+where
+    T::Error: Into<Error>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, ch)| {
+                    T::try_from(ch).map_err(|e| {
+                        let e = e.into();
+                        Error::at(y, x, e.message)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
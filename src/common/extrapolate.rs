@@ -0,0 +1,161 @@
+use crate::common::error::Error;
+use itertools::Itertools;
+
+/// A sequence sampled at evenly spaced steps whose differences, taken often enough, become
+/// constant: a polynomial in the step. Kept as the first sample and its leading differences,
+/// which is all Newton's forward formula needs.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Polynomial {
+    start: i128,
+    spacing: i128,
+    differences: Vec<i128>,
+}
+
+impl Polynomial {
+    /// The value at `step`, which has to be a whole number of spacings away from the first
+    /// sample, before or after it. Errors rather than overflowing.
+    pub fn at(&self, step: i128) -> Result<i128, Error> {
+        let offset = step - self.start;
+        if offset % self.spacing != 0 {
+            return Err(Error::new(format!(
+                "step {} isn't a multiple of {} away from the samples at {}",
+                step, self.spacing, self.start
+            )));
+        }
+        let n = offset / self.spacing;
+        let overflow = || Error::new(format!("the value at step {} overflows", step));
+        // Past the last non-zero difference the terms are all zero, and their binomials could
+        // overflow on their own.
+        let used = match self.differences.iter().rposition(|&d| d != 0) {
+            Some(last) => &self.differences[..=last],
+            None => return Ok(0),
+        };
+        // `binomial` goes through n choose 0, 1, 2, ..., each division is exact.
+        let mut binomial: i128 = 1;
+        let mut value: i128 = 0;
+        for (k, difference) in used.iter().enumerate() {
+            if k > 0 {
+                binomial = binomial
+                    .checked_mul(n - k as i128 + 1)
+                    .ok_or_else(overflow)?
+                    / k as i128;
+            }
+            let term = binomial.checked_mul(*difference).ok_or_else(overflow)?;
+            value = value.checked_add(term).ok_or_else(overflow)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Fits the `(step, value)` samples, which have to be evenly spaced and in order. The samples
+/// have to show the differences staying constant at least once, so `k + 2` of them are needed
+/// for a polynomial of degree `k`.
+pub fn fit(samples: &[(i128, i128)]) -> Result<Polynomial, Error> {
+    if samples.len() < 2 {
+        return Err(Error::new("at least two samples are needed"));
+    }
+    let start = samples[0].0;
+    let spacing = samples[1].0 - start;
+    if spacing <= 0 {
+        return Err(Error::new("the samples have to be in increasing steps"));
+    }
+    if let Some((i, _)) = samples
+        .iter()
+        .tuple_windows()
+        .find_position(|((a, _), (b, _))| b - a != spacing)
+    {
+        return Err(Error::new(format!(
+            "sample {} is {} steps after the one before it, not {}",
+            i + 1,
+            samples[i + 1].0 - samples[i].0,
+            spacing
+        )));
+    }
+    let mut row = samples.iter().map(|&(_, value)| value).collect_vec();
+    let mut differences = Vec::new();
+    while row.len() >= 2 {
+        differences.push(row[0]);
+        if row.iter().all_equal() {
+            return Ok(Polynomial {
+                start,
+                spacing,
+                differences,
+            });
+        }
+        row = row.iter().tuple_windows().map(|(a, b)| b - a).collect();
+    }
+    Err(Error::new(format!(
+        "the differences of {} samples never stay constant, more samples are needed",
+        samples.len()
+    )))
+}
+
+/// The value at `step` of the sequence through `samples`, see [`fit`].
+pub fn extrapolate(samples: &[(i128, i128)], step: i128) -> Result<i128, Error> {
+    fit(samples)?.at(step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(f: impl Fn(i128) -> i128, steps: &[i128]) -> Vec<(i128, i128)> {
+        steps.iter().map(|&step| (step, f(step))).collect()
+    }
+
+    #[test]
+    fn fits_a_constant() {
+        let polynomial = fit(&samples(|_| 7, &[0, 1])).unwrap();
+        assert_eq!(polynomial.differences.len(), 1);
+        assert_eq!(polynomial.at(1000), Ok(7));
+    }
+
+    #[test]
+    fn fits_a_line() {
+        let polynomial = fit(&samples(|x| 3 * x - 2, &[1, 2, 3])).unwrap();
+        assert_eq!(polynomial.differences.len(), 2);
+        assert_eq!(polynomial.at(10), Ok(28));
+        assert_eq!(polynomial.at(-5), Ok(-17));
+    }
+
+    #[test]
+    fn fits_a_quadratic_with_spacing() {
+        let f = |x| 2 * x * x - x + 5;
+        let polynomial = fit(&samples(f, &[65, 196, 327, 458])).unwrap();
+        assert_eq!(polynomial.differences.len(), 3);
+        assert_eq!(polynomial.at(26501365), Ok(f(26501365)));
+        assert_eq!(polynomial.at(65), Ok(f(65)));
+        assert!(polynomial.at(66).is_err());
+    }
+
+    #[test]
+    fn fits_a_cubic() {
+        let f = |x| x * x * x - 4 * x;
+        assert_eq!(extrapolate(&samples(f, &[0, 2, 4, 6, 8]), 100), Ok(f(100)));
+    }
+
+    #[test]
+    fn far_steps_dont_overflow_past_the_last_difference() {
+        // n choose 2 of this n overflows, but a line never needs it.
+        let polynomial = fit(&samples(|x| x + 1, &[0, 1, 2])).unwrap();
+        let far = 10i128.pow(20);
+        assert_eq!(polynomial.at(far), Ok(far + 1));
+        let flat = fit(&samples(|_| 0, &[0, 1])).unwrap();
+        assert_eq!(flat.at(far), Ok(0));
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        let polynomial = fit(&samples(|x| x * x, &[0, 1, 2, 3])).unwrap();
+        assert!(polynomial.at(10i128.pow(20)).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_samples() {
+        assert!(fit(&[(0, 1)]).is_err());
+        assert!(fit(&[(2, 1), (1, 1)]).is_err());
+        assert!(fit(&[(0, 1), (1, 2), (3, 3)]).is_err());
+        // a quadratic needs four samples to show it
+        assert!(fit(&samples(|x| x * x, &[0, 1, 2])).is_err());
+    }
+}
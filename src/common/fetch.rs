@@ -0,0 +1,178 @@
+use crate::common::config::{Config, CONFIG_FILE, SESSION_ENV};
+use crate::common::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// The site asks scripts to say who they are.
+pub const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+/// Returns the input cached at `path`, downloading it first if it isn't there yet.
+pub fn fetch_input(config: &Config, year: u16, day: u8, path: &Path) -> Result<String, Error> {
+    if path.exists() {
+        return fs::read_to_string(path)
+            .map_err(|e| Error::new(format!("{}: {}", path.display(), e)));
+    }
+    let session = config.session().ok_or_else(|| {
+        Error::new(format!(
+            "{} is missing, set {} or \"session\" in {} to fetch it",
+            path.display(),
+            SESSION_ENV,
+            CONFIG_FILE
+        ))
+    })?;
+    let url = format!(
+        "{}/{}/day/{}/input",
+        config.base_url().trim_end_matches('/'),
+        year,
+        day
+    );
+    let input = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| http_error(&url, e))?
+        .into_string()
+        .map_err(|e| Error::new(format!("{}: {}", url, e)))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::new(format!("{}: {}", dir.display(), e)))?;
+    }
+    fs::write(path, &input).map_err(|e| Error::new(format!("{}: {}", path.display(), e)))?;
+    Ok(input)
+}
+
+/// The site explains what's wrong (e.g. not logged in) in the body of the error response.
+pub fn http_error(url: &str, err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Status(code, response) => Error::new(format!(
+            "{}: status {}: {}",
+            url,
+            code,
+            response.into_string().unwrap_or_default().trim()
+        )),
+        err => Error::new(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::config::BASE_URL_ENV;
+    use std::env;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::process;
+    use std::sync::Mutex;
+    use std::thread::{self, JoinHandle};
+
+    /// The tests set the environment the config reads, one at a time.
+    static ENV: Mutex<()> = Mutex::new(());
+
+    /// An empty directory of its own for the test to cache inputs in.
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Answers a single request with `status` and `body`, then hands back the request's head.
+    fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn reads_the_cache() {
+        let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+        env::remove_var(SESSION_ENV);
+        let dir = cache_dir("hit");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("day01.txt");
+        fs::write(&path, "cached\n").unwrap();
+        // No session and nowhere to fetch from, it mustn't try.
+        let input = fetch_input(&Config::default(), 2024, 1, &path);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(input.unwrap(), "cached\n");
+    }
+
+    #[test]
+    fn fetches_and_caches() {
+        let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+        let (url, server) = serve_once(200, "1 2\n3 4\n");
+        env::set_var(BASE_URL_ENV, &url);
+        env::set_var(SESSION_ENV, "c00kie");
+        let dir = cache_dir("miss");
+        let path = dir.join("2024").join("day03.txt");
+        let input = fetch_input(&Config::default(), 2024, 3, &path);
+        env::remove_var(BASE_URL_ENV);
+        env::remove_var(SESSION_ENV);
+        let cached = fs::read_to_string(&path);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(input.unwrap(), "1 2\n3 4\n");
+        assert_eq!(cached.unwrap(), "1 2\n3 4\n");
+        let head = server.join().unwrap();
+        assert!(head.starts_with("GET /2024/day/3/input "), "{}", head);
+        assert!(head.contains("session=c00kie"), "{}", head);
+    }
+
+    #[test]
+    fn needs_a_session() {
+        let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+        env::remove_var(SESSION_ENV);
+        let dir = cache_dir("no-session");
+        let path = dir.join("day05.txt");
+        let err = fetch_input(&Config::default(), 2024, 5, &path).unwrap_err();
+        assert!(err.to_string().contains(SESSION_ENV), "{}", err);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn reports_what_the_site_says() {
+        let _env = ENV.lock().unwrap_or_else(|e| e.into_inner());
+        let (url, server) = serve_once(400, "Puzzle inputs differ by user.  Please log in.\n");
+        env::set_var(BASE_URL_ENV, &url);
+        env::remove_var(SESSION_ENV);
+        let config = Config {
+            session: Some("expired".to_string()),
+            ..Config::default()
+        };
+        let dir = cache_dir("refused");
+        let path = dir.join("day07.txt");
+        let result = fetch_input(&config, 2024, 7, &path);
+        env::remove_var(BASE_URL_ENV);
+        server.join().unwrap();
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("status 400"), "{}", err);
+        assert!(err.contains("Please log in."), "{}", err);
+        assert!(!path.exists());
+    }
+}
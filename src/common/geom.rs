@@ -0,0 +1,565 @@
+use crate::common::error::Error;
+use crate::common::map::{Coord, Position};
+use itertools::Itertools;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// One of the four ways to go on a grid. `y` grows downwards, so `Up` is towards row 0.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(2)
+    }
+    pub fn turn_right(self) -> Direction {
+        self.rotate(1)
+    }
+    pub fn turn_left(self) -> Direction {
+        self.rotate(3)
+    }
+    /// Turned clockwise `quarters` times, counterclockwise if negative.
+    pub fn rotate(self, quarters: i32) -> Direction {
+        Direction::ALL[(self as i32 + quarters).rem_euclid(4) as usize]
+    }
+    /// Mirrored left to right, `Up` and `Down` stay.
+    pub fn flip_horizontal(self) -> Direction {
+        match self {
+            Direction::Left | Direction::Right => self.opposite(),
+            _ => self,
+        }
+    }
+    /// Mirrored top to bottom, `Left` and `Right` stay.
+    pub fn flip_vertical(self) -> Direction {
+        match self {
+            Direction::Up | Direction::Down => self.opposite(),
+            _ => self,
+        }
+    }
+    /// Bounced off a `/` mirror.
+    pub fn reflect_slash(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Down,
+        }
+    }
+    /// Bounced off a `\` mirror.
+    pub fn reflect_backslash(self) -> Direction {
+        self.reflect_slash().opposite()
+    }
+    pub fn is_horizontal(self) -> bool {
+        self == Direction::Left || self == Direction::Right
+    }
+    /// The `(dx, dy)` of a step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+    /// A step from `at`, `None` if it'd go below zero. There's no upper bound, see
+    /// [`crate::common::map::Grid::step`] for one.
+    pub fn step(self, at: Coord) -> Option<Coord> {
+        let (dx, dy) = self.delta();
+        Some((at.0.checked_add_signed(dx)?, at.1.checked_add_signed(dy)?))
+    }
+}
+
+impl From<Direction> for (isize, isize) {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+/// The arrows of the puzzle maps.
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        };
+        write!(f, "{}", arrow)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '^' => Ok(Direction::Up),
+            '>' => Ok(Direction::Right),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            _ => Err(Error::new(format!("not an arrow: {}", ch))),
+        }
+    }
+}
+
+/// One of the eight ways to go on a grid, diagonals included. `N` is towards row 0.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Clockwise from `N`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    pub fn opposite(self) -> Direction8 {
+        self.rotate(4)
+    }
+    /// Turned clockwise by `eighths` of a full turn, counterclockwise if negative.
+    pub fn rotate(self, eighths: i32) -> Direction8 {
+        Direction8::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+    pub fn turn_right(self) -> Direction8 {
+        self.rotate(2)
+    }
+    pub fn turn_left(self) -> Direction8 {
+        self.rotate(-2)
+    }
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+    /// The `(dx, dy)` of a step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction8::N => (0, -1),
+            Direction8::NE => (1, -1),
+            Direction8::E => (1, 0),
+            Direction8::SE => (1, 1),
+            Direction8::S => (0, 1),
+            Direction8::SW => (-1, 1),
+            Direction8::W => (-1, 0),
+            Direction8::NW => (-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for (isize, isize) {
+    fn from(direction: Direction8) -> Self {
+        direction.delta()
+    }
+}
+
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// A point or a vector on the plane, `y` growing downwards like on the grids.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = T> + Add<Output = T>> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+    /// The king's distance, diagonal steps count as one.
+    pub fn chebyshev(self, other: Self) -> T {
+        max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Neg<Output = T>> Point2<T> {
+    /// Turned 90 degrees clockwise around the origin, `Up` becomes `Right`.
+    pub fn rotate_right(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+    pub fn rotate_left(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+    /// Mirrored on the `y` axis.
+    pub fn flip_horizontal(self) -> Self {
+        Point2::new(-self.x, self.y)
+    }
+    /// Mirrored on the `x` axis.
+    pub fn flip_vertical(self) -> Self {
+        Point2::new(self.x, -self.y)
+    }
+}
+
+impl Point2<usize> {
+    /// A step in `direction`, `None` if it'd leave the `width` by `height` rectangle from the
+    /// origin.
+    pub fn step(
+        self,
+        direction: impl Into<(isize, isize)>,
+        width: usize,
+        height: usize,
+    ) -> Option<Self> {
+        let (dx, dy) = direction.into();
+        let x = self.x.checked_add_signed(dx).filter(|&x| x < width)?;
+        let y = self.y.checked_add_signed(dy).filter(|&y| y < height)?;
+        Some(Point2::new(x, y))
+    }
+}
+
+impl From<Coord> for Point2<usize> {
+    fn from((x, y): Coord) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<Point2<usize>> for Coord {
+    fn from(p: Point2<usize>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl From<Direction> for Point2<isize> {
+    fn from(direction: Direction) -> Self {
+        let (x, y) = direction.delta();
+        Point2::new(x, y)
+    }
+}
+
+impl From<Direction8> for Point2<isize> {
+    fn from(direction: Direction8) -> Self {
+        let (x, y) = direction.delta();
+        Point2::new(x, y)
+    }
+}
+
+impl Position for Point2<usize> {
+    fn coord(self) -> Option<Coord> {
+        Some((self.x, self.y))
+    }
+}
+
+macro_rules! signed_point_position {
+    ($($t:ty),*) => {
+        $(
+            impl Position for Point2<$t> {
+                fn coord(self) -> Option<Coord> {
+                    (self.x, self.y).coord()
+                }
+            }
+        )*
+    };
+}
+
+signed_point_position!(isize, i32, i64);
+
+/// A point or a vector in space.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = T> + Add<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+    /// The king's distance, diagonal steps count as one.
+    pub fn chebyshev(self, other: Self) -> T {
+        max(
+            max(abs_diff(self.x, other.x), abs_diff(self.y, other.y)),
+            abs_diff(self.z, other.z),
+        )
+    }
+}
+
+macro_rules! vector_ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $point { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $point { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)*
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $point { $($field: -self.$field),* }
+            }
+        }
+
+        /// Scaling by a scalar.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $point { $($field: self.$field * rhs),* }
+            }
+        }
+    };
+}
+
+vector_ops!(Point2 { x, y });
+vector_ops!(Point3 { x, y, z });
+
+/// `x,y` as in most inputs.
+impl<T: FromStr> FromStr for Point2<T>
+where
+    T::Err: Into<Error>,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split(',')
+            .map(|n| n.trim().parse().map_err(|e: T::Err| e.into()))
+            .collect_tuple()
+            .ok_or_else(|| Error::new(format!("not an x,y pair: {}", s)))?;
+        Ok(Point2::new(x?, y?))
+    }
+}
+
+/// `x,y,z` as in most inputs.
+impl<T: FromStr> FromStr for Point3<T>
+where
+    T::Err: Into<Error>,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y, z) = s
+            .split(',')
+            .map(|n| n.trim().parse().map_err(|e: T::Err| e.into()))
+            .collect_tuple()
+            .ok_or_else(|| Error::new(format!("not an x,y,z triple: {}", s)))?;
+        Ok(Point3::new(x?, y?, z?))
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    #[test]
+    fn rotates() {
+        let table = [
+            (Up, 1, Right),
+            (Right, 1, Down),
+            (Down, 1, Left),
+            (Left, 1, Up),
+            (Up, -1, Left),
+            (Right, -1, Up),
+            (Down, -1, Right),
+            (Left, -1, Down),
+            (Up, 2, Down),
+            (Left, 2, Right),
+            (Right, 4, Right),
+            (Down, -6, Up),
+        ];
+        for (from, quarters, to) in table {
+            assert_eq!(from.rotate(quarters), to, "{:?} by {}", from, quarters);
+        }
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right(), direction.rotate(1));
+            assert_eq!(direction.turn_left(), direction.rotate(-1));
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+
+    #[test]
+    fn reflects() {
+        // Bounced off /, off \, flipped left to right, flipped top to bottom.
+        let table = [
+            (Up, Right, Left, Up, Down),
+            (Right, Up, Down, Left, Right),
+            (Down, Left, Right, Down, Up),
+            (Left, Down, Up, Right, Left),
+        ];
+        for (from, slash, backslash, horizontal, vertical) in table {
+            assert_eq!(from.reflect_slash(), slash, "{:?} off /", from);
+            assert_eq!(from.reflect_backslash(), backslash, "{:?} off \\", from);
+            assert_eq!(from.flip_horizontal(), horizontal, "{:?} flipped", from);
+            assert_eq!(from.flip_vertical(), vertical, "{:?} flipped", from);
+        }
+    }
+
+    #[test]
+    fn steps() {
+        let table = [
+            (Up, (3, 5), Some((3, 4))),
+            (Right, (3, 5), Some((4, 5))),
+            (Down, (3, 5), Some((3, 6))),
+            (Left, (3, 5), Some((2, 5))),
+            (Up, (3, 0), None),
+            (Left, (0, 5), None),
+            (Down, (0, 0), Some((0, 1))),
+            (Right, (0, 0), Some((1, 0))),
+        ];
+        for (direction, at, to) in table {
+            assert_eq!(direction.step(at), to, "{:?} from {:?}", direction, at);
+        }
+    }
+
+    #[test]
+    fn rotates_eighths() {
+        use Direction8::*;
+        let table = [
+            (N, 1, NE),
+            (NE, 1, E),
+            (E, 1, SE),
+            (SE, 1, S),
+            (S, 1, SW),
+            (SW, 1, W),
+            (W, 1, NW),
+            (NW, 1, N),
+            (N, -1, NW),
+            (N, 2, E),
+            (NE, -2, NW),
+            (SW, 4, NE),
+            (W, 10, N),
+        ];
+        for (from, eighths, to) in table {
+            assert_eq!(from.rotate(eighths), to, "{:?} by {}", from, eighths);
+        }
+        for direction in Direction::ALL {
+            assert_eq!(
+                Direction8::from(direction.turn_right()),
+                Direction8::from(direction).turn_right()
+            );
+        }
+    }
+
+    #[test]
+    fn steps_eighths() {
+        use Direction8::*;
+        let table = [
+            (N, (0, -1)),
+            (NE, (1, -1)),
+            (E, (1, 0)),
+            (SE, (1, 1)),
+            (S, (0, 1)),
+            (SW, (-1, 1)),
+            (W, (-1, 0)),
+            (NW, (-1, -1)),
+        ];
+        for (direction, delta) in table {
+            assert_eq!(direction.delta(), delta, "{:?}", direction);
+            assert_eq!(direction.opposite().delta(), (-delta.0, -delta.1));
+            assert_eq!(direction.is_diagonal(), delta.0 != 0 && delta.1 != 0);
+        }
+        let at = Point2::new(1usize, 1);
+        assert_eq!(at.step(NW, 3, 3), Some(Point2::new(0, 0)));
+        assert_eq!(at.step(SE, 3, 3), Some(Point2::new(2, 2)));
+        assert_eq!(Point2::new(2usize, 0).step(NE, 3, 3), None);
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point3::new(1, -2, 3), Point3::new(-3, 2, 4));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(b.manhattan(a), 9);
+        assert_eq!(a.manhattan(a), 0);
+        let (a, b) = (Point3::new(5usize, 0, 0), Point3::new(2usize, 7, 1));
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        let (a, b) = (Point2::new(0, 0), Point2::new(-3, 2));
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+    }
+}
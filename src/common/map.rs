@@ -1,192 +1,257 @@
-use crate::aoc2022::day14::Object;
-use crate::common::lines::Lineser;
-use itertools::Itertools;
-use std::borrow::Borrow;
+use crate::common::error::{parse_grid, Error};
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
-pub trait Bottom {
-    fn bottom() -> Self;
+/// An `(x, y)` cell of a [`Grid`], `x` going right and `y` going down from the top left corner.
+pub type Coord = (usize, usize);
+
+/// Something that points at a cell. Signed coordinates may point off the grid, those never
+/// resolve to a cell.
+pub trait Position: Copy {
+    fn coord(self) -> Option<Coord>;
+}
+
+impl Position for (usize, usize) {
+    fn coord(self) -> Option<Coord> {
+        Some(self)
+    }
+}
+
+macro_rules! signed_position {
+    ($($t:ty),*) => {
+        $(
+            impl Position for ($t, $t) {
+                fn coord(self) -> Option<Coord> {
+                    Some((usize::try_from(self.0).ok()?, usize::try_from(self.1).ok()?))
+                }
+            }
+        )*
+    };
 }
 
-#[derive(Clone)]
-pub struct Map<T> {
-    points: Vec<T>,
-    min_x: usize,
-    max_x: usize,
-    max_y: usize,
-    min_y: usize,
-    floor: bool,
-    rotated: bool,
+signed_position!(isize, i32, i64);
+
+/// A rectangle of cells, stored row by row.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
 }
 
-impl<T: Display + Default + Clone> Map<T> {
-    pub fn new(min_x: usize, max_x: usize, max_y: usize, floor: bool) -> Self {
-        let mut map = Map {
-            points: Vec::new(),
-            min_x: min_x - 5,
-            max_x: max_x + 5,
-            min_y: 0,
-            max_y: max_y + 1,
-            floor,
-            rotated: false,
-        };
-        map.points = vec![Default::default(); map.vec_len()];
-        map
+const SIDES: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        Grid {
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(&mut f)
+                .collect(),
+            width,
+            height,
+        }
+    }
+    /// Errors if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::new(format!(
+                    "row is {} long, the first one is {}",
+                    row.len(),
+                    width
+                ))
+                .on_line(y));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
     }
     pub fn width(&self) -> usize {
-        self.max_x - self.min_x + 1
+        self.width
     }
     pub fn height(&self) -> usize {
-        self.max_y - self.min_y + 1
+        self.height
     }
-    pub fn vec_len(&self) -> usize {
-        self.width() * self.height()
+    pub fn contains(&self, at: impl Position) -> bool {
+        self.offset(at).is_some()
     }
-    fn index(&self, i: usize, j: usize) -> usize {
-        if i < self.min_x {
-            print!("I {}", i);
-            println!("{}", self);
-        }
-        let idx_x = if !self.rotated {
-            i - self.min_x
+    fn offset(&self, at: impl Position) -> Option<usize> {
+        let (x, y) = at.coord()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
         } else {
-            j - self.min_x
-        };
-        let idx_y = if !self.rotated {
-            self.width() * j - self.min_y * self.width()
+            None
+        }
+    }
+    pub fn get(&self, at: impl Position) -> Option<&T> {
+        self.offset(at).map(|i| &self.cells[i])
+    }
+    pub fn get_mut(&mut self, at: impl Position) -> Option<&mut T> {
+        self.offset(at).map(move |i| &mut self.cells[i])
+    }
+    /// `at` moved by `(dx, dy)` or towards a [`crate::common::geom::Direction`], if that's still
+    /// on the grid.
+    pub fn step(&self, at: Coord, delta: impl Into<(isize, isize)>) -> Option<Coord> {
+        let (dx, dy) = delta.into();
+        let to = (at.0.checked_add_signed(dx)?, at.1.checked_add_signed(dy)?);
+        if self.contains(to) {
+            Some(to)
         } else {
-            self.width() * (self.width() - i - 1) - self.min_y * self.width()
-        };
-        idx_x + idx_y
-    }
-    pub fn set(&mut self, i: usize, j: usize, obj: T) {
-        let idx = self.index(i, j);
-        self.points[idx] = obj;
-    }
-    pub fn get_ref(&self, i: usize, j: usize) -> &T {
-        &self.points[self.index(i, j)]
-    }
-    pub fn rotate(&self) -> Map<T> {
-        Map {
-            points: self.points.clone(),
-            min_x: self.min_y,
-            max_x: self.max_y,
-            min_y: self.min_x,
-            max_y: self.max_x,
-            floor: self.floor,
-            rotated: !self.rotated,
+            None
         }
     }
+    /// The cells left, right, above and below `at` that are on the grid.
+    pub fn neighbors(&self, at: Coord) -> impl Iterator<Item = Coord> + '_ {
+        SIDES.iter().filter_map(move |&d| self.step(at, d))
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, an empty grid has no rows anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} is off the {}x{} grid",
+            x,
+            self.width,
+            self.height
+        );
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+    /// Every cell with where it is, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+    /// The cell at `(x, y)` of the grid repeated forever in every direction.
+    pub fn get_wrapping(&self, (x, y): (isize, isize)) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+    /// The first cell, row by row, holding `value`.
+    pub fn find(&self, value: &T) -> Option<Coord>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.cells().find(|(_, cell)| pred(cell)).map(|(at, _)| at)
+    }
+    /// Turned 90 degrees clockwise, the left column becomes the top row.
+    pub fn rotate(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
 }
 
-impl FromStr for Map<char> {
-    type Err = String;
+impl<T, P: Position> Index<P> for Grid<T> {
+    type Output = T;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let points: Vec<char> = s.chars().filter(|&c| c != '\n').collect_vec();
-        let lines = s.lines().collect_vec();
-        let max_y = lines.len() - 1;
-        let max_x = lines[0].len() - 1;
-
-        Ok(Map {
-            points,
-            floor: false,
-            min_x: 0,
-            min_y: 0,
-            max_x,
-            max_y,
-            rotated: false,
-        })
+    fn index(&self, at: P) -> &Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get(at)
+            .unwrap_or_else(|| panic!("{:?} is off the {}x{} grid", at.coord(), width, height))
     }
 }
 
-// impl<T: From<char>> FromStr for Map<T> {
-//     type Err = String;
-//
-//     fn from_str(s: &str) -> Result<Self, Self::Err> {
-//         let points: Vec<char> = s.chars().filter(|&c| c != '\n').collect_vec();
-//         let points = points.into_iter().map(|c| From::from(c)).collect_vec();
-//         let lines = s.lines().collect_vec();
-//         let max_y = lines.len() - 1;
-//         let max_x = lines[0].len() - 1;
-//
-//         Ok(Map {
-//             points,
-//             floor: false,
-//             min_x: 0,
-//             min_y: 0,
-//             max_x,
-//             max_y,
-//             rotated: false,
-//         })
-//     }
-// }
-
-impl<T: Display + Default + Clone + Copy> Map<T> {
-    pub fn get_no_floor(&self, i: usize, j: usize) -> T {
-        self.points[self.index(i, j)]
+impl<T, P: Position> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, at: P) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(at)
+            .unwrap_or_else(|| panic!("{:?} is off the {}x{} grid", at.coord(), width, height))
     }
 }
 
-impl<T: Display + Default + Clone + Bottom + Copy> Map<T> {
-    pub fn get(&self, i: usize, j: usize) -> T {
-        if self.floor && j == self.max_y {
-            return Bottom::bottom();
-        }
-        let idx = self.index(i, j);
-        self.points[idx]
+/// One character per cell, errors point at the character that isn't a `T`.
+impl<T: TryFrom<char>> FromStr for Grid<T>
+where
+    T::Error: Into<Error>,
+{
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::from_rows(parse_grid(s)?)
     }
 }
 
-impl Map<Object> {
-    pub fn drop(&mut self, drop_x: usize, drop_y: usize) -> bool {
-        let mut x = drop_x;
-        let mut y = drop_y;
-        loop {
-            if y >= self.max_y {
-                return true;
-            }
-            if self.get(x, y + 1) == Object::Air {
-                y += 1;
-            } else if self.get(x - 1, y + 1) == Object::Air {
-                x -= 1;
-                y += 1;
-            } else if self.get(x + 1, y + 1) == Object::Air {
-                x += 1;
-                y += 1;
-            } else {
-                self.set(x, y, Object::Sand);
-                break;
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
             }
+            writeln!(f)?;
         }
-        false
+        Ok(())
     }
 }
 
-impl<T: Display + Default + Clone> Display for Map<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.floor && !self.rotated {
-            for (i, elem) in self.points.iter().enumerate() {
-                if i >= self.max_y * self.width() {
-                    write!(f, "F");
-                } else {
-                    write!(f, "{}", elem);
-                    if i % self.width() == self.width() - 1 {
-                        writeln!(f);
-                    }
-                }
-            }
-        } else if self.floor && self.rotated {
-            writeln!(f, "rotated floor is just too much");
-        } else {
-            for x in 0..self.width() {
-                for y in 0..self.height() {
-                    write!(f, "{}", self.points[self.index(x, y)]);
-                }
-                writeln!(f);
-            }
-        }
-        write!(f, "")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is off the 3x2 grid")]
+    fn column_off_the_grid() {
+        grid().column(3);
+    }
+
+    #[test]
+    fn rotates_clockwise() {
+        let rotated = grid().rotate();
+        assert_eq!((rotated.width(), rotated.height()), (2, 3));
+        assert_eq!(rotated.to_string(), "da\neb\nfc\n");
     }
 }
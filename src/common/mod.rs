@@ -1,4 +1,21 @@
+pub mod answers;
+pub mod bench;
+pub mod catalogue;
 pub mod columns;
+pub mod config;
+pub mod cycle;
 pub mod day;
+pub mod error;
+pub mod extrapolate;
+pub mod fetch;
+pub mod geom;
 mod lines;
 pub mod map;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod step;
+pub mod submit;
+pub mod trace;
+pub mod watch;
@@ -0,0 +1,65 @@
+use crate::common::day::{DynDay, Question};
+
+/// A day as declared by [`register_day!`] in its module.
+pub struct Registration {
+    pub year: u16,
+    pub day: u8,
+    /// The puzzle's title on the site.
+    pub title: &'static str,
+    pub solution: &'static dyn DynDay,
+    /// How many parts are solved, fewer than 2 while a part is still `todo!()`.
+    pub parts: u8,
+    /// What kind of puzzle it is, e.g. "grid", "graph", "simulation".
+    pub tags: &'static [&'static str],
+}
+
+impl Registration {
+    pub const fn new(
+        year: u16,
+        day: u8,
+        title: &'static str,
+        solution: &'static dyn DynDay,
+    ) -> Self {
+        Registration {
+            year,
+            day,
+            title,
+            solution,
+            parts: 2,
+            tags: &[],
+        }
+    }
+    pub fn finished(&self) -> bool {
+        self.parts == 2
+    }
+    /// Whether `question` is among the solved parts, the first ones get solved first.
+    pub fn solved(&self, question: Question) -> bool {
+        match question {
+            Question::First => self.parts >= 1,
+            Question::Second => self.parts >= 2,
+        }
+    }
+}
+
+pub type DayMap = phf::Map<u8, &'static Registration>;
+
+/// Puts the day into [`YEARS`]: `register_day!(Day16, 2024, 16, "Reindeer Maze");`, optionally
+/// followed by other fields of [`Registration`], like `parts: 1, tags: &["grid"]`. build.rs
+/// looks for it in `src/aocYYYY/dayN.rs`, the module still has to be declared in its `mod.rs`.
+#[macro_export]
+macro_rules! register_day {
+    ($solution:expr, $year:literal, $day:literal, $title:literal $(, $field:ident: $value:expr)* $(,)?) => {
+        pub const REGISTRATION: $crate::common::registry::Registration =
+            $crate::common::registry::Registration {
+                $($field: $value,)*
+                ..$crate::common::registry::Registration::new($year, $day, $title, &$solution)
+            };
+    };
+}
+
+// `YEARS`, generated by build.rs.
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+pub fn find_day(year: u16, day: u8) -> Option<&'static Registration> {
+    YEARS.get(&year)?.get(&day).copied()
+}
@@ -0,0 +1,672 @@
+use crate::common::answers::AnswerStore;
+use crate::common::day::{Answer, DynDay, Params, Question, Value};
+use crate::common::error::Error;
+use crate::common::registry::DayMap;
+use crate::common::step;
+use itertools::Itertools;
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Read};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub question: Question,
+    /// The name of the example, `None` for the real input.
+    pub case: Option<String>,
+    pub expected: Option<String>,
+    /// `Err` holds the parse error, or the panic message if the solution blew up.
+    pub answer: Result<Answer, Error>,
+    /// Time spent parsing the input, shared by the questions run on the same input.
+    pub parse_time: Duration,
+    pub elapsed: Duration,
+    /// See [`input_hash`].
+    pub input_hash: String,
+}
+
+impl Run {
+    pub fn status(&self) -> &'static str {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => "fail",
+            (Ok(answer), Some(expected))
+                if answer.value.to_plain_string().trim_end() != expected.trim_end() =>
+            {
+                "wrong"
+            }
+            (Ok(_), _) => "pass",
+        }
+    }
+    /// Failed or wrong.
+    pub fn failed(&self) -> bool {
+        matches!(self.status(), "fail" | "wrong")
+    }
+    pub fn record(&self) -> Record {
+        let (answer, error) = match &self.answer {
+            Ok(answer) => (Some(answer.value.to_plain_string()), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        Record {
+            year: self.year,
+            day: self.day,
+            part: part(self.question),
+            case: self.case.clone(),
+            status: self.status(),
+            answer,
+            error,
+            expected: self.expected.clone(),
+            parse_ms: self.parse_time.as_secs_f64() * 1000.0,
+            solve_ms: self.elapsed.as_secs_f64() * 1000.0,
+            input_hash: self.input_hash.clone(),
+            verdict: None,
+        }
+    }
+}
+
+/// A run for `--format json`.
+#[derive(Serialize, Clone, Debug)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub case: Option<String>,
+    pub status: &'static str,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub expected: Option<String>,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+    pub input_hash: String,
+    /// How the answer compares with the stored one, only when verifying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<&'static str>,
+}
+
+/// FNV-1a of the input as hex, the same on every machine, to tell apart runs on different inputs.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Runs `f`, turning a panic into its message.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(panic::AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "panicked".to_string()
+        }
+    })
+}
+
+/// Like [`catch`], but the panic message isn't printed by the panic hook as well.
+pub fn catch_quietly<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = catch(f);
+    panic::set_hook(default_hook);
+    result
+}
+
+/// Parses the input once, then runs the questions on it.
+pub fn run(
+    day: &dyn DynDay,
+    year: u16,
+    day_n: u8,
+    input: &str,
+    questions: &[Question],
+    params: &Params,
+) -> Vec<Run> {
+    let start = Instant::now();
+    let parsed = catch_quietly(|| day.parse_any(input))
+        .map_err(Error::new)
+        .and_then(|parsed| parsed);
+    let parse_time = start.elapsed();
+    let hash = input_hash(input);
+    questions
+        .iter()
+        .map(|&question| {
+            let params = day.params(question).merged(params);
+            step::reset();
+            let start = Instant::now();
+            let answer = match &parsed {
+                Ok(parsed) => catch_quietly(|| day.solve_any(parsed.as_ref(), question, &params))
+                    .map_err(Error::new)
+                    .and_then(|answer| answer),
+                Err(err) => Err(err.clone()),
+            };
+            Run {
+                year,
+                day: day_n,
+                question,
+                case: None,
+                expected: None,
+                answer,
+                parse_time,
+                elapsed: start.elapsed(),
+                input_hash: hash.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Runs the examples of a day. A question is only run on the examples that give an answer for
+/// it.
+pub fn run_examples(
+    day: &dyn DynDay,
+    year: u16,
+    day_n: u8,
+    questions: &[Question],
+    overrides: &Params,
+) -> Vec<Run> {
+    let mut runs = Vec::new();
+    for example in day.examples().iter() {
+        // Without the answer there's nothing to check, and some examples don't even fit the
+        // question they have no answer for.
+        let questions = questions
+            .iter()
+            .copied()
+            .filter(|&q| example.expected(q).is_some())
+            .collect_vec();
+        if questions.is_empty() {
+            continue;
+        }
+        let params = example.params.merged(overrides);
+        for mut r in run(day, year, day_n, &example.input, &questions, &params) {
+            r.case = Some(example.name.clone());
+            r.expected = example.expected(r.question).cloned();
+            runs.push(r);
+        }
+    }
+    runs
+}
+
+/// Whether any of the examples of the day has the answer to the question, it's run on those.
+pub fn has_examples(day: &dyn DynDay, question: Question) -> bool {
+    day.examples()
+        .iter()
+        .any(|e| e.expected(question).is_some())
+}
+
+pub fn part(question: Question) -> u8 {
+    match question {
+        Question::First => 1,
+        Question::Second => 2,
+    }
+}
+
+/// A (year, day) pair as given on the command line, like `2023-5`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DayId {
+    pub year: u16,
+    pub day: u8,
+}
+
+pub fn parse_day_id(s: &str) -> Result<DayId, String> {
+    let (year, day) = s
+        .split_once('-')
+        .ok_or(format!("{} is not in <year>-<day> format", s))?;
+    let year = parse_year(year)?;
+    let day = day.parse().map_err(|_| format!("{} is not a day", day))?;
+    Ok(DayId { year, day })
+}
+
+/// A year as given on the command line, `24` is short for 2024.
+pub fn parse_year(s: &str) -> Result<u16, String> {
+    let year: u16 = s.parse().map_err(|_| format!("{} is not a year", s))?;
+    Ok(if year < 2000 { year + 2000 } else { year })
+}
+
+/// A `key=value` puzzle parameter from the command line.
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or(format!("{} is not in <key>=<value> format", s))?;
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+pub fn input_path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Reads the input file, `-` reads standard input.
+pub fn read_input(path: &Path) -> Result<String, Error> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        return io::stdin()
+            .read_to_string(&mut input)
+            .map(|_| input)
+            .map_err(|e| Error::new(format!("stdin: {}", e)));
+    }
+    fs::read_to_string(path).map_err(|e| Error::new(format!("{}: {}", path.display(), e)))
+}
+
+pub struct Batch {
+    pub runs: Vec<Run>,
+    pub skipped: Vec<(DayId, String)>,
+}
+
+/// How a batch spreads its jobs, one per (year, day, part).
+#[derive(Copy, Clone, Debug)]
+pub struct Pool {
+    pub threads: usize,
+    /// How long a job may run before it's reported as timed out. Its thread can't be stopped, it
+    /// keeps running and holding on to its memory until the process exits, while the worker that
+    /// waited for it moves on to the next job.
+    pub timeout: Duration,
+    /// How many jobs may time out before the batch gives up, as every one of them is left running
+    /// in the background. The jobs still queued then are reported as not run.
+    pub max_timeouts: usize,
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Pool {
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            timeout: Duration::from_secs(120),
+            max_timeouts: 8,
+        }
+    }
+}
+
+/// The same as the main thread's, as some days recurse deep.
+const JOB_STACK: usize = 8 << 20;
+
+/// A question of a day, on its input or on the examples when there's none.
+#[derive(Clone)]
+struct Job {
+    year: u16,
+    day: u8,
+    solution: &'static dyn DynDay,
+    question: Question,
+    input: Option<Arc<String>>,
+}
+
+impl Job {
+    fn run(&self) -> Vec<Run> {
+        let questions = [self.question];
+        match &self.input {
+            Some(input) => run(
+                self.solution,
+                self.year,
+                self.day,
+                input,
+                &questions,
+                &Params::new(),
+            ),
+            None => run_examples(
+                self.solution,
+                self.year,
+                self.day,
+                &questions,
+                &Params::new(),
+            ),
+        }
+    }
+
+    /// The job as a run that failed before getting anywhere.
+    fn failed(&self, error: String, elapsed: Duration) -> Run {
+        Run {
+            year: self.year,
+            day: self.day,
+            question: self.question,
+            case: None,
+            expected: None,
+            answer: Err(Error::new(error)),
+            parse_time: Duration::ZERO,
+            elapsed,
+            input_hash: self
+                .input
+                .as_deref()
+                .map(|i| input_hash(i))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Runs the job on a thread of its own, giving up on it after `timeout`. `Err` when it timed
+    /// out, its thread is still running then.
+    fn run_with_timeout(&self, timeout: Duration) -> Result<Vec<Run>, Box<Run>> {
+        let job = self.clone();
+        let (sender, receiver) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name(format!(
+                "{}-{}-{}",
+                self.year,
+                self.day,
+                part(self.question)
+            ))
+            .stack_size(JOB_STACK)
+            .spawn(move || {
+                let _ = sender.send(job.run());
+            });
+        let error = match spawned.map(|_| receiver.recv_timeout(timeout)) {
+            Ok(Ok(runs)) => return Ok(runs),
+            Ok(Err(RecvTimeoutError::Timeout)) => {
+                return Err(Box::new(
+                    self.failed(format!("timed out after {:?}", timeout), timeout),
+                ))
+            }
+            Ok(Err(RecvTimeoutError::Disconnected)) => "the job died".to_string(),
+            Err(e) => format!("can't start the job: {}", e),
+        };
+        Ok(vec![self.failed(error, Duration::ZERO)])
+    }
+}
+
+/// Runs every registered day between `from` and `to` (inclusive), the questions it has solved, on
+/// the inputs found under `inputs`. The questions are run in parallel on the `pool`, the runs come
+/// back in order of year, day and part all the same. Each run is handed to `on_run` as soon as the
+/// ones before it are done, so a batch that gets killed still reports what it got through.
+pub fn run_batch(
+    years: &phf::Map<u16, DayMap>,
+    inputs: &Path,
+    from: DayId,
+    to: DayId,
+    test: bool,
+    pool: Pool,
+    on_run: impl FnMut(&Run) + Send,
+) -> Batch {
+    let mut skipped = Vec::new();
+    let mut jobs = Vec::new();
+    for (&year, days) in years.entries().sorted_by_key(|(y, _)| **y) {
+        for (&day_n, day) in days.entries().sorted_by_key(|(d, _)| **d) {
+            let id = DayId { year, day: day_n };
+            if id < from || id > to {
+                continue;
+            }
+            let mut questions = [Question::First, Question::Second]
+                .iter()
+                .copied()
+                .filter(|&q| day.solved(q))
+                .collect_vec();
+            match questions.len() {
+                0 => {
+                    skipped.push((id, "not solved yet".to_string()));
+                    continue;
+                }
+                1 => skipped.push((id, "part 2 not solved yet".to_string())),
+                _ => {}
+            }
+            let input = if test {
+                questions.retain(|&q| {
+                    let checked = has_examples(day.solution, q);
+                    if !checked {
+                        let reason = format!("part {} has no example answer", part(q));
+                        skipped.push((id, reason));
+                    }
+                    checked
+                });
+                None
+            } else {
+                let path = input_path(inputs, year, day_n);
+                match fs::read_to_string(&path) {
+                    Ok(input) => Some(Arc::new(input)),
+                    Err(_) => {
+                        skipped.push((id, format!("missing {}", path.display())));
+                        continue;
+                    }
+                }
+            };
+            for question in questions {
+                jobs.push(Job {
+                    year,
+                    day: day_n,
+                    solution: day.solution,
+                    question,
+                    input: input.clone(),
+                });
+            }
+        }
+    }
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let done = Mutex::new(Done {
+        results: (0..jobs.len()).map(|_| None).collect(),
+        reported: 0,
+        on_run,
+    });
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let timeouts = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..pool.threads.max(1) {
+            scope.spawn(|| loop {
+                if timeouts.load(Ordering::SeqCst) >= pool.max_timeouts.max(1) {
+                    break;
+                }
+                let next = queue.lock().unwrap().next();
+                let Some((index, job)) = next else {
+                    break;
+                };
+                let runs = job
+                    .run_with_timeout(pool.timeout)
+                    .unwrap_or_else(|timed_out| {
+                        timeouts.fetch_add(1, Ordering::SeqCst);
+                        vec![*timed_out]
+                    });
+                done.lock().unwrap().finish(index, runs);
+            });
+        }
+    });
+    panic::set_hook(default_hook);
+    let mut done = done.into_inner().unwrap();
+    for (index, job) in queue.into_inner().unwrap() {
+        let error = format!(
+            "not run, {} jobs timed out already",
+            timeouts.load(Ordering::SeqCst)
+        );
+        done.finish(index, vec![job.failed(error, Duration::ZERO)]);
+    }
+    Batch {
+        runs: done.results.into_iter().flatten().flatten().collect(),
+        skipped,
+    }
+}
+
+/// The runs of a batch so far, and how many of them went to `on_run`.
+struct Done<F> {
+    results: Vec<Option<Vec<Run>>>,
+    reported: usize,
+    on_run: F,
+}
+
+impl<F: FnMut(&Run)> Done<F> {
+    fn finish(&mut self, index: usize, runs: Vec<Run>) {
+        self.results[index] = Some(runs);
+        while let Some(Some(runs)) = self.results.get(self.reported) {
+            runs.iter().for_each(&mut self.on_run);
+            self.reported += 1;
+        }
+    }
+}
+
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String, got: String },
+    Failed { expected: String, error: String },
+    Recorded(String),
+    Unknown,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong { .. } => "wrong",
+            Verdict::Failed { .. } => "failed",
+            Verdict::Recorded(_) => "recorded",
+            Verdict::Unknown => "unknown",
+        }
+    }
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Verdict::Wrong { .. } | Verdict::Failed { .. })
+    }
+}
+
+/// Compares the runs with the stored answers. With `record`, answers missing from the store are
+/// added to it, and the timings of the correct ones are updated.
+pub fn verify(runs: &[Run], store: &mut AnswerStore, record: bool) -> Vec<Verdict> {
+    runs.iter()
+        .map(|r| {
+            let expected = store.get(r.year, r.day, r.question).cloned();
+            let ms = (r.parse_time + r.elapsed).as_secs_f64() * 1000.0;
+            match (expected, &r.answer) {
+                (Some(expected), Ok(answer)) if expected == answer.value.to_plain_string() => {
+                    if record {
+                        store.set_time(r.year, r.day, r.question, ms);
+                    }
+                    Verdict::Correct
+                }
+                (Some(expected), Ok(answer)) => Verdict::Wrong {
+                    expected,
+                    got: answer.value.to_plain_string(),
+                },
+                (Some(expected), Err(error)) => Verdict::Failed {
+                    expected,
+                    error: error.to_string(),
+                },
+                (None, Ok(answer)) if record => {
+                    let got = answer.value.to_plain_string();
+                    store.set(r.year, r.day, r.question, got.clone());
+                    store.set_time(r.year, r.day, r.question, ms);
+                    Verdict::Recorded(got)
+                }
+                (None, _) => Verdict::Unknown,
+            }
+        })
+        .collect()
+}
+
+pub fn print_verdicts(runs: &[Run], verdicts: &[Verdict]) {
+    for (r, verdict) in runs.iter().zip(verdicts.iter()) {
+        let id = format!("{} day {} part {}", r.year, r.day, part(r.question));
+        match verdict {
+            Verdict::Correct => println!("{}: ok", id),
+            Verdict::Wrong { expected, got } => {
+                println!("{}: MISMATCH expected {} got {}", id, expected, got)
+            }
+            Verdict::Failed { expected, error } => {
+                println!("{}: FAILED expected {} but {}", id, expected, error)
+            }
+            Verdict::Recorded(answer) => println!("{}: recorded {}", id, answer),
+            Verdict::Unknown => println!("{}: no stored answer", id),
+        }
+    }
+}
+
+fn answer_cell(r: &Run) -> String {
+    let cell = match &r.answer {
+        Ok(Answer {
+            value: Value::Grid(_),
+            ..
+        }) => "(grid below)".to_string(),
+        Ok(answer) => answer.to_string(),
+        Err(err) => err.to_string().lines().next().unwrap_or("").to_string(),
+    };
+    match &r.expected {
+        Some(expected) if r.status() == "wrong" && !expected.contains('\n') => {
+            format!("{} (expected {})", cell, expected)
+        }
+        _ => cell,
+    }
+}
+
+/// Prints the rows as a table with aligned columns under the header.
+pub fn print_rows<R: AsRef<[String]>>(header: &[&str], rows: &[R]) {
+    let widths = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row.as_ref()[i].len())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect_vec();
+    print_header(header, &widths);
+    for row in rows.iter() {
+        print_row(row.as_ref(), &widths);
+    }
+}
+
+fn print_header(header: &[&str], widths: &[usize]) {
+    print_row(&header.iter().map(|h| h.to_string()).collect_vec(), widths);
+    println!("{}", widths.iter().map(|w| "-".repeat(*w)).join("-+-"));
+}
+
+fn print_row(row: &[String], widths: &[usize]) {
+    println!(
+        "{}",
+        row.iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{: <width$}", cell, width = width))
+            .join(" | ")
+            .trim_end()
+    );
+}
+
+const RUN_HEADER: [&str; 8] = [
+    "year", "day", "part", "case", "status", "answer", "parse", "time",
+];
+
+fn run_row(r: &Run) -> [String; 8] {
+    [
+        r.year.to_string(),
+        r.day.to_string(),
+        part(r.question).to_string(),
+        r.case.clone().unwrap_or_default(),
+        r.status().to_string(),
+        answer_cell(r),
+        format!("{:.3}ms", r.parse_time.as_secs_f64() * 1000.0),
+        format!("{:.3}ms", r.elapsed.as_secs_f64() * 1000.0),
+    ]
+}
+
+/// The widths of the columns when runs are printed one at a time as they come, as the rows to
+/// come aren't known yet. A cell too wide for its column pushes the rest of the row along.
+const STREAM_WIDTHS: [usize; 8] = [4, 3, 4, 9, 9, 20, 10, 12];
+
+pub fn print_run_header() {
+    print_header(&RUN_HEADER, &STREAM_WIDTHS);
+}
+
+pub fn print_run(r: &Run) {
+    print_row(&run_row(r), &STREAM_WIDTHS);
+}
+
+pub fn print_table(runs: &[Run]) {
+    print_rows(&RUN_HEADER, &runs.iter().map(run_row).collect_vec());
+    print_grids(runs);
+}
+
+/// Prints the answers that are grids, which don't fit in a table cell.
+pub fn print_grids(runs: &[Run]) {
+    for r in runs {
+        if let Ok(Answer {
+            value: Value::Grid(grid),
+            ..
+        }) = &r.answer
+        {
+            println!(
+                "\n{} day {} part {}{}:\n{}",
+                r.year,
+                r.day,
+                part(r.question),
+                r.case
+                    .as_ref()
+                    .map(|c| format!(" ({})", c))
+                    .unwrap_or_default(),
+                grid
+            );
+        }
+    }
+}
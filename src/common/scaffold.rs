@@ -0,0 +1,107 @@
+use crate::common::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = r#"use crate::common::day::{Answer, Day, Example, Params};
+use crate::common::error::Error;
+
+pub struct {{name}};
+
+crate::register_day!({{name}}, {{year}}, {{day}}, "{{title}}", parts: 0);
+
+impl Day for {{name}} {
+    type Input = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, _input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
+    }
+
+    fn part2(&self, _input: &Self::Input, _params: &Params) -> Result<Answer, Error> {
+        Err(Error::new("not solved yet"))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![{{example}}]
+    }
+}
+"#;
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::new(format!("{}: {}", path.display(), e))
+}
+
+/// Escapes the text for a `"..."` literal, keeping the line breaks like the hand written examples.
+fn string_literal(s: &str) -> String {
+    s.trim_end_matches('\n')
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+}
+
+/// The pasted example goes on its own lines, the way rustfmt lays out the multi-line ones.
+fn example_call(example: Option<&str>) -> String {
+    match example {
+        Some(example) => format!(
+            "Example::new(\n            \"{}\",\n        )",
+            string_literal(example)
+        ),
+        None => "Example::new(\"\")".to_string(),
+    }
+}
+
+/// Declares `module` in `path`, among the other declarations in the order rustfmt keeps them.
+/// `prefix` is how the declarations start, `pub mod ` in a year's `mod.rs`, `mod ` in main.rs.
+fn declare_module(path: &Path, prefix: &str, module: &str) -> Result<(), Error> {
+    let source = fs::read_to_string(path).unwrap_or_default();
+    let declaration = format!("{}{};", prefix, module);
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let declared = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, l.strip_prefix(prefix)?.strip_suffix(';')?)))
+        .collect::<Vec<_>>();
+    let at = match declared.iter().find(|(_, m)| *m > module) {
+        Some((i, _)) => *i,
+        None => declared.last().map_or(lines.len(), |(i, _)| i + 1),
+    };
+    lines.insert(at, declaration);
+    fs::write(path, lines.join("\n") + "\n").map_err(|e| io_error(path, e))
+}
+
+/// Where the day's module lives under `src`.
+pub fn day_source(src: &Path, year: u16, day: u8) -> PathBuf {
+    src.join(format!("aoc{}", year))
+        .join(format!("day{}.rs", day))
+}
+
+/// Writes `src/aocYYYY/dayN.rs` from the template and declares it, the year too if it's new.
+/// build.rs picks up the registration on the next build.
+pub fn new_day(
+    src: &Path,
+    year: u16,
+    day: u8,
+    title: &str,
+    example: Option<&str>,
+) -> Result<PathBuf, Error> {
+    let year_dir = src.join(format!("aoc{}", year));
+    let path = day_source(src, year, day);
+    if path.exists() {
+        return Err(Error::new(format!("{} already exists", path.display())));
+    }
+    if !year_dir.exists() {
+        fs::create_dir_all(&year_dir).map_err(|e| io_error(&year_dir, e))?;
+        declare_module(&src.join("main.rs"), "mod ", &format!("aoc{}", year))?;
+    }
+    let source = TEMPLATE
+        .replace("{{name}}", &format!("Day{}", day))
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title}}", &string_literal(title))
+        .replace("{{example}}", &example_call(example));
+    fs::write(&path, source).map_err(|e| io_error(&path, e))?;
+    declare_module(&year_dir.join("mod.rs"), "pub mod ", &format!("day{}", day))?;
+    Ok(path)
+}
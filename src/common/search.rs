@@ -0,0 +1,336 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found out: the cheapest cost of every state it reached, and for each of them
+/// every state it can be reached from at that cost. The latter makes a DAG of all the cheapest
+/// paths, not just one of them.
+pub struct Search<S, C> {
+    costs: HashMap<S, C>,
+    parents: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Search {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            goals: Vec::new(),
+        }
+    }
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal().and_then(|goal| self.cost(goal))
+    }
+    /// Every state reached, with its cheapest cost.
+    pub fn costs(&self) -> impl Iterator<Item = (&S, C)> {
+        self.costs.iter().map(|(state, &cost)| (state, cost))
+    }
+    /// One of the cheapest paths from a start to `to`, both ends included.
+    pub fn path(&self, to: &S) -> Option<Vec<S>> {
+        self.costs.get(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(parent) = self
+            .parents
+            .get(path.last().unwrap())
+            .and_then(|parents| parents.first())
+        {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+    /// One of the cheapest paths to a goal.
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.path(self.goal()?)
+    }
+    /// The states on any of the cheapest paths from a start to one of `to`.
+    pub fn on_paths_to<'a>(&self, to: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::new();
+        let mut queue = to
+            .into_iter()
+            .filter(|state| self.costs.contains_key(state))
+            .cloned()
+            .collect::<Vec<_>>();
+        while let Some(state) = queue.pop() {
+            // Reachable over more than one path, it was queued again before it was seen.
+            if seen.contains(&state) {
+                continue;
+            }
+            if let Some(parents) = self.parents.get(&state) {
+                queue.extend(parents.iter().filter(|p| !seen.contains(*p)).cloned());
+            }
+            seen.insert(state);
+        }
+        seen
+    }
+    /// The states on any of the cheapest paths to any of the goals.
+    pub fn on_goal_paths(&self) -> HashSet<S> {
+        self.on_paths_to(&self.goals)
+    }
+}
+
+/// Breadth first, every step costs 1. Stops once every goal as close as the closest one is
+/// found; pass `|_| false` as `is_goal` to walk everything reachable.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.costs.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((state, cost)) = queue.pop_front() {
+        if search.goal_cost().is_some_and(|best| cost > best) {
+            break;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+        for next in successors(&state) {
+            match search.costs.get(&next) {
+                Some(&known) if known < cost + 1 => {}
+                Some(_) => search.parents.entry(next).or_default().push(state.clone()),
+                None => {
+                    search.costs.insert(next.clone(), cost + 1);
+                    search.parents.insert(next.clone(), vec![state.clone()]);
+                    queue.push_back((next, cost + 1));
+                }
+            }
+        }
+    }
+    search
+}
+
+/// Cheapest first, `successors` gives the cost of each step with the state. Costs have to be
+/// positive.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Dijkstra led on by `heuristic`, an estimate of the cost left to a goal. The estimate must
+/// never be more than the real cost, nor drop by more than a step costs, or the paths found may
+/// not be the cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    let mut done = HashSet::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if search.costs.insert(start.clone(), C::default()).is_none() {
+            heap.push(Queued {
+                estimate: heuristic(&start),
+                cost: C::default(),
+                state: start,
+            });
+        }
+    }
+    while let Some(Queued {
+        estimate,
+        cost,
+        state,
+    }) = heap.pop()
+    {
+        if search.goal_cost().is_some_and(|best| estimate > best) {
+            break;
+        }
+        if search.costs[&state] < cost || !done.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            match search.costs.get(&next) {
+                Some(&known) if known < next_cost => {}
+                Some(&known) if known == next_cost => {
+                    search.parents.entry(next).or_default().push(state.clone())
+                }
+                _ => {
+                    search.costs.insert(next.clone(), next_cost);
+                    search.parents.insert(next.clone(), vec![state.clone()]);
+                    heap.push(Queued {
+                        estimate: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+    search
+}
+
+/// A state on the heap, ordered so that the lowest estimate comes out first.
+struct Queued<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two ways of the same length from a to e through b or c, and a longer one through f.
+    fn edges(state: &char) -> Vec<char> {
+        match state {
+            'a' => vec!['b', 'c', 'f'],
+            'b' | 'c' => vec!['d'],
+            'd' => vec!['e'],
+            'f' => vec!['g'],
+            'g' => vec!['h'],
+            'h' => vec!['e'],
+            _ => vec![],
+        }
+    }
+
+    /// a to d is cheapest through b and c, and at the same cost straight from a to c.
+    fn weighted(state: &char) -> Vec<(char, u32)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 2), ('d', 10)],
+            'b' => vec![('c', 1), ('d', 5)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_finds_the_shortest_path() {
+        let search = bfs(['a'], edges, |&s| s == 'e');
+        assert_eq!(search.goal_cost(), Some(3));
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 4);
+        assert_eq!((path[0], path[3]), ('a', 'e'));
+        assert_eq!(search.cost(&'g'), Some(2));
+    }
+
+    #[test]
+    fn bfs_without_a_goal_walks_everything() {
+        let search = bfs(['a'], edges, |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.costs().count(), 8);
+        assert_eq!(search.cost(&'e'), Some(3));
+    }
+
+    #[test]
+    fn bfs_from_several_starts() {
+        let search = bfs(['a', 'h'], edges, |&s| s == 'e');
+        assert_eq!(search.goal_cost(), Some(1));
+        assert_eq!(search.goal_path(), Some(vec!['h', 'e']));
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        let search = dijkstra(['a'], weighted, |&s| s == 'd');
+        assert_eq!(search.goal_cost(), Some(3));
+        assert_eq!(search.cost(&'c'), Some(2));
+        let path = search.goal_path().unwrap();
+        assert!(path == vec!['a', 'b', 'c', 'd'] || path == vec!['a', 'c', 'd']);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let search = dijkstra(['b'], weighted, |&s| s == 'a');
+        assert_eq!(search.goal_cost(), None);
+        assert_eq!(search.goal_path(), None);
+        assert!(search.on_goal_paths().is_empty());
+    }
+
+    #[test]
+    fn on_goal_paths_has_every_cheapest_path() {
+        let search = bfs(['a'], edges, |&s| s == 'e');
+        let on_paths = search.on_goal_paths();
+        assert_eq!(
+            on_paths,
+            ['a', 'b', 'c', 'd', 'e'].iter().copied().collect()
+        );
+
+        let search = dijkstra(['a'], weighted, |&s| s == 'd');
+        let on_paths = search.on_goal_paths();
+        assert_eq!(on_paths, ['a', 'b', 'c', 'd'].iter().copied().collect());
+    }
+
+    /// A 5x5 room with a wall down the middle, open at the bottom.
+    fn room(&(x, y): &(i32, i32)) -> Vec<((i32, i32), u32)> {
+        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+            .iter()
+            .copied()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y) && !(x == 2 && y < 4))
+            .map(|to| (to, 1))
+            .collect()
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i32, i32)| ((x - goal.0).abs() + (y - goal.1).abs()) as u32;
+        let led = astar([(0, 0)], room, manhattan, |&s| s == goal);
+        let plain = dijkstra([(0, 0)], room, |&s| s == goal);
+        assert_eq!(led.goal_cost(), Some(12));
+        assert_eq!(plain.goal_cost(), Some(12));
+        assert_eq!(led.goal_path().unwrap().len(), 13);
+        assert_eq!(led.on_goal_paths(), plain.on_goal_paths());
+        // led straight to the goal, it never looks at most of the room
+        assert!(led.costs().count() <= plain.costs().count());
+    }
+}
@@ -0,0 +1,95 @@
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// With `--step`, simulations stop at every [`tick`] until told how far to run.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STEPPER: Mutex<Stepper> = Mutex::new(Stepper {
+    tick: 0,
+    until: Until::Tick(0),
+});
+
+const HELP: &str = "[Enter] next tick, N: N ticks, @N: to tick N, /text: until the status has text, c: to the end, q: quit";
+
+/// Where the simulation runs to before stopping again.
+enum Until {
+    Tick(u64),
+    Matching(String),
+    End,
+}
+
+struct Stepper {
+    tick: u64,
+    until: Until,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Starts counting the ticks again, for the next question or example.
+pub fn reset() {
+    if ENABLED.load(Ordering::Relaxed) {
+        *STEPPER.lock().unwrap() = Stepper {
+            tick: 0,
+            until: Until::Tick(0),
+        };
+    }
+}
+
+/// One tick of a simulation. With `--step`, when it's time to stop, it clears the screen, shows
+/// `draw()` with the `status()` line and waits for a command on standard input.
+pub fn tick(status: impl FnOnce() -> String, draw: impl FnOnce() -> String) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut stepper = STEPPER.lock().unwrap();
+    stepper.tick += 1;
+    let status = match &stepper.until {
+        Until::End => return,
+        Until::Tick(n) if stepper.tick < *n => return,
+        Until::Tick(_) => status(),
+        Until::Matching(text) => {
+            let status = status();
+            if !status.contains(text.as_str()) {
+                return;
+            }
+            status
+        }
+    };
+    eprintln!("\x1b[2J\x1b[1;1H{}", draw().trim_end());
+    eprintln!("tick {}: {}", stepper.tick, status);
+    stepper.until = prompt(stepper.tick);
+}
+
+fn prompt(tick: u64) -> Until {
+    loop {
+        eprintln!("{}", HELP);
+        eprint!("> ");
+        let _ = io::stderr().flush();
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return Until::End;
+        }
+        let command = line.trim();
+        let until = match command {
+            "" => Some(Until::Tick(tick + 1)),
+            "c" => Some(Until::End),
+            "q" => process::exit(0),
+            _ => {
+                if let Some(text) = command.strip_prefix('/') {
+                    Some(Until::Matching(text.to_string()))
+                } else if let Some(n) = command.strip_prefix('@') {
+                    n.parse().ok().map(Until::Tick)
+                } else {
+                    command.parse::<u64>().ok().map(|n| Until::Tick(tick + n))
+                }
+            }
+        };
+        match until {
+            Some(until) => return until,
+            None => eprintln!("Unknown command {}", command),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use crate::common::config::{Config, CONFIG_FILE, SESSION_ENV};
+use crate::common::day::Question;
+use crate::common::error::Error;
+use crate::common::fetch::{http_error, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Sent too soon after a wrong answer, the guess wasn't checked.
+    TooSoon,
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    /// Reads the verdict out of the page the site answers with.
+    pub fn from_response(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Outcome::Correct
+        } else if body.contains("You gave an answer too recently") {
+            Outcome::TooSoon
+        } else if body.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else if body.contains("That's not the right answer") {
+            if body.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if body.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else {
+            Outcome::Unknown
+        }
+    }
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::TooSoon => "too soon, wait before trying again",
+            Outcome::WrongLevel => "already solved, or not unlocked yet",
+            Outcome::Unknown => "can't make sense of the response",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Guess {
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Guesses {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub part1: Vec<Guess>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub part2: Vec<Guess>,
+}
+
+impl Guesses {
+    pub fn get(&self, question: Question) -> &Vec<Guess> {
+        match question {
+            Question::First => &self.part1,
+            Question::Second => &self.part2,
+        }
+    }
+    fn get_mut(&mut self, question: Question) -> &mut Vec<Guess> {
+        match question {
+            Question::First => &mut self.part1,
+            Question::Second => &mut self.part2,
+        }
+    }
+}
+
+/// Every answer sent so far, stored as
+/// `{"2024": {"1": {"part1": [{"answer": "...", "outcome": "too_high"}]}}}`.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct GuessLog(BTreeMap<u16, BTreeMap<u8, Guesses>>);
+
+impl GuessLog {
+    /// A missing file is an empty log.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(GuessLog::default()),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let s = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, s + "\n").map_err(|e| format!("{}: {}", path.display(), e))
+    }
+    pub fn get(&self, year: u16, day: u8, question: Question) -> &[Guess] {
+        self.0
+            .get(&year)
+            .and_then(|days| days.get(&day))
+            .map(|guesses| guesses.get(question).as_slice())
+            .unwrap_or(&[])
+    }
+    pub fn add(&mut self, year: u16, day: u8, question: Question, guess: Guess) {
+        self.0
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+            .get_mut(question)
+            .push(guess);
+    }
+}
+
+/// Why `answer` is known to be wrong from the earlier guesses, `None` if it's worth a try.
+pub fn known_wrong(guesses: &[Guess], answer: &str) -> Option<String> {
+    let number = answer.parse::<i128>().ok();
+    for guess in guesses {
+        let earlier = guess.answer.parse::<i128>().ok();
+        match (guess.outcome, number, earlier) {
+            (Outcome::Correct, _, _) => {
+                return Some(format!("already solved with {}", guess.answer));
+            }
+            (outcome, _, _) if outcome.is_wrong() && guess.answer == answer => {
+                return Some(format!("{} was already {}", answer, outcome));
+            }
+            (Outcome::TooHigh, Some(n), Some(high)) if n >= high => {
+                return Some(format!("{} was too high, {} is not lower", high, n));
+            }
+            (Outcome::TooLow, Some(n), Some(low)) if n <= low => {
+                return Some(format!("{} was too low, {} is not higher", low, n));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Posts the answer to the puzzle page and reads the verdict.
+pub fn submit_answer(
+    config: &Config,
+    year: u16,
+    day: u8,
+    question: Question,
+    answer: &str,
+) -> Result<Outcome, Error> {
+    let session = config.session().ok_or_else(|| {
+        Error::new(format!(
+            "set {} or \"session\" in {} to submit",
+            SESSION_ENV, CONFIG_FILE
+        ))
+    })?;
+    let url = format!(
+        "{}/{}/day/{}/answer",
+        config.base_url().trim_end_matches('/'),
+        year,
+        day
+    );
+    let level = match question {
+        Question::First => "1",
+        Question::Second => "2",
+    };
+    let body = ureq::post(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", level), ("answer", answer)])
+        .map_err(|e| http_error(&url, e))?
+        .into_string()
+        .map_err(|e| Error::new(format!("{}: {}", url, e)))?;
+    Ok(Outcome::from_response(&body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    // Cut down from the pages the site answers with.
+    const RIGHT: &str =
+        "<article><p>That's the right answer!  You are <span class=\"day-success\">one \
+        gold star</span> closer to finding the chief.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, make sure \
+        you're using the full input data. Please wait one minute before trying again. \
+        [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  \
+        If you're stuck, make sure you're using the full input data. Please wait one minute \
+        before trying again.</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  \
+        Please wait one minute before trying again.</p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 37s left to wait.</p></article>";
+    const SOLVED: &str = "<article><p>You don't seem to be solving the right level.  Did you \
+        already complete it? [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article>";
+
+    fn guess(answer: &str, outcome: Outcome) -> Guess {
+        Guess {
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn reads_the_verdict() {
+        let table = [
+            (RIGHT, Outcome::Correct),
+            (WRONG, Outcome::Wrong),
+            (TOO_HIGH, Outcome::TooHigh),
+            (TOO_LOW, Outcome::TooLow),
+            (TOO_SOON, Outcome::TooSoon),
+            (SOLVED, Outcome::WrongLevel),
+            (
+                "<html>Puzzle inputs differ by user.</html>",
+                Outcome::Unknown,
+            ),
+        ];
+        for (body, outcome) in table {
+            assert_eq!(Outcome::from_response(body), outcome, "{}", body);
+        }
+    }
+
+    #[test]
+    fn knows_wrong_answers() {
+        let guesses = [
+            guess("abc", Outcome::Wrong),
+            guess("100", Outcome::TooHigh),
+            guess("20", Outcome::TooLow),
+            guess("50", Outcome::TooSoon),
+        ];
+        assert!(known_wrong(&guesses, "abc").is_some());
+        assert!(known_wrong(&guesses, "100").is_some());
+        assert!(known_wrong(&guesses, "150").is_some());
+        assert!(known_wrong(&guesses, "20").is_some());
+        assert!(known_wrong(&guesses, "-5").is_some());
+        // Never checked, it was sent too soon.
+        assert_eq!(known_wrong(&guesses, "50"), None);
+        assert_eq!(known_wrong(&guesses, "21"), None);
+        assert_eq!(known_wrong(&guesses, "99"), None);
+        assert_eq!(known_wrong(&guesses, "abd"), None);
+        assert_eq!(known_wrong(&[], "1"), None);
+    }
+
+    #[test]
+    fn knows_it_is_solved() {
+        let guesses = [guess("7", Outcome::TooLow), guess("42", Outcome::Correct)];
+        assert_eq!(
+            known_wrong(&guesses, "43"),
+            Some("already solved with 42".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_the_guesses() {
+        let path = std::env::temp_dir().join(format!("aoc-guesses-{}.json", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut log = GuessLog::load(&path).unwrap();
+        assert!(log.get(2024, 1, Question::First).is_empty());
+        log.add(2024, 1, Question::First, guess("100", Outcome::TooHigh));
+        log.add(2024, 1, Question::Second, guess("5", Outcome::Correct));
+        log.save(&path).unwrap();
+
+        let log = GuessLog::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let part1 = log.get(2024, 1, Question::First);
+        assert_eq!(part1.len(), 1);
+        assert_eq!(part1[0].outcome, Outcome::TooHigh);
+        assert!(known_wrong(part1, "120").is_some());
+        assert_eq!(log.get(2024, 1, Question::Second)[0].answer, "5");
+        assert!(log.get(2024, 2, Question::First).is_empty());
+    }
+
+    #[test]
+    fn rejects_a_broken_log() {
+        let path = std::env::temp_dir().join(format!("aoc-broken-guesses-{}.json", process::id()));
+        fs::write(&path, "{not json").unwrap();
+        let loaded = GuessLog::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(loaded.is_err());
+    }
+}
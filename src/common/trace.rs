@@ -0,0 +1,81 @@
+use crate::common::error::Error;
+use crate::common::runner::DayId;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+/// `-v` shows what [`debug!`] says, `-vv` what [`trace!`] says as well.
+pub const DEBUG: u8 = 1;
+pub const TRACE: u8 = 2;
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+/// The days being traced, all of them when empty.
+static DAYS: Mutex<Vec<DayId>> = Mutex::new(Vec::new());
+/// Where the traces and the diagnostics go, standard error when not set.
+static LOG: Mutex<Option<File>> = Mutex::new(None);
+
+/// Sets up tracing from the command line, `log` is a file to write to instead of standard error.
+pub fn init(level: u8, days: &[DayId], log: Option<&Path>) -> Result<(), Error> {
+    LEVEL.store(level, Ordering::Relaxed);
+    *DAYS.lock().unwrap() = days.to_vec();
+    if let Some(path) = log {
+        let file =
+            File::create(path).map_err(|e| Error::new(format!("{}: {}", path.display(), e)))?;
+        *LOG.lock().unwrap() = Some(file);
+    }
+    Ok(())
+}
+
+/// The day a module like `aoc::aoc2024::day16` solves.
+fn day_of(module: &str) -> Option<DayId> {
+    let mut segments = module.split("::");
+    let year = segments.find_map(|s| s.strip_prefix("aoc")?.parse().ok())?;
+    let day = segments.next()?.strip_prefix("day")?;
+    let day = day
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(day, |end| &day[..end])
+        .parse()
+        .ok()?;
+    Some(DayId { year, day })
+}
+
+/// Whether messages of `level` from `module` are shown.
+pub fn enabled(level: u8, module: &str) -> bool {
+    if LEVEL.load(Ordering::Relaxed) < level {
+        return false;
+    }
+    let days = DAYS.lock().unwrap();
+    days.is_empty() || day_of(module).is_some_and(|day| days.contains(&day))
+}
+
+/// Writes to the log file or standard error, whatever the level.
+pub fn write(args: fmt::Arguments) {
+    let mut log = LOG.lock().unwrap();
+    let _ = match log.as_mut() {
+        Some(file) => file.write_fmt(args),
+        None => io::stderr().write_fmt(args),
+    };
+}
+
+/// Like `println!`, but only with `-v`, and only for the traced days.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::common::trace::enabled($crate::common::trace::DEBUG, module_path!()) {
+            $crate::common::trace::write(format_args!("{}\n", format_args!($($arg)*)));
+        }
+    };
+}
+
+/// Like `println!`, but only with `-vv`, and only for the traced days.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::common::trace::enabled($crate::common::trace::TRACE, module_path!()) {
+            $crate::common::trace::write(format_args!("{}\n", format_args!($($arg)*)));
+        }
+    };
+}
@@ -0,0 +1,215 @@
+use crate::common::day::{Params, Question};
+use crate::common::error::Error;
+use crate::common::registry::Registration;
+use crate::common::runner::{self, part, print_rows, Run};
+use crate::common::scaffold::day_source;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// One run of a watched day, as the child process reports it back.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Sample {
+    pub part: u8,
+    /// The name of the example, `None` for the real input.
+    pub case: Option<String>,
+    pub status: String,
+    /// The plain answer, or the error.
+    pub answer: String,
+    pub ms: f64,
+}
+
+impl From<&Run> for Sample {
+    fn from(r: &Run) -> Self {
+        Sample {
+            part: part(r.question),
+            case: r.case.clone(),
+            status: r.status().to_string(),
+            answer: match &r.answer {
+                Ok(answer) => answer.value.to_plain_string(),
+                Err(err) => err.to_string(),
+            },
+            ms: (r.parse_time + r.elapsed).as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Runs the examples and the input once, what the watcher starts a fresh binary for.
+pub fn run_once(day: &Registration, input: &Path, overrides: &Params) -> Vec<Sample> {
+    let questions = [Question::First, Question::Second];
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut runs = runner::run_examples(day.solution, day.year, day.day, &questions, overrides);
+    let mut samples = match runner::read_input(input) {
+        Ok(input) => {
+            runs.extend(runner::run(
+                day.solution,
+                day.year,
+                day.day,
+                &input,
+                &questions,
+                overrides,
+            ));
+            Vec::new()
+        }
+        Err(err) => questions
+            .iter()
+            .map(|&question| Sample {
+                part: part(question),
+                case: None,
+                status: "fail".to_string(),
+                answer: err.to_string(),
+                ms: 0.0,
+            })
+            .collect_vec(),
+    };
+    samples.splice(0..0, runs.iter().map(Sample::from));
+    panic::set_hook(default_hook);
+    samples
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn answer_cell(answer: &str) -> String {
+    if answer.contains('\n') {
+        "(grid)".to_string()
+    } else {
+        answer.to_string()
+    }
+}
+
+/// Prints the samples, with how the answer and the time changed since the `previous` ones.
+pub fn print_diff(previous: &[Sample], samples: &[Sample]) {
+    let rows = samples
+        .iter()
+        .map(|s| {
+            let before = previous
+                .iter()
+                .find(|p| p.part == s.part && p.case == s.case);
+            let (change, was) = match before {
+                Some(p) => (
+                    format!(
+                        "{:+.3}ms ({:+.0}%)",
+                        s.ms - p.ms,
+                        (s.ms - p.ms) / p.ms.max(0.001) * 100.0
+                    ),
+                    if p.answer == s.answer {
+                        String::new()
+                    } else if s.answer.contains('\n') {
+                        "(another grid)".to_string()
+                    } else {
+                        answer_cell(&p.answer)
+                    },
+                ),
+                None => (String::new(), String::new()),
+            };
+            [
+                s.part.to_string(),
+                s.case.clone().unwrap_or_else(|| "input".to_string()),
+                s.status.clone(),
+                answer_cell(&s.answer),
+                format!("{:.3}ms", s.ms),
+                change,
+                was,
+            ]
+        })
+        .collect_vec();
+    print_rows(
+        &["part", "case", "status", "answer", "time", "change", "was"],
+        &rows,
+    );
+}
+
+fn rebuild(manifest_dir: &Path) -> Result<(), Error> {
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo.args(["build", "--quiet"]).current_dir(manifest_dir);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    // The warnings of every day would bury the results, only a failed build gets shown.
+    match cargo.output() {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(Error::new(
+            String::from_utf8_lossy(&output.stderr).trim_end(),
+        )),
+        Err(e) => Err(Error::new(format!("cargo build: {}", e))),
+    }
+}
+
+/// Runs `aoc watch <year> <day> --once` with `exe`, the binary on disk, which is the rebuilt one
+/// after the source changed.
+fn run_child(
+    exe: &Path,
+    day: &Registration,
+    input: &Path,
+    params: &[(String, String)],
+) -> Result<Vec<Sample>, Error> {
+    let mut child = Command::new(exe);
+    child
+        .arg("watch")
+        .arg(day.year.to_string())
+        .arg(day.day.to_string())
+        .arg("--once")
+        .arg("-f")
+        .arg(input);
+    for (key, value) in params {
+        child.arg("-p").arg(format!("{}={}", key, value));
+    }
+    let output = child
+        .output()
+        .map_err(|e| Error::new(format!("{}: {}", exe.display(), e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::new(stderr.trim()));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| Error::new(e.to_string()))
+}
+
+/// Polls the day's source and input, rebuilding when the source changed, and re-runs the day on
+/// every change. Never returns, stop it with Ctrl-C.
+pub fn watch(
+    manifest_dir: &Path,
+    day: &Registration,
+    input: &Path,
+    params: &[(String, String)],
+) -> ! {
+    // Asked for before rebuilding, as afterwards Linux reports the replaced binary as deleted.
+    let exe = env::current_exe().unwrap_or_else(|_| PathBuf::from("aoc"));
+    let source = day_source(&manifest_dir.join("src"), day.year, day.day);
+    let mut seen = None;
+    let mut previous = Vec::new();
+    loop {
+        let stamps = (modified(&source), modified(input));
+        if seen == Some(stamps) {
+            thread::sleep(POLL);
+            continue;
+        }
+        let source_changed = seen.is_some_and(|(s, _)| s != stamps.0);
+        seen = Some(stamps);
+        let built = if source_changed {
+            println!("{} changed, rebuilding", source.display());
+            rebuild(manifest_dir)
+        } else {
+            Ok(())
+        };
+        match built.and_then(|_| run_child(&exe, day, input, params)) {
+            Ok(samples) => {
+                println!("--- {} Day {}: {} ---", day.year, day.day, day.title);
+                print_diff(&previous, &samples);
+                previous = samples;
+            }
+            Err(err) => println!("{}", err),
+        }
+        println!("Watching {} and {}", source.display(), input.display());
+    }
+}
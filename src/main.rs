@@ -1,11 +1,24 @@
-#[allow(unused_imports)]
-use crate::common::day::Day;
-use clap::Parser;
-use common::day::Question;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use common::answers::AnswerStore;
+use common::bench;
+use common::catalogue;
+use common::config::{self, Config};
+use common::day::{Params, Question, Value};
+use common::error::Error;
+use common::fetch;
+use common::registry::{self, YEARS};
+use common::runner;
+use common::runner::{DayId, Pool};
+use common::scaffold;
+use common::step;
+use common::submit::{self, Guess, GuessLog, Outcome};
+use common::trace;
+use common::watch;
 use itertools::Itertools;
-use phf::phf_map;
 use std::any::Any;
-use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
 
 mod aoc2022;
 mod aoc2023;
@@ -16,120 +29,225 @@ mod hackerrank;
 #[derive(Parser)]
 #[command(name = "aoc", author, version, about, long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
     #[arg(help = "[defaults to the last day]")]
     day: Option<u8>,
-    #[arg(help = "[defaults to the last year]")]
+    #[arg(value_parser = runner::parse_year, help = "[defaults to the last year]")]
     year: Option<u16>,
     #[arg(short, value_parser=clap::value_parser!(u8).range(1..3), default_value_t=1)]
     question: u8,
-    #[arg(short, long, default_value = "input.txt")]
-    file_name: String,
-    #[arg(short, long)]
+    #[arg(
+        short,
+        long,
+        help = "Input file, - reads stdin [defaults to inputs/<year>/dayNN.txt]"
+    )]
+    file_name: Option<String>,
+    #[arg(short, long, help = "Run the examples and check their answers")]
     test: bool,
+    #[arg(short, long = "param", value_parser = runner::parse_param, help = "Puzzle parameter, e.g. -p steps=64")]
+    params: Vec<(String, String)>,
+    #[arg(short, long, help = "Run both questions and print a summary table")]
+    both: bool,
+    #[arg(
+        long,
+        help = "Stop at every tick of a simulation and wait for a command on stdin"
+    )]
+    step: bool,
+    #[arg(
+        short,
+        long,
+        help = "Run every registered day, inputs from inputs/<year>/dayNN.txt"
+    )]
+    all: bool,
+    #[arg(
+        long = "year",
+        value_parser = runner::parse_year,
+        help = "Only run or list this year with --all or --list-days"
+    )]
+    only_year: Option<u16>,
+    #[arg(long, value_parser = runner::parse_day_id, help = "First day to run with --all, e.g. 2023-5")]
+    from: Option<DayId>,
+    #[arg(long, value_parser = runner::parse_day_id, help = "Last day to run with --all, e.g. 2024-10")]
+    to: Option<DayId>,
 
-    #[arg(short, long)]
+    #[arg(
+        short,
+        long,
+        help = "List the days with their titles, status and recorded answers"
+    )]
     list_days: bool,
+    #[arg(long, help = "Only list the days with this tag, e.g. grid")]
+    tag: Option<String>,
+
+    #[arg(short, long, action = clap::ArgAction::Count, global = true, help = "Show the debug output of the days, -vv for the traces too")]
+    verbose: u8,
+    #[arg(long = "trace-day", value_parser = runner::parse_day_id, global = true, help = "Only show the debug output of this day, e.g. 2024-16")]
+    trace_days: Vec<DayId>,
+    #[arg(
+        long,
+        global = true,
+        help = "Write the debug output and the diagnostics to this file instead of stderr"
+    )]
+    log_file: Option<String>,
+    #[arg(long, value_enum, global = true, default_value_t = Format::Plain)]
+    format: Format,
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Threads to run --all and verify on [defaults to the number of cores]"
+    )]
+    jobs: Option<usize>,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 120,
+        help = "Seconds a question may run with --all or verify before it's reported as timed out"
+    )]
+    timeout: u64,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 8,
+        help = "Timed-out questions after which --all and verify give up on the rest"
+    )]
+    max_timeouts: usize,
+}
+
+#[derive(ValueEnum, Copy, Clone, Eq, PartialEq)]
+enum Format {
+    /// Tables to read
+    Plain,
+    /// JSON for scripts, a record for every run
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Re-run solutions on inputs/<year>/dayNN.txt and compare them with the stored answers
+    Verify {
+        #[arg(value_parser = runner::parse_year)]
+        year: Option<u16>,
+        day: Option<u8>,
+        #[arg(
+            short,
+            long,
+            help = "[defaults to the answers of the config, answers.json]"
+        )]
+        answers: Option<String>,
+        #[arg(short, long, help = "Store the answers that aren't recorded yet")]
+        record: bool,
+    },
+    /// Time the solved questions of a day over repeated runs
+    Bench(BenchArgs),
+    /// Download the input of a day into the inputs directory, unless it's already there
+    Fetch {
+        #[arg(value_parser = runner::parse_year)]
+        year: u16,
+        day: u8,
+    },
+    /// Start a new day from the template
+    New {
+        #[arg(value_parser = runner::parse_year)]
+        year: u16,
+        day: u8,
+        #[arg(short, long, default_value = "")]
+        title: String,
+        #[arg(short, long, help = "File with the example input")]
+        example: Option<String>,
+    },
+    /// Solve a question and send the answer, unless an earlier guess shows it's wrong
+    Submit {
+        #[arg(value_parser = runner::parse_year)]
+        year: u16,
+        day: u8,
+        #[arg(value_parser=clap::value_parser!(u8).range(1..3))]
+        part: u8,
+        #[arg(short, long, default_value = "guesses.json")]
+        log: String,
+        #[arg(short, long = "param", value_parser = runner::parse_param, help = "Puzzle parameter, e.g. -p steps=64")]
+        params: Vec<(String, String)>,
+    },
+    /// Re-run a day's examples and input whenever its source or input changes
+    Watch(WatchArgs),
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[arg(value_parser = runner::parse_year)]
+    year: u16,
+    day: u8,
+    #[arg(
+        short,
+        long,
+        help = "Input file, - reads stdin [defaults to inputs/<year>/dayNN.txt]"
+    )]
+    file_name: Option<String>,
+    #[arg(short = 'n', long, default_value_t = 10)]
+    runs: usize,
+    #[arg(short, long, default_value_t = 1)]
+    warmup: usize,
+    #[arg(short, long = "param", value_parser = runner::parse_param, help = "Puzzle parameter, e.g. -p steps=64")]
+    params: Vec<(String, String)>,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[arg(value_parser = runner::parse_year)]
+    year: u16,
+    day: u8,
+    #[arg(short, long, help = "Input file [defaults to inputs/<year>/dayNN.txt]")]
+    file_name: Option<String>,
+    #[arg(short, long = "param", value_parser = runner::parse_param, help = "Puzzle parameter, e.g. -p steps=64")]
+    params: Vec<(String, String)>,
+    /// Run once and print the results as JSON, what the watcher runs after each rebuild
+    #[arg(long, hide = true)]
+    once: bool,
+}
+
+fn must_read_file(path: &Path) -> String {
+    match runner::read_input(path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(2);
+        }
+    }
 }
 
-fn must_read_file(filename: &str) -> String {
-    fs::read_to_string(filename).expect("reading in file")
-}
-
-const DAYS_2025: phf::Map<u8, &'static dyn Day> = phf_map! {
-    1u8 => &aoc2025::day1::Day1,
-    3u8 => &aoc2025::day3::Day3,
-    6u8 => &aoc2025::day6::Day6,
-    7u8 => &aoc2025::day7::Day7,
-    8u8 => &aoc2025::day8::Day8,
-    9u8 => &aoc2025::day9::Day9,
-};
-
-const DAYS_2024: phf::Map<u8, &'static dyn Day> = phf_map! {
-    1u8 => &aoc2024::day1::Day1,
-    2u8 => &aoc2024::day2::Day2,
-    3u8 => &aoc2024::day3::Day3,
-    4u8 => &aoc2024::day4::Day4,
-    5u8 => &aoc2024::day5::Day5,
-    6u8 => &aoc2024::day6::Day6,
-    7u8 => &aoc2024::day7::Day7,
-    8u8 => &aoc2024::day8::Day8,
-    9u8 => &aoc2024::day9::Day9,
-    10u8 => &aoc2024::day10::Day10,
-    11u8 => &aoc2024::day11::Day11,
-    12u8 => &aoc2024::day12::Day12,
-    13u8 => &aoc2024::day13::Day13,
-    14u8 => &aoc2024::day14::Day14,
-    15u8 => &aoc2024::day15::Day15,
-    16u8 => &aoc2024::day16::Day16,
-    17u8 => &aoc2024::day17::Day17,
-    18u8 => &aoc2024::day18::Day18,
-    19u8 => &aoc2024::day19::Day19,
-    20u8 => &aoc2024::day20::Day20,
-    21u8 => &aoc2024::day21::Day21,
-    22u8 => &aoc2024::day22::Day22,
-    23u8 => &aoc2024::day23::Day23,
-    24u8 => &aoc2024::day24::Day24,
-    25u8 => &aoc2024::day25::Day25,
-};
-
-const DAYS_2023: phf::Map<u8, &'static dyn Day> = phf_map! {
-    1u8 => &aoc2023::day1::Day1,
-    2u8 => &aoc2023::day2::Day2,
-    3u8 => &aoc2023::day3::Day3,
-    4u8 => &aoc2023::day4::Day4,
-    5u8 => &aoc2023::day5::Day5,
-    6u8 => &aoc2023::day6::Day6,
-    7u8 => &aoc2023::day7::Day7,
-    8u8 => &aoc2023::day8::Day8,
-    9u8 => &aoc2023::day9::Day9,
-    10u8 => &aoc2023::day10::Day10,
-    11u8 => &aoc2023::day11::Day11,
-    12u8 => &aoc2023::day12::Day12,
-    13u8 => &aoc2023::day13::Day13,
-    14u8 => &aoc2023::day14::Day14,
-    15u8 => &aoc2023::day15::Day15,
-    16u8 => &aoc2023::day16::Day16,
-    17u8 => &aoc2023::day17::Day17,
-    18u8 => &aoc2023::day18::Day18,
-    19u8 => &aoc2023::day19::Day19,
-    20u8 => &aoc2023::day20::Day20,
-    21u8 => &aoc2023::day21::Day21,
-    22u8 => &aoc2023::day22::Day22,
-    // 23u8 => &aoc2023::day23::Day23,
-    // 24u8 => &aoc2023::day24::Day24,
-    // 25u8 => &aoc2023::day25::Day25,
-};
-
-const DAYS_2022: phf::Map<u8, &'static dyn Day> = phf_map! {
-    1u8 => &aoc2022::day1::Day1,
-    2u8 => &aoc2022::day2::Day2,
-    3u8 => &aoc2022::day3::Day3,
-    4u8 => &aoc2022::day4::Day4,
-    5u8 => &aoc2022::day5::Day5,
-    6u8 => &aoc2022::day6::Day6,
-    7u8 => &aoc2022::day7::Day7,
-    8u8 => &aoc2022::day8::Day8,
-    9u8 => &aoc2022::day9::Day9,
-    10u8 => &aoc2022::day10::Day10,
-    11u8 => &aoc2022::day11::Day11,
-    12u8 => &aoc2022::day12::Day12,
-    13u8 => &aoc2022::day13::Day13,
-    14u8 => &aoc2022::day14::Day14,
-    15u8 => &aoc2022::day15::Day15,
-    16u8 => &aoc2022::day16::Day16,
-    17u8 => &aoc2022::day17::Day17,
-    18u8 => &aoc2022::day18::Day18,
-    19u8 => &aoc2022::day19::Day19,
-    20u8 => &aoc2022::day20::Day20,
-    21u8 => &aoc2022::day21::Solution,
-};
-
-const YEARS: phf::Map<u16, phf::Map<u8, &'static dyn Day>> = phf_map! {
-    2022u16 => DAYS_2022,
-    2023u16 => DAYS_2023,
-    2024u16 => DAYS_2024,
-    2025u16 => DAYS_2025,
-};
+fn load_config() -> Config {
+    match Config::load(Path::new(config::CONFIG_FILE)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Can't read config: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+/// Reads the `-f` file if given, else the day's input from the inputs directory, fetching it
+/// first if it's not there yet.
+fn read_day_input(file_name: &Option<String>, year: u16, day: u8) -> String {
+    if let Some(file_name) = file_name {
+        return must_read_file(Path::new(file_name));
+    }
+    let config = load_config();
+    let path = runner::input_path(&config.inputs_root(), year, day);
+    match fetch::fetch_input(&config, year, day, &path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(2);
+        }
+    }
+}
+
+/// Prints the error as one line and exits, instead of panicking with a backtrace.
+fn exit_with(err: &Error) -> ! {
+    eprintln!("Error: {}", err);
+    process::exit(1);
+}
 
 fn print_available_days() {
     println!(
@@ -141,21 +259,428 @@ fn print_available_days() {
     );
 }
 
+fn list_days(cli: &Cli) {
+    let store = match AnswerStore::load(&load_config().answers_path()) {
+        Ok(store) => store,
+        Err(e) => {
+            println!("Can't read answers: {}", e);
+            process::exit(2);
+        }
+    };
+    let entries = catalogue::catalogue(&YEARS, &store, cli.only_year, cli.tag.as_deref());
+    match cli.format {
+        Format::Plain => catalogue::print_catalogue(&entries),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&entries).unwrap()),
+    }
+}
+
+fn pool(cli: &Cli) -> Pool {
+    let default = Pool::default();
+    Pool {
+        threads: cli.jobs.unwrap_or(default.threads),
+        timeout: Duration::from_secs(cli.timeout),
+        max_timeouts: cli.max_timeouts,
+    }
+}
+
+fn run_all(cli: &Cli) {
+    let from = match cli.only_year {
+        Some(year) => DayId { year, day: 0 },
+        None => cli.from.unwrap_or(DayId { year: 0, day: 0 }),
+    };
+    let to = match cli.only_year {
+        Some(year) => DayId { year, day: u8::MAX },
+        None => cli.to.unwrap_or(DayId {
+            year: u16::MAX,
+            day: u8::MAX,
+        }),
+    };
+    // Each run is printed as it comes, a batch that's killed halfway still shows how far it got.
+    let json = cli.format == Format::Json;
+    if json {
+        print!("[");
+    } else {
+        runner::print_run_header();
+    }
+    let mut first = true;
+    let batch = runner::run_batch(
+        &YEARS,
+        &load_config().inputs_root(),
+        from,
+        to,
+        cli.test,
+        pool(cli),
+        |r| {
+            if json {
+                let record = serde_json::to_string_pretty(&r.record()).unwrap();
+                println!(
+                    "{}\n  {}",
+                    if first { "" } else { "," },
+                    record.replace('\n', "\n  ")
+                );
+            } else {
+                runner::print_run(r);
+            }
+            first = false;
+        },
+    );
+    let failed = batch.runs.iter().filter(|r| r.failed()).count();
+    if json {
+        println!("]");
+        print_skipped(&batch.skipped, cli.format);
+    } else {
+        runner::print_grids(&batch.runs);
+        print_skipped(&batch.skipped, cli.format);
+        println!(
+            "{} passed, {} failed, {} skipped",
+            batch.runs.len() - failed,
+            failed,
+            batch.skipped.len()
+        );
+    }
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn verify(
+    year: Option<u16>,
+    day: Option<u8>,
+    answers: &Option<String>,
+    record: bool,
+    format: Format,
+    pool: Pool,
+) {
+    let config = load_config();
+    let path = match answers {
+        Some(answers) => PathBuf::from(answers),
+        None => config.answers_path(),
+    };
+    let mut store = match AnswerStore::load(&path) {
+        Ok(store) => store,
+        Err(e) => {
+            println!("Can't read answers: {}", e);
+            process::exit(2);
+        }
+    };
+    let from = DayId {
+        year: year.unwrap_or(0),
+        day: day.unwrap_or(0),
+    };
+    let to = DayId {
+        year: year.unwrap_or(u16::MAX),
+        day: day.unwrap_or(u8::MAX),
+    };
+    let batch = runner::run_batch(&YEARS, &config.inputs_root(), from, to, false, pool, |_| {});
+    let verdicts = runner::verify(&batch.runs, &mut store, record);
+    match format {
+        Format::Plain => runner::print_verdicts(&batch.runs, &verdicts),
+        Format::Json => print_records(
+            &batch
+                .runs
+                .iter()
+                .zip(verdicts.iter())
+                .map(|(r, verdict)| runner::Record {
+                    verdict: Some(verdict.name()),
+                    ..r.record()
+                })
+                .collect_vec(),
+        ),
+    }
+    if record {
+        if let Err(e) = store.save(&path) {
+            println!("Can't save answers: {}", e);
+            process::exit(2);
+        }
+    }
+    let regressions = verdicts.iter().filter(|v| v.is_regression()).count();
+    if format == Format::Json {
+        print_skipped(&batch.skipped, format);
+    } else {
+        println!(
+            "{} checked, {} regressions, {} skipped",
+            verdicts.len(),
+            regressions,
+            batch.skipped.len()
+        );
+    }
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+fn run_bench(args: &BenchArgs, format: Format) {
+    let year = args.year;
+    let day = match registry::find_day(year, args.day) {
+        Some(day) => day,
+        None => {
+            print!("Non existent day!");
+            print_available_days();
+            process::exit(2);
+        }
+    };
+    let input = read_day_input(&args.file_name, year, args.day);
+    let overrides = overrides(&args.params);
+    let stats = match bench::bench_day(day, &input, &overrides, args.warmup, args.runs) {
+        Ok(stats) => stats,
+        Err(err) => exit_with(&err),
+    };
+    match format {
+        Format::Plain => bench::print_stats(&stats),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&stats).unwrap()),
+    }
+}
+
+fn fetch(year: u16, day: u8) {
+    let config = load_config();
+    let path = runner::input_path(&config.inputs_root(), year, day);
+    match fetch::fetch_input(&config, year, day, &path) {
+        Ok(input) => println!("{}: {} lines", path.display(), input.lines().count()),
+        Err(err) => exit_with(&err),
+    }
+}
+
+fn new_day(year: u16, day: u8, title: &str, example: &Option<String>) {
+    let example = example.as_ref().map(|f| must_read_file(Path::new(f)));
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    match scaffold::new_day(&src, year, day, title, example.as_deref()) {
+        Ok(path) => println!(
+            "Created {}, try it with: cargo run -- {} {} -t",
+            path.display(),
+            day,
+            year
+        ),
+        Err(err) => exit_with(&err),
+    }
+}
+
+fn submit(year: u16, day_n: u8, part: u8, log: &str, params: &[(String, String)]) {
+    let question = if part == 1 {
+        Question::First
+    } else {
+        Question::Second
+    };
+    let day = match registry::find_day(year, day_n) {
+        Some(day) => day.solution,
+        None => {
+            print!("Non existent day!");
+            print_available_days();
+            process::exit(2);
+        }
+    };
+    let input = read_day_input(&None, year, day_n);
+    let overrides = overrides(params);
+    let params = day.params(question).merged(&overrides);
+    let answer = match runner::catch_quietly(|| day.question(&input, question, &params)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(err)) => exit_with(&err),
+        Err(panic) => exit_with(&Error::new(panic)),
+    };
+    if let Value::Grid(_) = answer.value {
+        println!("{}", answer);
+        exit_with(&Error::new("grid answers have to be typed in by hand"));
+    }
+    let answer = answer.value.to_plain_string();
+
+    let path = Path::new(log);
+    let mut guesses = match GuessLog::load(path) {
+        Ok(guesses) => guesses,
+        Err(e) => {
+            println!("Can't read guesses: {}", e);
+            process::exit(2);
+        }
+    };
+    if let Some(reason) = submit::known_wrong(guesses.get(year, day_n, question), &answer) {
+        println!("Not submitting {}: {}", answer, reason);
+        process::exit(1);
+    }
+    println!(
+        "Submitting {} for year {} day {}, {:?} question",
+        answer, year, day_n, question
+    );
+    let config = load_config();
+    let outcome = match submit::submit_answer(&config, year, day_n, question, &answer) {
+        Ok(outcome) => outcome,
+        Err(err) => exit_with(&err),
+    };
+    guesses.add(year, day_n, question, Guess { answer, outcome });
+    if let Err(e) = guesses.save(path) {
+        println!("Can't save guesses: {}", e);
+        process::exit(2);
+    }
+    println!("{}", outcome);
+    if outcome != Outcome::Correct {
+        process::exit(1);
+    }
+}
+
+fn run_watch(args: &WatchArgs, format: Format) {
+    let year = args.year;
+    let day = match registry::find_day(year, args.day) {
+        Some(day) => day,
+        None => {
+            print!("Non existent day!");
+            print_available_days();
+            process::exit(2);
+        }
+    };
+    if args.once {
+        let overrides = overrides(&args.params);
+        let path = Path::new(args.file_name.as_deref().unwrap_or("-"));
+        let samples = watch::run_once(day, path, &overrides);
+        println!("{}", serde_json::to_string(&samples).unwrap());
+        return;
+    }
+    // The changes are shown as a table each round, there's no record for them to go in.
+    if format == Format::Json {
+        exit_with(&Error::new(
+            "watch only prints tables, run it without --format json",
+        ));
+    }
+    let path = match &args.file_name {
+        Some(file_name) if file_name == "-" => exit_with(&Error::new("can't watch standard input")),
+        Some(file_name) => PathBuf::from(file_name),
+        None => {
+            let config = load_config();
+            let path = runner::input_path(&config.inputs_root(), year, args.day);
+            if let Err(err) = fetch::fetch_input(&config, year, args.day, &path) {
+                eprintln!("Error: {}", err);
+            }
+            path
+        }
+    };
+    watch::watch(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        day,
+        &path,
+        &args.params,
+    );
+}
+
+/// The `-p key=value` parameters, they override the day's own.
+fn overrides(params: &[(String, String)]) -> Params {
+    let mut overrides = Params::new();
+    for (key, value) in params.iter() {
+        overrides.set(key, value);
+    }
+    overrides
+}
+
+fn print_records(records: &[runner::Record]) {
+    println!("{}", serde_json::to_string_pretty(records).unwrap());
+}
+
+/// On stderr with `--format json`, to keep stdout parseable.
+fn print_skipped(skipped: &[(DayId, String)], format: Format) {
+    for (id, reason) in skipped.iter() {
+        let message = format!("Skipped {} day {}: {}", id.year, id.day, reason);
+        if format == Format::Json {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    }
+}
+
+fn print_runs(runs: &[runner::Run], format: Format) {
+    match format {
+        Format::Plain => runner::print_table(runs),
+        Format::Json => print_records(&runs.iter().map(|r| r.record()).collect_vec()),
+    }
+}
+
+fn print_diagnostics(runs: &[runner::Run]) {
+    for answer in runs.iter().filter_map(|r| r.answer.as_ref().ok()) {
+        for diagnostic in answer.diagnostics.iter() {
+            trace::write(format_args!("{}\n", diagnostic));
+        }
+    }
+}
+
 fn main() {
     let cli: Cli = Cli::parse();
+    // Naming a day to trace is asking for its output.
+    let level = if cli.trace_days.is_empty() {
+        cli.verbose
+    } else {
+        cli.verbose.max(trace::DEBUG)
+    };
+    if let Err(err) = trace::init(
+        level,
+        &cli.trace_days,
+        cli.log_file.as_deref().map(Path::new),
+    ) {
+        exit_with(&err);
+    }
+
+    match &cli.command {
+        Some(Command::Verify {
+            year,
+            day,
+            answers,
+            record,
+        }) => {
+            verify(*year, *day, answers, *record, cli.format, pool(&cli));
+            return;
+        }
+        Some(Command::Bench(args)) => {
+            run_bench(args, cli.format);
+            return;
+        }
+        Some(Command::Fetch { year, day }) => {
+            fetch(*year, *day);
+            return;
+        }
+        Some(Command::New {
+            year,
+            day,
+            title,
+            example,
+        }) => {
+            new_day(*year, *day, title, example);
+            return;
+        }
+        Some(Command::Submit {
+            year,
+            day,
+            part,
+            log,
+            params,
+        }) => {
+            submit(*year, *day, *part, log, params);
+            return;
+        }
+        Some(Command::Watch(args)) => {
+            run_watch(args, cli.format);
+            return;
+        }
+        None => {}
+    }
 
     if cli.list_days {
-        print_available_days();
+        list_days(&cli);
+        return;
+    }
+
+    if cli.all {
+        run_all(&cli);
         return;
     }
 
+    if cli.step {
+        if cli.file_name.as_deref() == Some("-") {
+            exit_with(&Error::new(
+                "--step reads its commands from stdin, the input can't come from there",
+            ));
+        }
+        step::enable();
+    }
     let question = match cli.question {
         1 => Question::First,
         2 => Question::Second,
         _ => panic!("question"),
     };
     let year_n = match cli.year {
-        Some(y) if y < 2000 => y + 2000,
         Some(y) => y,
         None => YEARS.keys().max().unwrap().clone(),
     };
@@ -178,17 +703,79 @@ fn main() {
         print_available_days();
         return;
     }
-    let day = day.unwrap();
-    let input = if cli.test {
-        day.test_data()
+    let registration = day.unwrap();
+    let plain = cli.format == Format::Plain;
+    if plain {
+        println!(
+            "--- {} Day {}: {} ---",
+            registration.year, registration.day, registration.title
+        );
+    }
+    let day = registration.solution;
+    let overrides = overrides(&cli.params);
+    let questions = if cli.both {
+        vec![Question::First, Question::Second]
     } else {
-        must_read_file(&cli.file_name)
+        vec![question]
     };
+    if cli.test {
+        if plain {
+            println!("Running year {} day {} examples", year_n, day_n);
+        }
+        let runs = runner::run_examples(day, year_n, day_n, &questions, &overrides);
+        print_diagnostics(&runs);
+        print_runs(&runs, cli.format);
+        let id = DayId {
+            year: year_n,
+            day: day_n,
+        };
+        let unchecked = questions
+            .iter()
+            .filter(|&&q| !runner::has_examples(day, q))
+            .map(|&q| {
+                (
+                    id,
+                    format!("part {} has no example answer", runner::part(q)),
+                )
+            })
+            .collect_vec();
+        print_skipped(&unchecked, cli.format);
+        if runs.iter().any(|r| r.failed()) {
+            process::exit(1);
+        }
+        return;
+    }
+    let input = read_day_input(&cli.file_name, year_n, day_n);
+    // A single question in JSON is a table of one run as well.
+    if cli.both || !plain {
+        if plain {
+            println!("Running year {} day {}, both questions", year_n, day_n);
+        }
+        let runs = runner::run(day, year_n, day_n, &input, &questions, &overrides);
+        print_diagnostics(&runs);
+        print_runs(&runs, cli.format);
+        if runs.iter().any(|r| r.answer.is_err()) {
+            process::exit(1);
+        }
+        return;
+    }
     println!(
         "Running year {} day {}, {:?} question",
         year_n, day_n, question
     );
-    day.question(&input, question);
+    let params = day.params(question).merged(&overrides);
+    if !params.is_empty() {
+        println!("Parameters: {}", params);
+    }
+    let answer = match runner::catch_quietly(|| day.question(&input, question, &params)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(err)) => exit_with(&err),
+        Err(panic) => exit_with(&Error::new(panic)),
+    };
+    for diagnostic in answer.diagnostics.iter() {
+        trace::write(format_args!("{}\n", diagnostic));
+    }
+    println!("{}", answer);
 
     // hackerrank::main();
     // let input = must_read_file();